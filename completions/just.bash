@@ -10,7 +10,7 @@ _just() {
     else
         cur="${COMP_WORDS[COMP_CWORD]}"
         prev="${COMP_WORDS[COMP_CWORD-1]}"
-        words=$COMP_WORDS
+        words=("${COMP_WORDS[@]}")
         cword=$COMP_CWORD
     fi
 
@@ -30,12 +30,16 @@ _just() {
 
     case "${cmd}" in
         just)
-            opts="-E -n -g -f -q -u -v -d -c -e -l -s -h -V --alias-style --ceiling --check --chooser --clear-shell-args --color --command-color --cygpath --dotenv-filename --dotenv-path --dry-run --dump-format --explain --global-justfile --highlight --justfile --list-heading --list-prefix --list-submodules --no-aliases --no-deps --no-dotenv --no-highlight --one --quiet --allow-missing --set --shell --shell-arg --shell-command --tempdir --timestamp --timestamp-format --unsorted --unstable --verbose --working-directory --yes --changelog --choose --command --completions --dump --edit --evaluate --fmt --groups --init --list --man --request --show --summary --variables --help --version [ARGUMENTS]..."
+            opts="-E -n -g -j -f -q -u -v -d -c -e -l -s -h -V --alias-style --ceiling --check --chooser --clear-shell-args --color --command-color --cygpath --dotenv-filename --dotenv-path --dotenv-profile --dry-run --dump-format --events --events-file --explain --force --global-justfile --graph-eval --graph-format --highlight --interactive --jobs --keep-going --justfile --list-heading --list-prefix --list-submodules --no-aliases --no-config --no-deps --no-dotenv --no-highlight --no-timestamp --no-unsorted --one --plan --plan-eval --quiet --report --allow-missing --set --shell --shell-arg --shell-command --tempdir --timeout --timestamp --timestamp-format --unsorted --unstable --verbose --working-directory --yes --cache-clean --changelog --choose --command --complete --completions --dump --edit --evaluate --fmt --graph --groups --init --lint --list --lsp --man --request --show --show-config --summary --variables --watch --help --version [ARGUMENTS]..."
                 if [[ ${cur} == -* ]] ; then
-                    COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                    local options=$(just --complete "${words[@]:1:cword-1}" 2> /dev/null | \grep -oE -- '--[^ ]+')
+                    if [[ -z "${options}" ]]; then
+                        local options="${opts}"
+                    fi
+                    COMPREPLY=( $(compgen -W "${options}" -- "${cur}") )
                     return 0
                 else
-                    local recipes=$(just --summary 2> /dev/null)
+                    local recipes=$(just --complete "${words[@]:1:cword-1}" 2> /dev/null)
 
                     if echo "${cur}" | \grep -qF '/'; then
                         local path_prefix=$(echo "${cur}" | sed 's/[/][^/]*$/\//')
@@ -88,10 +92,34 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --dotenv-profile)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --dump-format)
                     COMPREPLY=($(compgen -W "json just" -- "${cur}"))
                     return 0
                     ;;
+                --events)
+                    COMPREPLY=($(compgen -W "json" -- "${cur}"))
+                    return 0
+                    ;;
+                --events-file)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --graph-format)
+                    COMPREPLY=($(compgen -W "dot mermaid" -- "${cur}"))
+                    return 0
+                    ;;
+                --jobs)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                -j)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --justfile)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -108,6 +136,10 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --report)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --set)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -124,6 +156,10 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --timeout)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --timestamp-format)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -144,6 +180,10 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --complete)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --completions)
                     COMPREPLY=($(compgen -W "bash elvish fish nushell powershell zsh" -- "${cur}"))
                     return 0
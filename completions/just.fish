@@ -2,7 +2,7 @@ function __fish_just_complete_recipes
         if string match -rq '(-f|--justfile)\s*=?(?<justfile>[^\s]+)' -- (string split -- ' -- ' (commandline -pc))[1]
           set -fx JUST_JUSTFILE "$justfile"
         end
-        printf "%s\n" (string split " " (just --summary))
+        printf "%s\n" (string split " " (just --complete (commandline -opc)[2..]))
 end
 
 # don't suggest files right off
@@ -30,18 +30,27 @@ yellow\t''"
 complete -c just -l cygpath -d 'Use binary at <CYGPATH> to convert between unix and Windows paths.' -r -F
 complete -c just -l dotenv-filename -d 'Search for environment file named <DOTENV-FILENAME> instead of `.env`' -r
 complete -c just -s E -l dotenv-path -d 'Load <DOTENV-PATH> as environment file instead of searching for one' -r -F
+complete -c just -l dotenv-profile -d 'Substitute <DOTENV-PROFILE> for `{{profile}}` in `dotenv-files` setting' -r
 complete -c just -l dump-format -d 'Dump justfile as <FORMAT>' -r -f -a "json\t''
 just\t''"
+complete -c just -l events -d 'Write run events to stderr as <FORMAT>, one per line' -r -f -a "json\t''"
+complete -c just -l events-file -d 'Write run events to <PATH> instead of stderr' -r -F
+complete -c just -l graph-format -d 'Print dependency graph as <FORMAT>' -r -f -a "dot\t''
+mermaid\t''"
+complete -c just -s j -l jobs -d 'Run up to <JOBS> recipes at once. Invocations and dependencies that do not depend on each other run concurrently when <JOBS> is greater than one.' -r
 complete -c just -s f -l justfile -d 'Use <JUSTFILE> as justfile' -r -F
 complete -c just -l list-heading -d 'Print <TEXT> before list' -r
 complete -c just -l list-prefix -d 'Print <TEXT> before each list item' -r
+complete -c just -l report -d 'Write a report of executed recipes to <PATH> in <FORMAT> [possible formats: junit]' -r
 complete -c just -l set -d 'Override <VARIABLE> with <VALUE>' -r
 complete -c just -l shell -d 'Invoke <SHELL> to run recipes' -r
 complete -c just -l shell-arg -d 'Invoke shell with <SHELL-ARG> as an argument' -r
 complete -c just -l tempdir -d 'Save temporary files to <TEMPDIR>.' -r -F
+complete -c just -l timeout -d 'Stop recipes without a `[timeout]` attribute after <DURATION>, e.g. `30s` or `5m`' -r
 complete -c just -l timestamp-format -d 'Timestamp format string' -r
 complete -c just -s d -l working-directory -d 'Use <WORKING-DIRECTORY> as working directory. --justfile must also be set' -r -F
 complete -c just -s c -l command -d 'Run an arbitrary command with the working directory, `.env`, overrides, and exports set' -r
+complete -c just -l complete -d 'Print completions for the argument following <WORDS>. For use by shell completion scripts.' -r
 complete -c just -l completions -d 'Print shell completion script for <SHELL>' -r -f -a "bash\t''
 elvish\t''
 fish\t''
@@ -55,14 +64,23 @@ complete -c just -l check -d 'Run `--fmt` in \'check\' mode. Exits with 0 if jus
 complete -c just -l clear-shell-args -d 'Clear shell arguments'
 complete -c just -s n -l dry-run -d 'Print what just would do without doing it'
 complete -c just -l explain -d 'Print recipe doc comment before running it'
+complete -c just -l force -d 'Run recipes with `[outputs]` even if their outputs are up to date'
 complete -c just -s g -l global-justfile -d 'Use global justfile'
+complete -c just -l graph-eval -d 'Evaluate dependency arguments when printing dependency graph'
 complete -c just -l highlight -d 'Highlight echoed recipe lines in bold'
+complete -c just -l interactive -d 'Prompt for arguments not given on the command line, if stdin is a terminal'
+complete -c just -l keep-going -d 'Keep running recipes that do not depend on failed recipes, and print a summary of passed, failed, and skipped recipes'
 complete -c just -l list-submodules -d 'List recipes in submodules'
 complete -c just -l no-aliases -d 'Don\'t show aliases in list'
+complete -c just -l no-config -d 'Don\'t load user or project config files'
 complete -c just -l no-deps -d 'Don\'t run recipe dependencies'
 complete -c just -l no-dotenv -d 'Don\'t load `.env` file'
 complete -c just -l no-highlight -d 'Don\'t highlight echoed recipe lines in bold'
+complete -c just -l no-timestamp -d 'Don\'t print recipe command timestamps, overriding config files'
+complete -c just -l no-unsorted -d 'Return list and summary entries sorted, overriding config files'
 complete -c just -l one -d 'Forbid multiple recipes from being invoked on the command line'
+complete -c just -l plan -d 'Print the recipes that would be run, without running them'
+complete -c just -l plan-eval -d 'Run backticks when printing plan'
 complete -c just -s q -l quiet -d 'Suppress all output'
 complete -c just -l allow-missing -d 'Ignore missing recipe and module errors'
 complete -c just -l shell-command -d 'Invoke <COMMAND> with the shell used to run recipe lines and backticks'
@@ -71,16 +89,22 @@ complete -c just -s u -l unsorted -d 'Return list and summary entries in source
 complete -c just -l unstable -d 'Enable unstable features'
 complete -c just -s v -l verbose -d 'Use verbose output'
 complete -c just -l yes -d 'Automatically confirm all recipes.'
+complete -c just -l cache-clean -d 'Remove cached content hashes of `[cache]` recipes'
 complete -c just -l changelog -d 'Print changelog'
-complete -c just -l choose -d 'Select one or more recipes to run using a binary chooser. If `--chooser` is not passed the chooser defaults to the value of $JUST_CHOOSER, falling back to `fzf`'
+complete -c just -l choose -d 'Select one or more recipes to run using a binary chooser. If `--chooser` is not passed the chooser defaults to the value of $JUST_CHOOSER, falling back to `fzf`, or a built-in chooser if `fzf` is not installed'
 complete -c just -l dump -d 'Print justfile'
 complete -c just -s e -l edit -d 'Edit justfile with editor given by $VISUAL or $EDITOR, falling back to `vim`'
 complete -c just -l evaluate -d 'Evaluate and print all variables. If a variable name is given as an argument, only print that variable\'s value.'
 complete -c just -l fmt -d 'Format and overwrite justfile'
+complete -c just -l graph -d 'Print recipe dependency graph. If recipes are given as arguments, only print recipes reachable from them.'
 complete -c just -l groups -d 'List recipe groups'
 complete -c just -l init -d 'Initialize new justfile in project root'
+complete -c just -l lint -d 'Print lint warnings and exit with failure if there are any'
+complete -c just -l lsp -d 'Run language server, communicating over stdin and stdout'
 complete -c just -l man -d 'Print man page'
+complete -c just -l show-config -d 'Print effective values of configurable options and where they were set'
 complete -c just -l summary -d 'List names of available recipes'
 complete -c just -l variables -d 'List names of variables'
+complete -c just -l watch -d 'Run recipes, then rerun them when the justfile, its imports and modules, or files matching their `[watch]` patterns change'
 complete -c just -s h -l help -d 'Print help'
 complete -c just -s V -l version -d 'Print version'
@@ -1,14 +1,17 @@
 use {super::*, CompileErrorKind::*};
 
+type FunctionKey<'src> = (*const Table<'src, UserFunction<'src>>, &'src str);
+
 #[derive(Default)]
 pub(crate) struct Analyzer<'run, 'src> {
   aliases: Table<'src, Alias<'src, Namepath<'src>>>,
   assignments: Vec<&'run Binding<'src, Expression<'src>>>,
+  functions: Table<'src, UserFunction<'src>>,
   modules: Table<'src, Justfile<'src>>,
   recipes: Vec<&'run Recipe<'src, UnresolvedDependency<'src>>>,
   sets: Table<'src, Set<'src>>,
   unexports: HashSet<String>,
-  warnings: Vec<Warning>,
+  warnings: Vec<Warning<'src>>,
 }
 
 impl<'run, 'src> Analyzer<'run, 'src> {
@@ -20,8 +23,9 @@ impl<'run, 'src> Analyzer<'run, 'src> {
     name: Option<Name<'src>>,
     paths: &HashMap<PathBuf, PathBuf>,
     root: &Path,
+    parent: &FunctionScope<'_, 'src>,
   ) -> CompileResult<'src, Justfile<'src>> {
-    Self::default().justfile(asts, doc, groups, loaded, name, paths, root)
+    Self::default().justfile(asts, doc, groups, loaded, name, paths, root, parent)
   }
 
   fn justfile(
@@ -33,9 +37,12 @@ impl<'run, 'src> Analyzer<'run, 'src> {
     name: Option<Name<'src>>,
     paths: &HashMap<PathBuf, PathBuf>,
     root: &Path,
+    parent: &FunctionScope<'_, 'src>,
   ) -> CompileResult<'src, Justfile<'src>> {
     let mut definitions = HashMap::new();
     let mut imports = HashSet::new();
+    let mut module = Vec::new();
+    let mut modules = Vec::new();
     let mut unstable_features = BTreeSet::new();
 
     let mut stack = Vec::new();
@@ -43,6 +50,7 @@ impl<'run, 'src> Analyzer<'run, 'src> {
     stack.push(ast);
 
     while let Some(ast) = stack.pop() {
+      module.push(ast);
       unstable_features.extend(&ast.unstable_features);
 
       for item in &ast.items {
@@ -51,10 +59,14 @@ impl<'run, 'src> Analyzer<'run, 'src> {
             Self::define(&mut definitions, alias.name, "alias", false)?;
             self.aliases.insert(alias.clone());
           }
-          Item::Assignment(assignment) => {
+          Item::Assignment { assignment, .. } => {
             self.assignments.push(assignment);
           }
           Item::Comment(_) => (),
+          Item::Function(function) => {
+            self.analyze_function(function)?;
+            self.functions.insert(function.clone());
+          }
           Item::Import { absolute, .. } => {
             if let Some(absolute) = absolute {
               if imports.insert(absolute) {
@@ -71,15 +83,7 @@ impl<'run, 'src> Analyzer<'run, 'src> {
           } => {
             if let Some(absolute) = absolute {
               Self::define(&mut definitions, *name, "module", false)?;
-              self.modules.insert(Self::analyze(
-                asts,
-                doc.clone(),
-                groups.as_slice(),
-                loaded,
-                Some(*name),
-                paths,
-                absolute,
-              )?);
+              modules.push((absolute, doc, groups, *name));
             }
           }
           Item::Recipe(recipe) => {
@@ -88,7 +92,7 @@ impl<'run, 'src> Analyzer<'run, 'src> {
               self.recipes.push(recipe);
             }
           }
-          Item::Set(set) => {
+          Item::Set { set, .. } => {
             self.analyze_set(set)?;
             self.sets.insert(set.clone());
           }
@@ -105,6 +109,24 @@ impl<'run, 'src> Analyzer<'run, 'src> {
       self.warnings.extend(ast.warnings.iter().cloned());
     }
 
+    // submodules are analyzed once all functions in this module are known,
+    // so that they can call them and the functions of this module's ancestors
+    for (absolute, doc, groups, name) in modules {
+      let module = Self::analyze(
+        asts,
+        doc.clone(),
+        groups.as_slice(),
+        loaded,
+        Some(name),
+        paths,
+        absolute,
+        &parent.child(&self.functions, None),
+      )?;
+      self.modules.insert(module);
+    }
+
+    let functions = parent.child(&self.functions, Some(&self.modules));
+
     let settings = Settings::from_table(self.sets);
 
     let mut assignments: Table<'src, Assignment<'src>> = Table::default();
@@ -153,6 +175,23 @@ impl<'run, 'src> Analyzer<'run, 'src> {
       deduplicated_recipes,
     )?;
 
+    for function in self.functions.values() {
+      Self::analyze_calls(&functions, &function.body)?;
+    }
+
+    for assignment in assignments.values() {
+      Self::analyze_calls(&functions, &assignment.value)?;
+    }
+
+    for recipe in recipes.values() {
+      Self::analyze_recipe_calls(&functions, recipe, &settings)?;
+    }
+
+    let mut resolved = BTreeSet::new();
+    for function in self.functions.values() {
+      Self::resolve_function(&functions, function, &mut resolved, &mut Vec::new())?;
+    }
+
     let mut aliases = Table::new();
     while let Some(alias) = self.aliases.pop() {
       aliases.insert(Self::resolve_alias(&self.modules, &recipes, alias)?);
@@ -169,6 +208,10 @@ impl<'run, 'src> Analyzer<'run, 'src> {
       unstable_features.insert(UnstableFeature::ScriptInterpreterSetting);
     }
 
+    self
+      .warnings
+      .extend(Linter::lint(&module, &settings, &assignments));
+
     let source = root.to_owned();
     let root = paths.get(root).unwrap();
 
@@ -204,6 +247,7 @@ impl<'run, 'src> Analyzer<'run, 'src> {
       assignments,
       default,
       doc: doc.filter(|doc| !doc.is_empty()),
+      functions: self.functions,
       groups: groups.into(),
       loaded: loaded.into(),
       module_path: ast.module_path.clone(),
@@ -261,6 +305,10 @@ impl<'run, 'src> Analyzer<'run, 'src> {
 
       parameters.insert(parameter.name.lexeme());
 
+      if parameter.kind.is_option() {
+        continue;
+      }
+
       if parameter.default.is_some() {
         passed_default = true;
       } else if passed_default && parameter.is_required() {
@@ -303,6 +351,170 @@ impl<'run, 'src> Analyzer<'run, 'src> {
     Ok(())
   }
 
+  fn analyze_function(&self, function: &UserFunction<'src>) -> CompileResult<'src> {
+    let name = function.name.lexeme();
+
+    if function::get(name).is_some() {
+      return Err(
+        function
+          .name
+          .error(BuiltinFunctionRedefinition { function: name }),
+      );
+    }
+
+    if let Some(original) = self.functions.get(name) {
+      return Err(function.name.error(DuplicateFunction {
+        function: name,
+        first: original.name.line,
+      }));
+    }
+
+    let mut parameters = BTreeSet::new();
+
+    for parameter in &function.parameters {
+      if !parameters.insert(parameter.lexeme()) {
+        return Err(parameter.error(DuplicateFunctionParameter {
+          function: name,
+          parameter: parameter.lexeme(),
+        }));
+      }
+    }
+
+    for variable in function.body.variables() {
+      let name = variable.lexeme();
+
+      if !parameters.contains(name) && !constants().contains_key(name) {
+        return Err(variable.error(UndefinedVariable { variable: name }));
+      }
+    }
+
+    Ok(())
+  }
+
+  fn analyze_calls(
+    functions: &FunctionScope<'_, 'src>,
+    expression: &Expression<'src>,
+  ) -> CompileResult<'src> {
+    for expression in expression.walk() {
+      let Expression::Call { thunk } = expression else {
+        continue;
+      };
+
+      let (path, name, found) = match thunk {
+        Thunk::UserDefined { name, args, path } => (path.as_slice(), *name, args.len()),
+        Thunk::Filter { function, .. } | Thunk::Map { function, .. } => (&[][..], *function, 1),
+        _ => continue,
+      };
+
+      let expected = if let Some((_scope, function)) = functions.resolve(path, name.lexeme()) {
+        function.parameters.len()..=function.parameters.len()
+      } else if let (true, Some(function)) = (path.is_empty(), function::get(name.lexeme())) {
+        function.argc()
+      } else {
+        return Err(name.error(UnknownFunction {
+          function: name.lexeme(),
+        }));
+      };
+
+      if !expected.contains(&found) {
+        return Err(name.error(FunctionArgumentCountMismatch {
+          function: name.lexeme(),
+          found,
+          expected,
+        }));
+      }
+    }
+
+    Ok(())
+  }
+
+  fn analyze_recipe_calls(
+    functions: &FunctionScope<'_, 'src>,
+    recipe: &Recipe<'src>,
+    settings: &Settings,
+  ) -> CompileResult<'src> {
+    for parameter in &recipe.parameters {
+      if let Some(default) = &parameter.default {
+        Self::analyze_calls(functions, default)?;
+      }
+    }
+
+    for dependency in &recipe.dependencies {
+      for argument in &dependency.arguments {
+        Self::analyze_calls(functions, argument)?;
+      }
+    }
+
+    for (_key, value) in recipe.env() {
+      Self::analyze_calls(functions, value)?;
+    }
+
+    for line in &recipe.body {
+      if line.is_comment() && settings.ignore_comments {
+        continue;
+      }
+
+      for fragment in &line.fragments {
+        if let Fragment::Interpolation { expression, .. } = fragment {
+          Self::analyze_calls(functions, expression)?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Check that calls made by `function`, which is defined in the innermost
+  /// module of `scope`, are not circular
+  ///
+  /// Functions are identified by the module they are defined in and their
+  /// name, since functions in different modules may share a name. Calls which
+  /// cannot be resolved yet, because they are made by functions in ancestor
+  /// modules to their submodules, are checked when those ancestors are
+  /// analyzed.
+  fn resolve_function(
+    scope: &FunctionScope<'_, 'src>,
+    function: &UserFunction<'src>,
+    resolved: &mut BTreeSet<FunctionKey<'src>>,
+    stack: &mut Vec<FunctionKey<'src>>,
+  ) -> CompileResult<'src> {
+    let key = (scope.innermost(), function.name.lexeme());
+
+    if resolved.contains(&key) {
+      return Ok(());
+    }
+
+    stack.push(key);
+
+    for (path, callee) in UserFunction::calls(&function.body) {
+      let Some((callee_scope, callee_function)) = scope.resolve(path, callee.lexeme()) else {
+        continue;
+      };
+
+      let callee_key = (callee_scope.innermost(), callee.lexeme());
+
+      if let Some(start) = stack.iter().position(|key| *key == callee_key) {
+        let mut circle = stack[start..]
+          .iter()
+          .map(|(_module, name)| *name)
+          .collect::<Vec<&str>>();
+        circle.push(callee.lexeme());
+        return Err(callee.error(CircularFunctionCall {
+          function: callee.lexeme(),
+          circle,
+        }));
+      }
+
+      Self::resolve_function(&callee_scope, callee_function, resolved, stack)?;
+    }
+
+    stack.pop();
+
+    resolved.insert(key);
+
+    Ok(())
+  }
+
   fn analyze_set(&self, set: &Set<'src>) -> CompileResult<'src> {
     if let Some(original) = self.sets.get(set.name.lexeme()) {
       return Err(set.name.error(DuplicateSet {
@@ -461,4 +673,144 @@ mod tests {
     width:  6,
     kind:   ExtraLeadingWhitespace,
   }
+
+  analysis_error! {
+    name:   unknown_function,
+    input:  "a := foo()",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  3,
+    kind:   UnknownFunction{function: "foo"},
+  }
+
+  analysis_error! {
+    name:   unknown_function_in_interpolation,
+    input:  "a:\n echo {{bar()}}",
+    offset: 11,
+    line:   1,
+    column: 8,
+    width:  3,
+    kind:   UnknownFunction{function: "bar"},
+  }
+
+  analysis_error! {
+    name:   unknown_function_in_default,
+    input:  "a f=baz():",
+    offset: 4,
+    line:   0,
+    column: 4,
+    width:  3,
+    kind:   UnknownFunction{function: "baz"},
+  }
+
+  analysis_error! {
+    name:   user_function_argument_count_mismatch,
+    input:  "fn f(a) := a\nx := f('a', 'b')",
+    offset: 18,
+    line:   1,
+    column: 5,
+    width:  1,
+    kind:   FunctionArgumentCountMismatch{function: "f", found: 2, expected: 1..=1},
+  }
+
+  analysis_error! {
+    name:   user_function_builtin_redefinition,
+    input:  "fn uppercase(s) := s",
+    offset: 3,
+    line:   0,
+    column: 3,
+    width:  9,
+    kind:   BuiltinFunctionRedefinition{function: "uppercase"},
+  }
+
+  analysis_error! {
+    name:   user_function_calls_itself,
+    input:  "fn f(a) := f(a)",
+    offset: 11,
+    line:   0,
+    column: 11,
+    width:  1,
+    kind:   CircularFunctionCall{function: "f", circle: vec!["f", "f"]},
+  }
+
+  analysis_error! {
+    name:   user_function_circular_call,
+    input:  "fn f(a) := g(a)\nfn g(a) := f(a)",
+    offset: 27,
+    line:   1,
+    column: 11,
+    width:  1,
+    kind:   CircularFunctionCall{function: "f", circle: vec!["f", "g", "f"]},
+  }
+
+  analysis_error! {
+    name:   user_function_duplicate,
+    input:  "fn f() := ''\nfn f() := ''",
+    offset: 16,
+    line:   1,
+    column: 3,
+    width:  1,
+    kind:   DuplicateFunction{function: "f", first: 0},
+  }
+
+  analysis_error! {
+    name:   user_function_duplicate_parameter,
+    input:  "fn f(a, a) := a",
+    offset: 8,
+    line:   0,
+    column: 8,
+    width:  1,
+    kind:   DuplicateFunctionParameter{function: "f", parameter: "a"},
+  }
+
+  analysis_error! {
+    name:   user_function_undefined_variable,
+    input:  "x := 'a'\nfn f(a) := a + x",
+    offset: 24,
+    line:   1,
+    column: 15,
+    width:  1,
+    kind:   UndefinedVariable{variable: "x"},
+  }
+
+  analysis_error! {
+    name:   map_unknown_function,
+    input:  "x := map(['a'], foo)",
+    offset: 16,
+    line:   0,
+    column: 16,
+    width:  3,
+    kind:   UnknownFunction{function: "foo"},
+  }
+
+  analysis_error! {
+    name:   map_function_argument_count_mismatch,
+    input:  "fn f(a, b) := a\nx := filter(['a'], f)",
+    offset: 35,
+    line:   1,
+    column: 19,
+    width:  1,
+    kind:   FunctionArgumentCountMismatch{function: "f", found: 1, expected: 2..=2},
+  }
+
+  analysis_error! {
+    name:   map_builtin_argument_count_mismatch,
+    input:  "x := map(['a'], replace)",
+    offset: 16,
+    line:   0,
+    column: 16,
+    width:  7,
+    kind:   FunctionArgumentCountMismatch{function: "replace", found: 1, expected: 3..=3},
+  }
+
+  analysis_error! {
+    name:   map_user_function_calls_itself,
+    input:  "fn f(a) := map(a, f)",
+    offset: 18,
+    line:   0,
+    column: 18,
+    width:  1,
+    kind:   CircularFunctionCall{function: "f", circle: vec!["f", "f"]},
+  }
 }
@@ -19,25 +19,30 @@ use super::*;
 /// module.
 pub(crate) struct ArgumentParser<'src: 'run, 'run> {
   arguments: &'run [&'run str],
+  config: &'run Config,
   next: usize,
   root: &'run Justfile<'src>,
 }
 
 #[derive(Debug, PartialEq)]
-pub(crate) struct ArgumentGroup<'run> {
-  pub(crate) arguments: Vec<&'run str>,
+pub(crate) struct ArgumentGroup {
+  pub(crate) arguments: Vec<String>,
+  pub(crate) help: bool,
+  pub(crate) options: BTreeMap<String, String>,
   pub(crate) path: Vec<String>,
 }
 
 impl<'src: 'run, 'run> ArgumentParser<'src, 'run> {
   pub(crate) fn parse_arguments(
+    config: &'run Config,
     root: &'run Justfile<'src>,
     arguments: &'run [&'run str],
-  ) -> RunResult<'src, Vec<ArgumentGroup<'run>>> {
+  ) -> RunResult<'src, Vec<ArgumentGroup>> {
     let mut groups = Vec::new();
 
     let mut invocation_parser = Self {
       arguments,
+      config,
       next: 0,
       root,
     };
@@ -53,54 +58,182 @@ impl<'src: 'run, 'run> ArgumentParser<'src, 'run> {
     Ok(groups)
   }
 
-  fn parse_group(&mut self) -> RunResult<'src, ArgumentGroup<'run>> {
-    let (recipe, path) = if let Some(next) = self.next() {
+  fn parse_group(&mut self) -> RunResult<'src, ArgumentGroup> {
+    let (module, recipe, path) = if let Some(next) = self.next() {
       if next.contains(':') {
         let module_path =
           ModulePath::try_from([next].as_slice()).map_err(|()| Error::UnknownRecipe {
             recipe: next.into(),
             suggestion: None,
           })?;
-        let (recipe, path, _) = self.resolve_recipe(true, &module_path.path)?;
+        let (module, recipe, path, _) = self.resolve_recipe(true, &module_path.path)?;
         self.next += 1;
-        (recipe, path)
+        (module, recipe, path)
       } else {
-        let (recipe, path, consumed) = self.resolve_recipe(false, self.rest())?;
+        let (module, recipe, path, consumed) = self.resolve_recipe(false, self.rest())?;
         self.next += consumed;
-        (recipe, path)
+        (module, recipe, path)
       }
     } else {
-      let (recipe, path, consumed) = self.resolve_recipe(false, self.rest())?;
+      let (module, recipe, path, consumed) = self.resolve_recipe(false, self.rest())?;
       assert_eq!(consumed, 0);
-      (recipe, path)
+      (module, recipe, path)
     };
 
+    let options = recipe
+      .parameters
+      .iter()
+      .any(|parameter| parameter.kind.is_option());
+
+    // `--help` is treated as a request for the recipe's usage if it is the
+    // first argument, unless the recipe has an option named `help`, and can
+    // be passed to the recipe as an ordinary argument after `--`
+    let help = !recipe
+      .parameters
+      .iter()
+      .any(|parameter| parameter.kind.is_option() && parameter.name.lexeme() == "help");
+
+    if help && self.next() == Some("--help") {
+      self.next += 1;
+      return Ok(ArgumentGroup {
+        arguments: Vec::new(),
+        help: true,
+        options: BTreeMap::new(),
+        path,
+      });
+    }
+
+    if options {
+      let (arguments, options) = self.parse_options(module, recipe)?;
+      return Ok(ArgumentGroup {
+        arguments,
+        help: false,
+        options,
+        path,
+      });
+    }
+
+    if self.next() == Some("--") {
+      self.next += 1;
+    }
+
     let rest = self.rest();
 
     let argument_range = recipe.argument_range();
     let argument_count = cmp::min(rest.len(), recipe.max_arguments());
-    if !argument_range.range_contains(&argument_count) {
+    if !argument_range.range_contains(&argument_count) && !recipe.prompts(self.config) {
       return Err(Error::ArgumentCountMismatch {
         recipe: recipe.name(),
-        parameters: recipe.parameters.clone(),
         found: rest.len(),
         min: recipe.min_arguments(),
         max: recipe.max_arguments(),
+        usage: Box::new(RecipeUsage::new(module, recipe)),
       });
     }
 
-    let arguments = rest[..argument_count].to_vec();
+    let arguments = rest[..argument_count]
+      .iter()
+      .copied()
+      .map(str::to_owned)
+      .collect();
 
     self.next += argument_count;
 
-    Ok(ArgumentGroup { arguments, path })
+    Ok(ArgumentGroup {
+      arguments,
+      help: false,
+      options: BTreeMap::new(),
+      path,
+    })
+  }
+
+  /// Parse the arguments of a recipe with options. Arguments starting with
+  /// `--` are options, which may be interleaved with positional arguments,
+  /// until an argument of `--`, after which all arguments are positional.
+  /// Positional arguments and options, by name, are returned separately.
+  fn parse_options(
+    &mut self,
+    module: &Justfile<'src>,
+    recipe: &Recipe<'src>,
+  ) -> RunResult<'src, (Vec<String>, BTreeMap<String, String>)> {
+    let max = recipe.max_arguments();
+
+    let mut positional = Vec::new();
+    let mut options = BTreeMap::new();
+    let mut separated = false;
+
+    while let Some(argument) = self.next() {
+      if argument == "--" && !separated {
+        separated = true;
+        self.next += 1;
+        continue;
+      }
+
+      let Some(option) = argument.strip_prefix("--").filter(|_| !separated) else {
+        if positional.len() == max {
+          break;
+        }
+        positional.push(argument.to_owned());
+        self.next += 1;
+        continue;
+      };
+
+      self.next += 1;
+
+      let (name, value) = match option.split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (option, None),
+      };
+
+      let parameter = recipe
+        .parameters
+        .iter()
+        .find(|parameter| parameter.kind.is_option() && parameter.name.lexeme() == name)
+        .ok_or_else(|| Error::UnknownOption {
+          recipe: recipe.name(),
+          option: name.into(),
+        })?;
+
+      let value = match (parameter.kind, value) {
+        (ParameterKind::Flag, None) => "true",
+        (ParameterKind::Flag, Some(_)) => {
+          return Err(Error::FlagValue {
+            recipe: recipe.name(),
+            flag: parameter.name.lexeme(),
+          })
+        }
+        (_, Some(value)) => value,
+        (_, None) => {
+          let value = self.next().ok_or_else(|| Error::OptionMissingValue {
+            recipe: recipe.name(),
+            option: parameter.name.lexeme(),
+          })?;
+          self.next += 1;
+          value
+        }
+      };
+
+      options.insert(name.to_owned(), value.to_owned());
+    }
+
+    if !recipe.argument_range().range_contains(&positional.len()) && !recipe.prompts(self.config) {
+      return Err(Error::ArgumentCountMismatch {
+        recipe: recipe.name(),
+        found: positional.len(),
+        min: recipe.min_arguments(),
+        max: recipe.max_arguments(),
+        usage: Box::new(RecipeUsage::new(module, recipe)),
+      });
+    }
+
+    Ok((positional, options))
   }
 
   fn resolve_recipe(
     &self,
     module_path: bool,
     args: &[impl AsRef<str>],
-  ) -> RunResult<'src, (&'run Recipe<'src>, Vec<String>, usize)> {
+  ) -> RunResult<'src, (&'run Justfile<'src>, &'run Recipe<'src>, Vec<String>, usize)> {
     let mut current = self.root;
     let mut path = Vec::new();
 
@@ -121,7 +254,7 @@ impl<'src: 'run, 'run> ArgumentParser<'src, 'run> {
             },
           });
         }
-        return Ok((recipe, path, i + 1));
+        return Ok((current, recipe, path, i + 1));
       } else {
         if module_path && i + 1 < args.len() {
           return Err(Error::UnknownSubmodule {
@@ -141,9 +274,11 @@ impl<'src: 'run, 'run> ArgumentParser<'src, 'run> {
     }
 
     if let Some(recipe) = &current.default {
-      recipe.check_can_be_default_recipe()?;
+      if !recipe.prompts(self.config) {
+        recipe.check_can_be_default_recipe()?;
+      }
       path.push(recipe.name().into());
-      Ok((recipe, path, args.len()))
+      Ok((current, recipe, path, args.len()))
     } else if current.recipes.is_empty() {
       Err(Error::NoRecipes)
     } else {
@@ -181,10 +316,12 @@ mod tests {
     let justfile = testing::compile("foo:");
 
     assert_eq!(
-      ArgumentParser::parse_arguments(&justfile, &["foo"]).unwrap(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo"]).unwrap(),
       vec![ArgumentGroup {
         path: vec!["foo".into()],
-        arguments: Vec::new()
+        arguments: Vec::new(),
+        help: false,
+        options: BTreeMap::new(),
       }],
     );
   }
@@ -194,10 +331,12 @@ mod tests {
     let justfile = testing::compile("foo bar:");
 
     assert_eq!(
-      ArgumentParser::parse_arguments(&justfile, &["foo", "baz"]).unwrap(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo", "baz"]).unwrap(),
       vec![ArgumentGroup {
         path: vec!["foo".into()],
-        arguments: vec!["baz"],
+        arguments: vec!["baz".into()],
+        help: false,
+        options: BTreeMap::new(),
       }],
     );
   }
@@ -207,7 +346,7 @@ mod tests {
     let justfile = testing::compile("foo bar:");
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&justfile, &["foo"]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo"]).unwrap_err(),
       Error::ArgumentCountMismatch {
         recipe: "foo",
         found: 0,
@@ -223,7 +362,7 @@ mod tests {
     let justfile = testing::compile("foo:");
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&justfile, &["bar"]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["bar"]).unwrap_err(),
       Error::UnknownRecipe {
         recipe,
         suggestion: None
@@ -236,7 +375,7 @@ mod tests {
     let justfile = testing::compile("foo:");
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&justfile, &["bar", "baz"]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["bar", "baz"]).unwrap_err(),
       Error::UnknownRecipe {
         recipe,
         suggestion: None
@@ -255,10 +394,17 @@ mod tests {
     let compilation = Compiler::compile(&loader, &path).unwrap();
 
     assert_eq!(
-      ArgumentParser::parse_arguments(&compilation.justfile, &["foo", "bar"]).unwrap(),
+      ArgumentParser::parse_arguments(
+        &testing::config(&[]),
+        &compilation.justfile,
+        &["foo", "bar"]
+      )
+      .unwrap(),
       vec![ArgumentGroup {
         path: vec!["foo".into(), "bar".into()],
-        arguments: Vec::new()
+        arguments: Vec::new(),
+        help: false,
+        options: BTreeMap::new(),
       }],
     );
   }
@@ -274,7 +420,7 @@ mod tests {
     let compilation = Compiler::compile(&loader, &path).unwrap();
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&compilation.justfile, &["foo", "zzz"]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &compilation.justfile, &["foo", "zzz"]).unwrap_err(),
       Error::UnknownRecipe {
         recipe,
         suggestion: None
@@ -292,7 +438,7 @@ mod tests {
     let compilation = Compiler::compile(&loader, &tempdir.path().join("justfile")).unwrap();
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&compilation.justfile, &["foo::zzz"]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &compilation.justfile, &["foo::zzz"]).unwrap_err(),
       Error::UnknownRecipe {
         recipe,
         suggestion: None
@@ -310,7 +456,7 @@ mod tests {
     let compilation = Compiler::compile(&loader, &tempdir.path().join("justfile")).unwrap();
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&compilation.justfile, &["foo::bar::baz"]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &compilation.justfile, &["foo::bar::baz"]).unwrap_err(),
       Error::ExpectedSubmoduleButFoundRecipe {
         path,
       } if path == "foo::bar",
@@ -326,7 +472,8 @@ mod tests {
     let compilation = Compiler::compile(&loader, &tempdir.path().join("justfile")).unwrap();
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&compilation.justfile, &[]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &compilation.justfile, &[])
+        .unwrap_err(),
       Error::NoRecipes,
     );
   }
@@ -340,7 +487,8 @@ mod tests {
     let compilation = Compiler::compile(&loader, &tempdir.path().join("justfile")).unwrap();
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&compilation.justfile, &[]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &compilation.justfile, &[])
+        .unwrap_err(),
       Error::DefaultRecipeRequiresArguments {
         recipe: "foo",
         min_arguments: 1,
@@ -358,7 +506,8 @@ mod tests {
     let compilation = Compiler::compile(&loader, &tempdir.path().join("justfile")).unwrap();
 
     assert_matches!(
-      ArgumentParser::parse_arguments(&compilation.justfile, &[]).unwrap_err(),
+      ArgumentParser::parse_arguments(&testing::config(&[]), &compilation.justfile, &[])
+        .unwrap_err(),
       Error::NoDefaultRecipe,
     );
   }
@@ -380,6 +529,7 @@ BAZ +Z:
 
     assert_eq!(
       ArgumentParser::parse_arguments(
+        &testing::config(&[]),
         &justfile,
         &["BAR", "0", "FOO", "1", "2", "BAZ", "3", "4", "5"]
       )
@@ -387,17 +537,216 @@ BAZ +Z:
       vec![
         ArgumentGroup {
           path: vec!["BAR".into()],
-          arguments: vec!["0"],
+          arguments: vec!["0".into()],
+          help: false,
+          options: BTreeMap::new(),
         },
         ArgumentGroup {
           path: vec!["FOO".into()],
-          arguments: vec!["1", "2"],
+          arguments: vec!["1".into(), "2".into()],
+          help: false,
+          options: BTreeMap::new(),
         },
         ArgumentGroup {
           path: vec!["BAZ".into()],
-          arguments: vec!["3", "4", "5"],
+          arguments: vec!["3".into(), "4".into(), "5".into()],
+          help: false,
+          options: BTreeMap::new(),
         },
       ],
     );
   }
+
+  #[test]
+  fn options() {
+    let justfile = testing::compile(
+      "
+foo --dry --region='us' target:
+bar:
+",
+    );
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(
+        &testing::config(&[]),
+        &justfile,
+        &["foo", "--region", "eu", "web", "--dry", "bar"]
+      )
+      .unwrap(),
+      vec![
+        ArgumentGroup {
+          path: vec!["foo".into()],
+          arguments: vec!["web".into()],
+          help: false,
+          options: [
+            ("dry".into(), "true".into()),
+            ("region".into(), "eu".into())
+          ]
+          .into(),
+        },
+        ArgumentGroup {
+          path: vec!["bar".into()],
+          arguments: Vec::new(),
+          help: false,
+          options: BTreeMap::new(),
+        },
+      ],
+    );
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(
+        &testing::config(&[]),
+        &justfile,
+        &["foo", "--region=eu", "web"]
+      )
+      .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["foo".into()],
+        arguments: vec!["web".into()],
+        help: false,
+        options: [("region".into(), "eu".into())].into(),
+      }],
+    );
+  }
+
+  #[test]
+  fn options_end_at_separator() {
+    let justfile = testing::compile("foo --dry *args:");
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(
+        &testing::config(&[]),
+        &justfile,
+        &["foo", "--dry", "--", "--dry", "--", "--bar=baz"]
+      )
+      .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["foo".into()],
+        arguments: vec!["--dry".into(), "--".into(), "--bar=baz".into()],
+        help: false,
+        options: [("dry".into(), "true".into())].into(),
+      }],
+    );
+  }
+
+  #[test]
+  fn options_are_only_parsed_for_recipes_with_options() {
+    let justfile = testing::compile("foo *args:");
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo", "--release"])
+        .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["foo".into()],
+        arguments: vec!["--release".into()],
+        help: false,
+        options: BTreeMap::new(),
+      }],
+    );
+  }
+
+  #[test]
+  fn help() {
+    let justfile = testing::compile("foo:\nbar x:\nbaz *args:\nqux --release *args:");
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo", "--help"])
+        .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["foo".into()],
+        arguments: Vec::new(),
+        help: true,
+        options: BTreeMap::new(),
+      }],
+    );
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["bar", "--help"])
+        .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["bar".into()],
+        arguments: Vec::new(),
+        help: true,
+        options: BTreeMap::new(),
+      }],
+    );
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["baz", "--help"])
+        .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["baz".into()],
+        arguments: Vec::new(),
+        help: true,
+        options: BTreeMap::new(),
+      }],
+    );
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["bar", "--", "--help"])
+        .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["bar".into()],
+        arguments: vec!["--help".into()],
+        help: false,
+        options: BTreeMap::new(),
+      }],
+    );
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["baz", "--", "--help"])
+        .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["baz".into()],
+        arguments: vec!["--help".into()],
+        help: false,
+        options: BTreeMap::new(),
+      }],
+    );
+
+    assert_eq!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["qux", "--help"])
+        .unwrap(),
+      vec![ArgumentGroup {
+        path: vec!["qux".into()],
+        arguments: Vec::new(),
+        help: true,
+        options: BTreeMap::new(),
+      }],
+    );
+  }
+
+  #[test]
+  fn option_errors() {
+    let justfile = testing::compile("foo --dry --region='us':");
+
+    assert_matches!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo", "--bar"]).unwrap_err(),
+      Error::UnknownOption { recipe: "foo", option } if option == "bar",
+    );
+
+    assert_matches!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo", "--bar=baz"])
+        .unwrap_err(),
+      Error::UnknownOption { recipe: "foo", option } if option == "bar",
+    );
+
+    assert_matches!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo", "--dry=false"])
+        .unwrap_err(),
+      Error::FlagValue {
+        recipe: "foo",
+        flag: "dry",
+      },
+    );
+
+    assert_matches!(
+      ArgumentParser::parse_arguments(&testing::config(&[]), &justfile, &["foo", "--region"])
+        .unwrap_err(),
+      Error::OptionMissingValue {
+        recipe: "foo",
+        option: "region",
+      },
+    );
+  }
 }
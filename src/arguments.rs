@@ -0,0 +1,27 @@
+use super::*;
+
+/// The arguments of a recipe invocation
+///
+/// Options are kept separately from positional arguments, so that a
+/// positional argument which looks like an option, for example one passed as
+/// a dependency argument or after `--` on the command line, is never treated
+/// as one.
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct Arguments {
+  /// Options and flags, by name, with flags having the value `true`
+  pub(crate) options: BTreeMap<String, String>,
+  pub(crate) positional: Vec<String>,
+  /// Indices of positional arguments which are derived from secrets, and so
+  /// are bound to secret parameters
+  pub(crate) secret: BTreeSet<usize>,
+}
+
+impl From<Vec<String>> for Arguments {
+  fn from(positional: Vec<String>) -> Self {
+    Self {
+      options: BTreeMap::new(),
+      positional,
+      secret: BTreeSet::new(),
+    }
+  }
+}
@@ -3,6 +3,23 @@ use super::*;
 /// An assignment, e.g `foo := bar`
 pub(crate) type Assignment<'src> = Binding<'src, Expression<'src>>;
 
+impl Assignment<'_> {
+  /// A copy of this assignment with its value replaced with `***` if it is a
+  /// secret string literal
+  pub(crate) fn redacted(&self) -> Self {
+    let mut redacted = self.clone();
+
+    if let (true, Expression::StringLiteral { string_literal }) =
+      (redacted.secret, &mut redacted.value)
+    {
+      string_literal.cooked = secrets::REDACTED.into();
+      string_literal.raw = secrets::REDACTED;
+    }
+
+    redacted
+  }
+}
+
 impl Display for Assignment<'_> {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     if self.private {
@@ -13,6 +30,10 @@ impl Display for Assignment<'_> {
       write!(f, "export ")?;
     }
 
+    if self.secret {
+      write!(f, "secret ")?;
+    }
+
     write!(f, "{} := {}", self.name, self.value)
   }
 }
@@ -8,7 +8,7 @@ pub(crate) struct Ast<'src> {
   pub(crate) items: Vec<Item<'src>>,
   pub(crate) module_path: String,
   pub(crate) unstable_features: BTreeSet<UnstableFeature>,
-  pub(crate) warnings: Vec<Warning>,
+  pub(crate) warnings: Vec<Warning<'src>>,
   pub(crate) working_directory: PathBuf,
 }
 
@@ -1,41 +1,76 @@
 use super::*;
 
-#[derive(
-  EnumDiscriminants, PartialEq, Debug, Clone, Serialize, Ord, PartialOrd, Eq, IntoStaticStr,
-)]
+#[derive(EnumDiscriminants, Debug, Clone, Serialize, IntoStaticStr)]
+#[derive_where(PartialEq, Ord, PartialOrd, Eq)]
 #[strum(serialize_all = "kebab-case")]
 #[serde(rename_all = "kebab-case")]
 #[strum_discriminants(name(AttributeDiscriminant))]
 #[strum_discriminants(derive(EnumString, Ord, PartialOrd))]
 #[strum_discriminants(strum(serialize_all = "kebab-case"))]
 pub(crate) enum Attribute<'src> {
+  Allow(Vec<StringLiteral<'src>>),
+  Cache,
   Confirm(Option<StringLiteral<'src>>),
   Default,
   Doc(Option<StringLiteral<'src>>),
+  Env(
+    StringLiteral<'src>,
+    #[derive_where(skip(EqHashOrd))] Expression<'src>,
+  ),
   ExitMessage,
   Extension(StringLiteral<'src>),
+  Finally(Vec<StringLiteral<'src>>),
   Group(StringLiteral<'src>),
+  Inputs(Vec<StringLiteral<'src>>),
   Linux,
   Macos,
   Metadata(Vec<StringLiteral<'src>>),
   NoCd,
   NoExitMessage,
   NoQuiet,
+  OnFailure(Vec<StringLiteral<'src>>),
   Openbsd,
+  Outputs(Vec<StringLiteral<'src>>),
   Parallel,
   PositionalArguments,
   Private,
+  Prompt,
+  #[serde(serialize_with = "serialize_arguments")]
+  Retry(
+    Vec<StringLiteral<'src>>,
+    #[derive_where(skip(EqHashOrd))] Retry,
+  ),
   Script(Option<Interpreter<'src>>),
+  Secret(Vec<StringLiteral<'src>>),
+  #[serde(serialize_with = "serialize_arguments")]
+  Timeout(
+    StringLiteral<'src>,
+    #[derive_where(skip(EqHashOrd))] Timeout,
+  ),
   Unix,
+  Watch(Vec<StringLiteral<'src>>),
   Windows,
   WorkingDirectory(StringLiteral<'src>),
 }
 
+/// Serialize the arguments of an attribute whose value is parsed from its
+/// arguments, omitting the parsed value
+fn serialize_arguments<S: Serializer, T: Serialize, V>(
+  arguments: &T,
+  _value: &V,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  arguments.serialize(serializer)
+}
+
 impl AttributeDiscriminant {
   fn argument_range(self) -> RangeInclusive<usize> {
     match self {
       Self::Confirm | Self::Doc => 0..=1,
-      Self::Default
+      Self::Env => 2..=2,
+      Self::Retry => 1..=3,
+      Self::Cache
+      | Self::Default
       | Self::ExitMessage
       | Self::Linux
       | Self::Macos
@@ -46,10 +81,18 @@ impl AttributeDiscriminant {
       | Self::Parallel
       | Self::PositionalArguments
       | Self::Private
+      | Self::Prompt
       | Self::Unix
       | Self::Windows => 0..=0,
-      Self::Extension | Self::Group | Self::WorkingDirectory => 1..=1,
-      Self::Metadata => 1..=usize::MAX,
+      Self::Extension | Self::Group | Self::Timeout | Self::WorkingDirectory => 1..=1,
+      Self::Allow
+      | Self::Finally
+      | Self::Inputs
+      | Self::Metadata
+      | Self::OnFailure
+      | Self::Outputs
+      | Self::Secret
+      | Self::Watch => 1..=usize::MAX,
       Self::Script => 0..=usize::MAX,
     }
   }
@@ -59,6 +102,7 @@ impl<'src> Attribute<'src> {
   pub(crate) fn new(
     name: Name<'src>,
     arguments: Vec<StringLiteral<'src>>,
+    value: Option<Expression<'src>>,
   ) -> CompileResult<'src, Self> {
     let discriminant = name
       .lexeme()
@@ -70,7 +114,7 @@ impl<'src> Attribute<'src> {
         })
       })?;
 
-    let found = arguments.len();
+    let found = arguments.len() + usize::from(value.is_some());
     let range = discriminant.argument_range();
     if !range.contains(&found) {
       return Err(
@@ -84,22 +128,37 @@ impl<'src> Attribute<'src> {
     }
 
     Ok(match discriminant {
+      AttributeDiscriminant::Allow => Self::Allow(Self::lints(name, arguments)?),
+      AttributeDiscriminant::Cache => Self::Cache,
       AttributeDiscriminant::Confirm => Self::Confirm(arguments.into_iter().next()),
       AttributeDiscriminant::Default => Self::Default,
       AttributeDiscriminant::Doc => Self::Doc(arguments.into_iter().next()),
+      AttributeDiscriminant::Env => Self::Env(
+        Self::env(name, arguments.into_iter().next().unwrap())?,
+        value.unwrap(),
+      ),
       AttributeDiscriminant::ExitMessage => Self::ExitMessage,
       AttributeDiscriminant::Extension => Self::Extension(arguments.into_iter().next().unwrap()),
+      AttributeDiscriminant::Finally => Self::Finally(arguments),
       AttributeDiscriminant::Group => Self::Group(arguments.into_iter().next().unwrap()),
+      AttributeDiscriminant::Inputs => Self::Inputs(Self::patterns(name, arguments)?),
       AttributeDiscriminant::Linux => Self::Linux,
       AttributeDiscriminant::Macos => Self::Macos,
       AttributeDiscriminant::Metadata => Self::Metadata(arguments),
       AttributeDiscriminant::NoCd => Self::NoCd,
       AttributeDiscriminant::NoExitMessage => Self::NoExitMessage,
       AttributeDiscriminant::NoQuiet => Self::NoQuiet,
+      AttributeDiscriminant::OnFailure => Self::OnFailure(arguments),
       AttributeDiscriminant::Openbsd => Self::Openbsd,
+      AttributeDiscriminant::Outputs => Self::Outputs(arguments),
       AttributeDiscriminant::Parallel => Self::Parallel,
       AttributeDiscriminant::PositionalArguments => Self::PositionalArguments,
       AttributeDiscriminant::Private => Self::Private,
+      AttributeDiscriminant::Prompt => Self::Prompt,
+      AttributeDiscriminant::Retry => {
+        let retry = Self::retry(name, &arguments)?;
+        Self::Retry(arguments, retry)
+      }
       AttributeDiscriminant::Script => Self::Script({
         let mut arguments = arguments.into_iter();
         arguments.next().map(|command| Interpreter {
@@ -107,7 +166,14 @@ impl<'src> Attribute<'src> {
           arguments: arguments.collect(),
         })
       }),
+      AttributeDiscriminant::Secret => Self::Secret(arguments),
+      AttributeDiscriminant::Timeout => {
+        let argument = arguments.into_iter().next().unwrap();
+        let timeout = Self::timeout(name, &argument)?;
+        Self::Timeout(argument, timeout)
+      }
       AttributeDiscriminant::Unix => Self::Unix,
+      AttributeDiscriminant::Watch => Self::Watch(Self::patterns(name, arguments)?),
       AttributeDiscriminant::Windows => Self::Windows,
       AttributeDiscriminant::WorkingDirectory => {
         Self::WorkingDirectory(arguments.into_iter().next().unwrap())
@@ -119,12 +185,77 @@ impl<'src> Attribute<'src> {
     self.into()
   }
 
+  fn env(name: Name<'src>, key: StringLiteral<'src>) -> CompileResult<'src, StringLiteral<'src>> {
+    if key.cooked.is_empty() || key.cooked.contains(['=', '\0']) {
+      return Err(name.error(CompileErrorKind::InvalidEnvironmentVariable {
+        variable: key.cooked,
+      }));
+    }
+
+    Ok(key)
+  }
+
+  fn lints(
+    name: Name<'src>,
+    lints: Vec<StringLiteral<'src>>,
+  ) -> CompileResult<'src, Vec<StringLiteral<'src>>> {
+    for lint in &lints {
+      if lint.cooked.parse::<Lint>().is_err() {
+        return Err(name.error(CompileErrorKind::UnknownLint {
+          lint: lint.cooked.clone(),
+        }));
+      }
+    }
+
+    Ok(lints)
+  }
+
   pub(crate) fn name(&self) -> &'static str {
     self.into()
   }
 
+  fn patterns(
+    name: Name<'src>,
+    patterns: Vec<StringLiteral<'src>>,
+  ) -> CompileResult<'src, Vec<StringLiteral<'src>>> {
+    for pattern in &patterns {
+      if let Err(error) = glob::Pattern::new(&pattern.cooked) {
+        return Err(name.error(CompileErrorKind::InvalidGlob {
+          pattern: pattern.cooked.clone(),
+          message: error.msg.into(),
+        }));
+      }
+    }
+
+    Ok(patterns)
+  }
+
+  fn retry(name: Name<'src>, arguments: &[StringLiteral<'src>]) -> CompileResult<'src, Retry> {
+    Retry::parse(arguments).map_err(|(argument, message)| {
+      name.error(CompileErrorKind::InvalidRetry { argument, message })
+    })
+  }
+
+  fn timeout(name: Name<'src>, timeout: &StringLiteral<'src>) -> CompileResult<'src, Timeout> {
+    timeout.cooked.parse::<Timeout>().map_err(|message| {
+      name.error(CompileErrorKind::InvalidTimeout {
+        message,
+        timeout: timeout.cooked.clone(),
+      })
+    })
+  }
+
   pub(crate) fn repeatable(&self) -> bool {
-    matches!(self, Attribute::Group(_) | Attribute::Metadata(_))
+    matches!(
+      self,
+      Attribute::Allow(_)
+        | Attribute::Env(..)
+        | Attribute::Group(_)
+        | Attribute::Inputs(_)
+        | Attribute::Metadata(_)
+        | Attribute::Outputs(_)
+        | Attribute::Watch(_)
+    )
   }
 }
 
@@ -133,7 +264,8 @@ impl Display for Attribute<'_> {
     write!(f, "{}", self.name())?;
 
     match self {
-      Self::Confirm(None)
+      Self::Cache
+      | Self::Confirm(None)
       | Self::Default
       | Self::Doc(None)
       | Self::ExitMessage
@@ -146,6 +278,7 @@ impl Display for Attribute<'_> {
       | Self::Parallel
       | Self::PositionalArguments
       | Self::Private
+      | Self::Prompt
       | Self::Script(None)
       | Self::Unix
       | Self::Windows => {}
@@ -153,8 +286,17 @@ impl Display for Attribute<'_> {
       | Self::Doc(Some(argument))
       | Self::Extension(argument)
       | Self::Group(argument)
+      | Self::Timeout(argument, _)
       | Self::WorkingDirectory(argument) => write!(f, "({argument})")?,
-      Self::Metadata(arguments) => {
+      Self::Allow(arguments)
+      | Self::Finally(arguments)
+      | Self::Inputs(arguments)
+      | Self::Metadata(arguments)
+      | Self::OnFailure(arguments)
+      | Self::Outputs(arguments)
+      | Self::Retry(arguments, _)
+      | Self::Secret(arguments)
+      | Self::Watch(arguments) => {
         write!(f, "(")?;
         for (i, argument) in arguments.iter().enumerate() {
           if i > 0 {
@@ -164,6 +306,7 @@ impl Display for Attribute<'_> {
         }
         write!(f, ")")?;
       }
+      Self::Env(key, value) => write!(f, "({key}, {value})")?,
       Self::Script(Some(shell)) => write!(f, "({shell})")?,
     }
 
@@ -8,6 +8,24 @@ impl<'src> AttributeSet<'src> {
     self.0.len()
   }
 
+  /// Lints silenced by `[allow(…)]` attributes
+  pub(crate) fn allowed(&self) -> BTreeSet<Lint> {
+    self
+      .0
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Allow(lints) => Some(lints),
+        _ => None,
+      })
+      .flatten()
+      .map(|lint| lint.cooked.parse().unwrap())
+      .collect()
+  }
+
+  pub(crate) fn allows(&self, lint: Lint) -> bool {
+    self.allowed().contains(&lint)
+  }
+
   pub(crate) fn contains(&self, target: AttributeDiscriminant) -> bool {
     self.0.iter().any(|attr| attr.discriminant() == target)
   }
@@ -2,7 +2,7 @@ use super::*;
 
 /// A binding of `name` to `value`
 #[derive(Debug, Clone, PartialEq, Serialize)]
-pub(crate) struct Binding<'src, V = String> {
+pub(crate) struct Binding<'src, V = Value> {
   #[serde(skip)]
   pub(crate) constant: bool,
   pub(crate) export: bool,
@@ -10,6 +10,7 @@ pub(crate) struct Binding<'src, V = String> {
   pub(crate) file_depth: u32,
   pub(crate) name: Name<'src>,
   pub(crate) private: bool,
+  pub(crate) secret: bool,
   pub(crate) value: V,
 }
 
@@ -0,0 +1,151 @@
+use {super::*, std::ffi::OsStr};
+
+/// Content hashes of successful recipe runs, keyed by recipe namepath and
+/// stored as JSON in `.just/cache` next to the root justfile
+pub(crate) struct Cache;
+
+impl Cache {
+  pub(crate) fn clean(search: &Search) -> RunResult<'static> {
+    let path = Self::path(search);
+
+    match fs::remove_file(&path) {
+      Err(io_error) if io_error.kind() != io::ErrorKind::NotFound => {
+        Err(Error::CacheIo { path, io_error })
+      }
+      _ => Ok(()),
+    }
+  }
+
+  pub(crate) fn get<'src, D>(
+    search: &Search,
+    recipe: &Recipe<'src, D>,
+  ) -> RunResult<'src, Option<String>> {
+    let _guard = Self::lock();
+    Ok(Self::load(&Self::path(search))?.remove(Self::name(recipe)))
+  }
+
+  pub(crate) fn insert<'src, D>(
+    search: &Search,
+    recipe: &Recipe<'src, D>,
+    key: String,
+  ) -> RunResult<'src> {
+    let _guard = Self::lock();
+
+    let path = Self::path(search);
+
+    let mut entries = Self::load(&path)?;
+
+    entries.insert(Self::name(recipe).into(), key);
+
+    fs::create_dir_all(path.parent().unwrap())
+      .and_then(|()| fs::write(&path, serde_json::to_string(&entries).unwrap()))
+      .map_err(|io_error| Error::CacheIo { path, io_error })
+  }
+
+  /// The recipe's cache key, covering its evaluated body and `[env]` values,
+  /// so that changes in the output of backticks and function calls cause it to
+  /// rerun
+  pub(crate) fn key<'src, 'run, D>(
+    context: &ExecutionContext<'src, 'run>,
+    recipe: &Recipe<'src, D>,
+    scope: &Scope<'src, 'run>,
+    is_dependency: bool,
+  ) -> RunResult<'src, String> {
+    #[derive(Serialize)]
+    struct Key<'a> {
+      body: Vec<String>,
+      environment: BTreeMap<Cow<'a, str>, Option<Cow<'a, str>>>,
+      inputs: BTreeMap<PathBuf, String>,
+      parameters: Vec<Option<&'a Value>>,
+    }
+
+    let mut evaluator = Evaluator::new(context, is_dependency, scope);
+
+    let body = recipe
+      .body
+      .iter()
+      .map(|line| {
+        evaluator
+          .evaluate_line(line, false)
+          .map(|(evaluated, _redacted)| evaluated)
+      })
+      .collect::<RunResult<Vec<String>>>()?;
+
+    let mut command = Command::new("");
+
+    let env = recipe.evaluate_env(&mut evaluator)?;
+
+    command.export(
+      &context.module.settings,
+      context.dotenv,
+      scope,
+      &env,
+      &context.module.unexports,
+    );
+
+    let directory = recipe.input_directory(context);
+
+    let mut inputs = BTreeMap::new();
+
+    for path in Freshness::inputs(&directory, &recipe.inputs())? {
+      let mut hasher = blake3::Hasher::new();
+
+      hasher
+        .update_mmap_rayon(&path)
+        .map_err(|io_error| Error::CacheIo {
+          path: path.clone(),
+          io_error,
+        })?;
+
+      let path = path.strip_prefix(&directory).unwrap_or(&path).into();
+
+      inputs.insert(path, hasher.finalize().to_string());
+    }
+
+    let key = Key {
+      body,
+      environment: command
+        .get_envs()
+        .map(|(key, value)| (key.to_string_lossy(), value.map(OsStr::to_string_lossy)))
+        .collect(),
+      inputs,
+      parameters: recipe
+        .parameters
+        .iter()
+        .map(|parameter| scope.value(parameter.name.lexeme()))
+        .collect(),
+    };
+
+    Ok(blake3::hash(&serde_json::to_vec(&key).unwrap()).to_string())
+  }
+
+  /// A missing or malformed cache is treated as empty
+  fn load<'src>(path: &Path) -> RunResult<'src, BTreeMap<String, String>> {
+    match fs::read_to_string(path) {
+      Ok(json) => Ok(serde_json::from_str(&json).unwrap_or_default()),
+      Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+      Err(io_error) => Err(Error::CacheIo {
+        path: path.into(),
+        io_error,
+      }),
+    }
+  }
+
+  fn lock() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap()
+  }
+
+  fn name<'a, D>(recipe: &'a Recipe<D>) -> &'a str {
+    recipe.namepath.as_deref().unwrap_or(recipe.name())
+  }
+
+  fn path(search: &Search) -> PathBuf {
+    search
+      .justfile
+      .parent()
+      .unwrap()
+      .join(".just")
+      .join("cache")
+  }
+}
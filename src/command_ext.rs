@@ -1,4 +1,7 @@
-use super::*;
+use {
+  super::*,
+  std::time::{Duration, Instant},
+};
 
 pub(crate) trait CommandExt {
   fn export(
@@ -6,6 +9,7 @@ pub(crate) trait CommandExt {
     settings: &Settings,
     dotenv: &BTreeMap<String, String>,
     scope: &Scope,
+    env: &BTreeMap<String, String>,
     unexports: &HashSet<String>,
   ) -> &mut Command;
 
@@ -16,6 +20,8 @@ pub(crate) trait CommandExt {
   fn output_guard_stdout(self) -> Result<String, OutputError>;
 
   fn status_guard(self) -> (io::Result<ExitStatus>, Option<Signal>);
+
+  fn status_guard_timeout(self, timeout: Duration) -> (io::Result<ExitStatus>, Option<Signal>);
 }
 
 impl CommandExt for Command {
@@ -24,6 +30,7 @@ impl CommandExt for Command {
     settings: &Settings,
     dotenv: &BTreeMap<String, String>,
     scope: &Scope,
+    env: &BTreeMap<String, String>,
     unexports: &HashSet<String>,
   ) -> &mut Command {
     for (name, value) in dotenv {
@@ -34,6 +41,12 @@ impl CommandExt for Command {
       self.export_scope(settings, parent, unexports);
     }
 
+    for (name, value) in env {
+      if !unexports.contains(name) {
+        self.env(name, value);
+      }
+    }
+
     self
   }
 
@@ -48,7 +61,7 @@ impl CommandExt for Command {
 
     for binding in scope.bindings() {
       if binding.export || (settings.export && !binding.constant) {
-        self.env(binding.name.lexeme(), &binding.value);
+        self.env(binding.name.lexeme(), binding.value.to_string());
       }
     }
   }
@@ -82,4 +95,65 @@ impl CommandExt for Command {
   fn status_guard(self) -> (io::Result<ExitStatus>, Option<Signal>) {
     SignalHandler::spawn(self, |mut child| child.wait())
   }
+
+  fn status_guard_timeout(self, timeout: Duration) -> (io::Result<ExitStatus>, Option<Signal>) {
+    SignalHandler::spawn(self, |child| wait_timeout(child, timeout))
+  }
+}
+
+/// Wait up to `timeout` for `child` to exit
+fn poll(child: &mut process::Child, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+  let start = Instant::now();
+  let mut interval = Duration::from_millis(1);
+
+  loop {
+    if let Some(status) = child.try_wait()? {
+      return Ok(Some(status));
+    }
+
+    let remaining = timeout.saturating_sub(start.elapsed());
+
+    if remaining.is_zero() {
+      return Ok(None);
+    }
+
+    thread::sleep(interval.min(remaining));
+
+    interval = (interval * 2).min(Duration::from_millis(50));
+  }
+}
+
+/// Wait for `child` to exit. If it is still running after `timeout`, send
+/// SIGTERM to its process group, followed by SIGKILL if it is still running
+/// after a grace period, and return a `TimedOut` error.
+fn wait_timeout(mut child: process::Child, timeout: Duration) -> io::Result<ExitStatus> {
+  const GRACE: Duration = Duration::from_secs(5);
+
+  if let Some(status) = poll(&mut child, timeout)? {
+    return Ok(status);
+  }
+
+  #[cfg(unix)]
+  {
+    use nix::{sys::signal, unistd::Pid};
+
+    let pid = Pid::from_raw(i32::try_from(child.id()).map_err(io::Error::other)?);
+
+    let kill = |signal| {
+      signal::killpg(pid, signal).ok();
+    };
+
+    kill(signal::Signal::SIGTERM);
+
+    if poll(&mut child, GRACE)?.is_none() {
+      kill(signal::Signal::SIGKILL);
+    }
+  }
+
+  #[cfg(windows)]
+  child.kill().ok();
+
+  child.wait()?;
+
+  Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))
 }
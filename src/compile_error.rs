@@ -54,6 +54,20 @@ impl Display for CompileError<'_> {
         }
       }
       BacktickShebang => write!(f, "Backticks may not start with `#!`"),
+      BuiltinFunctionRedefinition { function } => {
+        write!(f, "Function `{function}` redefines a built-in function")
+      }
+      CircularFunctionCall { function, circle } => {
+        if circle.len() == 2 {
+          write!(f, "Function `{function}` calls itself")
+        } else {
+          write!(
+            f,
+            "Function `{function}` has circular call `{}`",
+            circle.join(" -> ")
+          )
+        }
+      }
       CircularRecipeDependency { recipe, circle } => {
         if circle.len() == 2 {
           write!(f, "Recipe `{recipe}` depends on itself")
@@ -65,6 +79,17 @@ impl Display for CompileError<'_> {
           )
         }
       }
+      CircularRecipeHook { recipe, circle } => {
+        if circle.len() == 2 {
+          write!(f, "Recipe `{recipe}` is its own hook")
+        } else {
+          write!(
+            f,
+            "Recipe `{recipe}` has circular hook `{}`",
+            circle.join(" -> ")
+          )
+        }
+      }
       CircularVariableDependency { variable, circle } => {
         if circle.len() == 2 {
           write!(f, "Variable `{variable}` is defined in terms of itself")
@@ -107,6 +132,19 @@ impl Display for CompileError<'_> {
         f,
         "Recipe `{recipe}` has duplicate `[default]` attribute, which may only appear once per module",
       ),
+      DuplicateFunction { function, first } => write!(
+        f,
+        "Function `{function}` first defined on line {} is redefined on line {}",
+        first.ordinal(),
+        self.token.line.ordinal(),
+      ),
+      DuplicateFunctionParameter {
+        function,
+        parameter,
+      } => write!(
+        f,
+        "Function `{function}` has duplicate parameter `{parameter}`"
+      ),
       DuplicateParameter { recipe, parameter } => {
         write!(f, "Recipe `{recipe}` has duplicate parameter `{parameter}`")
       }
@@ -126,6 +164,10 @@ impl Display for CompileError<'_> {
         f,
         "Recipe `{recipe}` has both `[exit-message]` and `[no-exit-message]` attributes"
       ),
+      ExpectedFunctionName { function } => write!(
+        f,
+        "Second argument to `{function}` must be the name of a function"
+      ),
       ExpectedKeyword { expected, found } => {
         let expected = List::or_ticked(expected);
         if found.kind == TokenKind::Identifier {
@@ -155,6 +197,10 @@ impl Display for CompileError<'_> {
         Count("argument", *found),
         expected.display(),
       ),
+      HookRequiresArguments { hook, recipe } => write!(
+        f,
+        "Recipe `{recipe}` has hook `{hook}` which requires arguments"
+      ),
       Include => write!(
         f,
         "The `!include` directive has been stabilized as `import`"
@@ -166,6 +212,10 @@ impl Display for CompileError<'_> {
         ShowWhitespace(expected),
         ShowWhitespace(found)
       ),
+      InputsWithoutOutputsAttribute { recipe } => write!(
+        f,
+        "Recipe `{recipe}` has `[inputs]` attribute without `[outputs]` or `[cache]` attribute"
+      ),
       Internal { message } => write!(
         f,
         "Internal error, this may indicate a bug in just: {message}\n\
@@ -180,6 +230,9 @@ impl Display for CompileError<'_> {
         "{item_kind} `{item_name}` has invalid attribute `{}`",
         attribute.name(),
       ),
+      InvalidEnvironmentVariable { variable } => {
+        write!(f, "Invalid environment variable name `{variable}`")
+      }
       InvalidEscapeSequence { character } => write!(
         f,
         "`\\{}` is not a valid escape sequence",
@@ -191,6 +244,11 @@ impl Display for CompileError<'_> {
           _ => character.escape_default().collect(),
         }
       ),
+      InvalidGlob { pattern, message } => write!(f, "Invalid glob pattern `{pattern}`: {message}"),
+      InvalidRetry { argument, message } => {
+        write!(f, "Invalid `[retry]` argument `{argument}`: {message}")
+      }
+      InvalidTimeout { message, timeout } => write!(f, "Invalid timeout `{timeout}`: {message}"),
       MismatchedClosingDelimiter {
         open,
         open_line,
@@ -294,6 +352,16 @@ impl Display for CompileError<'_> {
         write!(f, "Recipe `{recipe}` has unknown dependency `{unknown}`")
       }
       UnknownFunction { function } => write!(f, "Call to unknown function `{function}`"),
+      UnknownHook { hook, recipe } => write!(f, "Recipe `{recipe}` has unknown hook `{hook}`"),
+      UnknownLint { lint } => write!(f, "Unknown lint `{lint}`"),
+      UnknownParameterType { parameter_type } => write!(
+        f,
+        "Unknown parameter type `{parameter_type}`, expected `bool`, `int`, or a parenthesized \
+        list of choices"
+      ),
+      UnknownSecretParameter { parameter, recipe } => {
+        write!(f, "Recipe `{recipe}` has unknown secret parameter `{parameter}`")
+      }
       UnknownSetting { setting } => write!(f, "Unknown setting `{setting}`"),
       UnknownStartOfToken { start } => {
         write!(f, "Unknown start of token '{start}'")?;
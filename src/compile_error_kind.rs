@@ -9,10 +9,21 @@ pub(crate) enum CompileErrorKind<'src> {
     max: usize,
   },
   BacktickShebang,
+  BuiltinFunctionRedefinition {
+    function: &'src str,
+  },
+  CircularFunctionCall {
+    function: &'src str,
+    circle: Vec<&'src str>,
+  },
   CircularRecipeDependency {
     recipe: &'src str,
     circle: Vec<&'src str>,
   },
+  CircularRecipeHook {
+    recipe: &'src str,
+    circle: Vec<&'src str>,
+  },
   CircularVariableDependency {
     variable: &'src str,
     circle: Vec<&'src str>,
@@ -30,6 +41,14 @@ pub(crate) enum CompileErrorKind<'src> {
   DuplicateDefault {
     recipe: &'src str,
   },
+  DuplicateFunction {
+    function: &'src str,
+    first: usize,
+  },
+  DuplicateFunctionParameter {
+    function: &'src str,
+    parameter: &'src str,
+  },
   DuplicateParameter {
     recipe: &'src str,
     parameter: &'src str,
@@ -47,6 +66,9 @@ pub(crate) enum CompileErrorKind<'src> {
   ExitMessageAndNoExitMessageAttribute {
     recipe: &'src str,
   },
+  ExpectedFunctionName {
+    function: &'src str,
+  },
   ExpectedKeyword {
     expected: Vec<Keyword>,
     found: Token<'src>,
@@ -63,11 +85,18 @@ pub(crate) enum CompileErrorKind<'src> {
     found: usize,
     expected: RangeInclusive<usize>,
   },
+  HookRequiresArguments {
+    hook: &'src str,
+    recipe: &'src str,
+  },
   Include,
   InconsistentLeadingWhitespace {
     expected: &'src str,
     found: &'src str,
   },
+  InputsWithoutOutputsAttribute {
+    recipe: &'src str,
+  },
   Internal {
     message: String,
   },
@@ -76,9 +105,24 @@ pub(crate) enum CompileErrorKind<'src> {
     item_name: &'src str,
     attribute: Attribute<'src>,
   },
+  InvalidEnvironmentVariable {
+    variable: String,
+  },
   InvalidEscapeSequence {
     character: char,
   },
+  InvalidGlob {
+    pattern: String,
+    message: String,
+  },
+  InvalidRetry {
+    argument: String,
+    message: String,
+  },
+  InvalidTimeout {
+    message: String,
+    timeout: String,
+  },
   MismatchedClosingDelimiter {
     close: Delimiter,
     open: Delimiter,
@@ -153,6 +197,20 @@ pub(crate) enum CompileErrorKind<'src> {
   UnknownFunction {
     function: &'src str,
   },
+  UnknownHook {
+    hook: String,
+    recipe: &'src str,
+  },
+  UnknownLint {
+    lint: String,
+  },
+  UnknownParameterType {
+    parameter_type: &'src str,
+  },
+  UnknownSecretParameter {
+    parameter: String,
+    recipe: &'src str,
+  },
   UnknownSetting {
     setting: &'src str,
   },
@@ -99,7 +99,16 @@ impl Compiler {
       asts.insert(current.path, ast.clone());
     }
 
-    let justfile = Analyzer::analyze(&asts, None, &[], &loaded, None, &paths, root)?;
+    let justfile = Analyzer::analyze(
+      &asts,
+      None,
+      &[],
+      &loaded,
+      None,
+      &paths,
+      root,
+      &FunctionScope::default(),
+    )?;
 
     Ok(Compilation {
       asts,
@@ -224,7 +233,16 @@ impl Compiler {
     asts.insert(root.clone(), ast);
     let mut paths: HashMap<PathBuf, PathBuf> = HashMap::new();
     paths.insert(root.clone(), root.clone());
-    Analyzer::analyze(&asts, None, &[], &[], None, &paths, &root)
+    Analyzer::analyze(
+      &asts,
+      None,
+      &[],
+      &[],
+      None,
+      &paths,
+      &root,
+      &FunctionScope::default(),
+    )
   }
 }
 
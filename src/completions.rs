@@ -104,7 +104,7 @@ mod tests {
         if string match -rq '(-f|--justfile)\s*=?(?<justfile>[^\s]+)' -- (string split -- ' -- ' (commandline -pc))[1]
           set -fx JUST_JUSTFILE "$justfile"
         end
-        printf "%s\n" (string split " " (just --summary))
+        printf "%s\n" (string split " " (just --complete (commandline -opc)[2..]))
 end
 
 # don't suggest files right off
@@ -168,8 +168,17 @@ complete -c just -a '(__fish_just_complete_recipes)'
                 # Arguments contain equal would be recognised as a variable
                 _message "value"
             elif [[ $recipe ]]; then
-                # Show usage message
-                _message "`just --show $recipe`"
+                local values; values=(
+                    ${(s: :)$(_call_program commands just --complete ${words[2,-2]})}
+                )
+
+                if [[ ${#values} -gt 0 && ${values[*]} != ${cmds[*]} ]]; then
+                    # Complete with values accepted by the next parameter
+                    _describe -t values 'values' values && ret=0
+                else
+                    # Show usage message
+                    _message "`just --show $recipe`"
+                fi
                 # Or complete with other commands
                 #_arguments -s -S $common '*:: :_just_commands'
             else
@@ -264,10 +273,14 @@ _just "$@""#,
                 return 0
             fi"#,
       r#"                if [[ ${cur} == -* ]] ; then
-                    COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                    local options=$(just --complete "${words[@]:1:cword-1}" 2> /dev/null | \grep -oE -- '--[^ ]+')
+                    if [[ -z "${options}" ]]; then
+                        local options="${opts}"
+                    fi
+                    COMPREPLY=( $(compgen -W "${options}" -- "${cur}") )
                     return 0
                 elif [[ ${COMP_CWORD} -eq 1 ]]; then
-                    local recipes=$(just --summary 2> /dev/null)
+                    local recipes=$(just --complete "${words[@]:1:cword-1}" 2> /dev/null)
 
                     if echo "${cur}" | \grep -qF '/'; then
                         local path_prefix=$(echo "${cur}" | sed 's/[/][^/]*$/\//')
@@ -297,7 +310,7 @@ _just "$@""#,
     else
         cur="${COMP_WORDS[COMP_CWORD]}"
         prev="${COMP_WORDS[COMP_CWORD-1]}"
-        words=$COMP_WORDS
+        words=("${COMP_WORDS[@]}")
         cword=$COMP_CWORD
     fi
 "#,
@@ -19,13 +19,27 @@ pub(crate) struct Config {
   pub(crate) color: Color,
   pub(crate) command_color: Option<ansi_term::Color>,
   pub(crate) cygpath: PathBuf,
+  /// Shell set in a config file, used if the justfile doesn't set one
+  pub(crate) default_shell: Option<String>,
+  /// Shell arguments set in a config file, used if the justfile doesn't set a
+  /// shell
+  pub(crate) default_shell_args: Option<Vec<String>>,
   pub(crate) dotenv_filename: Option<String>,
   pub(crate) dotenv_path: Option<PathBuf>,
+  pub(crate) dotenv_profile: Option<String>,
   pub(crate) dry_run: bool,
   pub(crate) dump_format: DumpFormat,
+  pub(crate) events: Option<EventFormat>,
+  pub(crate) events_file: Option<PathBuf>,
   pub(crate) explain: bool,
+  pub(crate) force: bool,
+  pub(crate) graph_eval: bool,
+  pub(crate) graph_format: GraphFormat,
   pub(crate) highlight: bool,
+  pub(crate) interactive: bool,
   pub(crate) invocation_directory: PathBuf,
+  pub(crate) jobs: Option<NonZeroUsize>,
+  pub(crate) keep_going: bool,
   pub(crate) list_heading: String,
   pub(crate) list_prefix: String,
   pub(crate) list_submodules: bool,
@@ -33,12 +47,16 @@ pub(crate) struct Config {
   pub(crate) no_aliases: bool,
   pub(crate) no_dependencies: bool,
   pub(crate) one: bool,
+  pub(crate) plan: bool,
+  pub(crate) plan_eval: bool,
+  pub(crate) report: Option<Report>,
   pub(crate) search_config: SearchConfig,
   pub(crate) shell: Option<String>,
   pub(crate) shell_args: Option<Vec<String>>,
   pub(crate) shell_command: bool,
   pub(crate) subcommand: Subcommand,
   pub(crate) tempdir: Option<PathBuf>,
+  pub(crate) timeout: Option<Timeout>,
   pub(crate) timestamp: bool,
   pub(crate) timestamp_format: String,
   pub(crate) unsorted: bool,
@@ -48,43 +66,69 @@ pub(crate) struct Config {
 }
 
 mod cmd {
+  pub(crate) const CACHE_CLEAN: &str = "CACHE-CLEAN";
   pub(crate) const CHANGELOG: &str = "CHANGELOG";
   pub(crate) const CHOOSE: &str = "CHOOSE";
   pub(crate) const COMMAND: &str = "COMMAND";
+  pub(crate) const COMPLETE: &str = "COMPLETE";
   pub(crate) const COMPLETIONS: &str = "COMPLETIONS";
   pub(crate) const DUMP: &str = "DUMP";
   pub(crate) const EDIT: &str = "EDIT";
   pub(crate) const EVALUATE: &str = "EVALUATE";
   pub(crate) const FORMAT: &str = "FORMAT";
+  pub(crate) const GRAPH: &str = "GRAPH";
   pub(crate) const GROUPS: &str = "GROUPS";
   pub(crate) const INIT: &str = "INIT";
+  pub(crate) const LINT: &str = "LINT";
   pub(crate) const LIST: &str = "LIST";
+  pub(crate) const LSP: &str = "LSP";
   pub(crate) const MAN: &str = "MAN";
   pub(crate) const REQUEST: &str = "REQUEST";
   pub(crate) const SHOW: &str = "SHOW";
+  pub(crate) const SHOW_CONFIG: &str = "SHOW-CONFIG";
   pub(crate) const SUMMARY: &str = "SUMMARY";
   pub(crate) const VARIABLES: &str = "VARIABLES";
+  pub(crate) const WATCH: &str = "WATCH";
 
   pub(crate) const ALL: &[&str] = &[
+    CACHE_CLEAN,
     CHANGELOG,
     CHOOSE,
     COMMAND,
+    COMPLETE,
     COMPLETIONS,
     DUMP,
     EDIT,
     EVALUATE,
     FORMAT,
+    GRAPH,
     INIT,
+    LINT,
     LIST,
+    LSP,
     MAN,
     REQUEST,
     SHOW,
+    SHOW_CONFIG,
     SUMMARY,
     VARIABLES,
+    WATCH,
   ];
 
-  pub(crate) const ARGLESS: &[&str] =
-    &[CHANGELOG, DUMP, EDIT, FORMAT, INIT, MAN, SUMMARY, VARIABLES];
+  pub(crate) const ARGLESS: &[&str] = &[
+    CACHE_CLEAN,
+    CHANGELOG,
+    DUMP,
+    EDIT,
+    FORMAT,
+    INIT,
+    LINT,
+    LSP,
+    MAN,
+    SHOW_CONFIG,
+    SUMMARY,
+    VARIABLES,
+  ];
 
   pub(crate) const HEADING: &str = "Commands";
 }
@@ -102,26 +146,42 @@ mod arg {
   pub(crate) const CYGPATH: &str = "CYGPATH";
   pub(crate) const DOTENV_FILENAME: &str = "DOTENV-FILENAME";
   pub(crate) const DOTENV_PATH: &str = "DOTENV-PATH";
+  pub(crate) const DOTENV_PROFILE: &str = "DOTENV-PROFILE";
   pub(crate) const DRY_RUN: &str = "DRY-RUN";
   pub(crate) const DUMP_FORMAT: &str = "DUMP-FORMAT";
+  pub(crate) const EVENTS: &str = "EVENTS";
+  pub(crate) const EVENTS_FILE: &str = "EVENTS-FILE";
   pub(crate) const EXPLAIN: &str = "EXPLAIN";
+  pub(crate) const FORCE: &str = "FORCE";
   pub(crate) const GLOBAL_JUSTFILE: &str = "GLOBAL-JUSTFILE";
+  pub(crate) const GRAPH_EVAL: &str = "GRAPH-EVAL";
+  pub(crate) const GRAPH_FORMAT: &str = "GRAPH-FORMAT";
   pub(crate) const HIGHLIGHT: &str = "HIGHLIGHT";
+  pub(crate) const INTERACTIVE: &str = "INTERACTIVE";
+  pub(crate) const JOBS: &str = "JOBS";
+  pub(crate) const KEEP_GOING: &str = "KEEP-GOING";
   pub(crate) const JUSTFILE: &str = "JUSTFILE";
   pub(crate) const LIST_HEADING: &str = "LIST-HEADING";
   pub(crate) const LIST_PREFIX: &str = "LIST-PREFIX";
   pub(crate) const LIST_SUBMODULES: &str = "LIST-SUBMODULES";
   pub(crate) const NO_ALIASES: &str = "NO-ALIASES";
+  pub(crate) const NO_CONFIG: &str = "NO-CONFIG";
   pub(crate) const NO_DEPS: &str = "NO-DEPS";
   pub(crate) const NO_DOTENV: &str = "NO-DOTENV";
   pub(crate) const NO_HIGHLIGHT: &str = "NO-HIGHLIGHT";
+  pub(crate) const NO_TIMESTAMP: &str = "NO-TIMESTAMP";
+  pub(crate) const NO_UNSORTED: &str = "NO-UNSORTED";
   pub(crate) const ONE: &str = "ONE";
+  pub(crate) const PLAN: &str = "PLAN";
+  pub(crate) const PLAN_EVAL: &str = "PLAN-EVAL";
   pub(crate) const QUIET: &str = "QUIET";
+  pub(crate) const REPORT: &str = "REPORT";
   pub(crate) const SET: &str = "SET";
   pub(crate) const SHELL: &str = "SHELL";
   pub(crate) const SHELL_ARG: &str = "SHELL-ARG";
   pub(crate) const SHELL_COMMAND: &str = "SHELL-COMMAND";
   pub(crate) const TEMPDIR: &str = "TEMPDIR";
+  pub(crate) const TIMEOUT: &str = "TIMEOUT";
   pub(crate) const TIMESTAMP: &str = "TIMESTAMP";
   pub(crate) const TIMESTAMP_FORMAT: &str = "TIMESTAMP-FORMAT";
   pub(crate) const UNSORTED: &str = "UNSORTED";
@@ -236,6 +296,13 @@ impl Config {
           .value_parser(value_parser!(PathBuf))
           .help("Load <DOTENV-PATH> as environment file instead of searching for one"),
       )
+      .arg(
+        Arg::new(arg::DOTENV_PROFILE)
+          .long("dotenv-profile")
+          .env("JUST_DOTENV_PROFILE")
+          .action(ArgAction::Set)
+          .help("Substitute <DOTENV-PROFILE> for `{{profile}}` in `dotenv-files` setting"),
+      )
       .arg(
         Arg::new(arg::DRY_RUN)
           .short('n')
@@ -255,6 +322,25 @@ impl Config {
           .value_name("FORMAT")
           .help("Dump justfile as <FORMAT>"),
       )
+      .arg(
+        Arg::new(arg::EVENTS)
+          .long("events")
+          .env("JUST_EVENTS")
+          .action(ArgAction::Set)
+          .value_parser(clap::value_parser!(EventFormat))
+          .value_name("FORMAT")
+          .help("Write run events to stderr as <FORMAT>, one per line"),
+      )
+      .arg(
+        Arg::new(arg::EVENTS_FILE)
+          .long("events-file")
+          .env("JUST_EVENTS_FILE")
+          .action(ArgAction::Set)
+          .value_parser(value_parser!(PathBuf))
+          .value_name("PATH")
+          .requires(arg::EVENTS)
+          .help("Write run events to <PATH> instead of stderr"),
+      )
       .arg(
         Arg::new(arg::EXPLAIN)
           .action(ArgAction::SetTrue)
@@ -262,6 +348,13 @@ impl Config {
           .env("JUST_EXPLAIN")
           .help("Print recipe doc comment before running it"),
       )
+      .arg(
+        Arg::new(arg::FORCE)
+          .long("force")
+          .env("JUST_FORCE")
+          .action(ArgAction::SetTrue)
+          .help("Run recipes with `[outputs]` even if their outputs are up to date"),
+      )
       .arg(
         Arg::new(arg::GLOBAL_JUSTFILE)
           .action(ArgAction::SetTrue)
@@ -271,6 +364,24 @@ impl Config {
           .conflicts_with(arg::WORKING_DIRECTORY)
           .help("Use global justfile"),
       )
+      .arg(
+        Arg::new(arg::GRAPH_EVAL)
+          .long("graph-eval")
+          .env("JUST_GRAPH_EVAL")
+          .action(ArgAction::SetTrue)
+          .help("Evaluate dependency arguments when printing dependency graph")
+          .requires(cmd::GRAPH),
+      )
+      .arg(
+        Arg::new(arg::GRAPH_FORMAT)
+          .long("graph-format")
+          .env("JUST_GRAPH_FORMAT")
+          .action(ArgAction::Set)
+          .value_parser(clap::value_parser!(GraphFormat))
+          .default_value("dot")
+          .value_name("FORMAT")
+          .help("Print dependency graph as <FORMAT>"),
+      )
       .arg(
         Arg::new(arg::HIGHLIGHT)
           .long("highlight")
@@ -279,6 +390,35 @@ impl Config {
           .help("Highlight echoed recipe lines in bold")
           .overrides_with(arg::NO_HIGHLIGHT),
       )
+      .arg(
+        Arg::new(arg::INTERACTIVE)
+          .long("interactive")
+          .env("JUST_INTERACTIVE")
+          .action(ArgAction::SetTrue)
+          .help("Prompt for arguments not given on the command line, if stdin is a terminal"),
+      )
+      .arg(
+        Arg::new(arg::JOBS)
+          .short('j')
+          .long("jobs")
+          .env("JUST_JOBS")
+          .action(ArgAction::Set)
+          .value_parser(value_parser!(NonZeroUsize))
+          .help(
+            "Run up to <JOBS> recipes at once. Invocations and dependencies that do not depend on \
+             each other run concurrently when <JOBS> is greater than one.",
+          ),
+      )
+      .arg(
+        Arg::new(arg::KEEP_GOING)
+          .long("keep-going")
+          .env("JUST_KEEP_GOING")
+          .action(ArgAction::SetTrue)
+          .help(
+            "Keep running recipes that do not depend on failed recipes, and print a summary of \
+             passed, failed, and skipped recipes",
+          ),
+      )
       .arg(
         Arg::new(arg::JUSTFILE)
           .short('f')
@@ -321,6 +461,13 @@ impl Config {
           .action(ArgAction::SetTrue)
           .help("Don't show aliases in list"),
       )
+      .arg(
+        Arg::new(arg::NO_CONFIG)
+          .long("no-config")
+          .env("JUST_NO_CONFIG")
+          .action(ArgAction::SetTrue)
+          .help("Don't load user or project config files"),
+      )
       .arg(
         Arg::new(arg::NO_DEPS)
           .long("no-deps")
@@ -344,6 +491,20 @@ impl Config {
           .help("Don't highlight echoed recipe lines in bold")
           .overrides_with(arg::HIGHLIGHT),
       )
+      .arg(
+        Arg::new(arg::NO_TIMESTAMP)
+          .long("no-timestamp")
+          .action(ArgAction::SetTrue)
+          .help("Don't print recipe command timestamps, overriding config files")
+          .overrides_with(arg::TIMESTAMP),
+      )
+      .arg(
+        Arg::new(arg::NO_UNSORTED)
+          .long("no-unsorted")
+          .action(ArgAction::SetTrue)
+          .help("Return list and summary entries sorted, overriding config files")
+          .overrides_with(arg::UNSORTED),
+      )
       .arg(
         Arg::new(arg::ONE)
           .long("one")
@@ -351,6 +512,21 @@ impl Config {
           .action(ArgAction::SetTrue)
           .help("Forbid multiple recipes from being invoked on the command line"),
       )
+      .arg(
+        Arg::new(arg::PLAN)
+          .long("plan")
+          .env("JUST_PLAN")
+          .action(ArgAction::SetTrue)
+          .help("Print the recipes that would be run, without running them"),
+      )
+      .arg(
+        Arg::new(arg::PLAN_EVAL)
+          .long("plan-eval")
+          .env("JUST_PLAN_EVAL")
+          .action(ArgAction::SetTrue)
+          .help("Run backticks when printing plan")
+          .requires(arg::PLAN),
+      )
       .arg(
         Arg::new(arg::QUIET)
           .short('q')
@@ -360,6 +536,17 @@ impl Config {
           .help("Suppress all output")
           .conflicts_with(arg::DRY_RUN),
       )
+      .arg(
+        Arg::new(arg::REPORT)
+          .long("report")
+          .env("JUST_REPORT")
+          .action(ArgAction::Set)
+          .value_parser(value_parser!(Report))
+          .value_name("FORMAT=PATH")
+          .help(
+            "Write a report of executed recipes to <PATH> in <FORMAT> [possible formats: junit]",
+          ),
+      )
       .arg(
         Arg::new(arg::ALLOW_MISSING)
           .long("allow-missing")
@@ -404,12 +591,24 @@ impl Config {
           .value_parser(value_parser!(PathBuf))
           .help("Save temporary files to <TEMPDIR>."),
       )
+      .arg(
+        Arg::new(arg::TIMEOUT)
+          .long("timeout")
+          .env("JUST_TIMEOUT")
+          .action(ArgAction::Set)
+          .value_parser(value_parser!(Timeout))
+          .value_name("DURATION")
+          .help(
+            "Stop recipes without a `[timeout]` attribute after <DURATION>, e.g. `30s` or `5m`",
+          ),
+      )
       .arg(
         Arg::new(arg::TIMESTAMP)
           .action(ArgAction::SetTrue)
           .long("timestamp")
           .env("JUST_TIMESTAMP")
-          .help("Print recipe command timestamps"),
+          .help("Print recipe command timestamps")
+          .overrides_with(arg::NO_TIMESTAMP),
       )
       .arg(
         Arg::new(arg::TIMESTAMP_FORMAT)
@@ -425,7 +624,8 @@ impl Config {
           .env("JUST_UNSORTED")
           .short('u')
           .action(ArgAction::SetTrue)
-          .help("Return list and summary entries in source order"),
+          .help("Return list and summary entries in source order")
+          .overrides_with(arg::NO_UNSORTED),
       )
       .arg(
         Arg::new(arg::UNSTABLE)
@@ -460,6 +660,13 @@ impl Config {
           .action(ArgAction::SetTrue)
           .help("Automatically confirm all recipes."),
       )
+      .arg(
+        Arg::new(cmd::CACHE_CLEAN)
+          .long("cache-clean")
+          .action(ArgAction::SetTrue)
+          .help("Remove cached content hashes of `[cache]` recipes")
+          .help_heading(cmd::HEADING),
+      )
       .arg(
         Arg::new(cmd::CHANGELOG)
           .long("changelog")
@@ -473,7 +680,8 @@ impl Config {
           .action(ArgAction::SetTrue)
           .help(
             "Select one or more recipes to run using a binary chooser. If `--chooser` is not \
-             passed the chooser defaults to the value of $JUST_CHOOSER, falling back to `fzf`",
+             passed the chooser defaults to the value of $JUST_CHOOSER, falling back to `fzf`, or \
+             a built-in chooser if `fzf` is not installed",
           )
           .help_heading(cmd::HEADING),
       )
@@ -491,6 +699,21 @@ impl Config {
           )
           .help_heading(cmd::HEADING),
       )
+      .arg(
+        Arg::new(cmd::COMPLETE)
+          .long("complete")
+          .num_args(0..)
+          .allow_hyphen_values(true)
+          .action(ArgAction::Set)
+          .value_name("WORDS")
+          .conflicts_with(arg::ARGUMENTS)
+          .hide(true)
+          .help(
+            "Print completions for the argument following <WORDS>. For use by shell completion \
+             scripts.",
+          )
+          .help_heading(cmd::HEADING),
+      )
       .arg(
         Arg::new(cmd::COMPLETIONS)
           .long("completions")
@@ -535,6 +758,16 @@ impl Config {
           .help("Format and overwrite justfile")
           .help_heading(cmd::HEADING),
       )
+      .arg(
+        Arg::new(cmd::GRAPH)
+          .long("graph")
+          .action(ArgAction::SetTrue)
+          .help(
+            "Print recipe dependency graph. If recipes are given as arguments, only print \
+             recipes reachable from them.",
+          )
+          .help_heading(cmd::HEADING),
+      )
       .arg(
         Arg::new(cmd::GROUPS)
           .long("groups")
@@ -550,6 +783,13 @@ impl Config {
           .help("Initialize new justfile in project root")
           .help_heading(cmd::HEADING),
       )
+      .arg(
+        Arg::new(cmd::LINT)
+          .long("lint")
+          .action(ArgAction::SetTrue)
+          .help("Print lint warnings and exit with failure if there are any")
+          .help_heading(cmd::HEADING),
+      )
       .arg(
         Arg::new(cmd::LIST)
           .short('l')
@@ -561,6 +801,13 @@ impl Config {
           .help("List available recipes in <MODULE> or root if omitted")
           .help_heading(cmd::HEADING),
       )
+      .arg(
+        Arg::new(cmd::LSP)
+          .long("lsp")
+          .action(ArgAction::SetTrue)
+          .help("Run language server, communicating over stdin and stdout")
+          .help_heading(cmd::HEADING),
+      )
       .arg(
         Arg::new(cmd::MAN)
           .long("man")
@@ -590,6 +837,13 @@ impl Config {
           .help("Show recipe at <PATH>")
           .help_heading(cmd::HEADING),
       )
+      .arg(
+        Arg::new(cmd::SHOW_CONFIG)
+          .long("show-config")
+          .action(ArgAction::SetTrue)
+          .help("Print effective values of configurable options and where they were set")
+          .help_heading(cmd::HEADING),
+      )
       .arg(
         Arg::new(cmd::SUMMARY)
           .long("summary")
@@ -604,6 +858,16 @@ impl Config {
           .help("List names of variables")
           .help_heading(cmd::HEADING),
       )
+      .arg(
+        Arg::new(cmd::WATCH)
+          .long("watch")
+          .action(ArgAction::SetTrue)
+          .help(
+            "Run recipes, then rerun them when the justfile, its imports and modules, or files \
+             matching their `[watch]` patterns change",
+          )
+          .help_heading(cmd::HEADING),
+      )
       .group(ArgGroup::new("SUBCOMMAND").args(cmd::ALL))
       .arg(
         Arg::new(arg::ARGUMENTS)
@@ -710,13 +974,46 @@ impl Config {
       }
     }
 
-    let subcommand = if matches.get_flag(cmd::CHANGELOG) {
+    let invocation_directory = env::current_dir().context(config_error::CurrentDirContext)?;
+
+    let mut layers = ConfigLayers::new(
+      matches,
+      &invocation_directory,
+      matches.get_flag(arg::NO_CONFIG),
+    )?;
+
+    let chooser = layers.string("chooser");
+    let color = layers.color("color");
+    let list_heading = layers.string("list-heading").unwrap();
+    let list_prefix = layers.string("list-prefix").unwrap();
+    let shell = layers.string("shell");
+    let shell_args = if matches.get_flag(arg::CLEAR_SHELL_ARGS) {
+      Some(Vec::new())
+    } else {
+      layers.strings("shell-arg")
+    };
+    // shells set in config files rank below `set shell` and its relatives,
+    // unlike shells set on the command line, which override them
+    let (shell, default_shell) = if layers.set_in_file("shell") {
+      (None, shell)
+    } else {
+      (shell, None)
+    };
+    let (shell_args, default_shell_args) = if layers.set_in_file("shell-arg") {
+      (None, shell_args)
+    } else {
+      (shell_args, None)
+    };
+    let timestamp = layers.flag("timestamp");
+    let timestamp_format = layers.string("timestamp-format").unwrap();
+    let unsorted = layers.flag("unsorted");
+
+    let subcommand = if matches.get_flag(cmd::CACHE_CLEAN) {
+      Subcommand::CacheClean
+    } else if matches.get_flag(cmd::CHANGELOG) {
       Subcommand::Changelog
     } else if matches.get_flag(cmd::CHOOSE) {
-      Subcommand::Choose {
-        chooser: matches.get_one::<String>(arg::CHOOSER).map(Into::into),
-        overrides,
-      }
+      Subcommand::Choose { chooser, overrides }
     } else if let Some(values) = matches.get_many::<OsString>(cmd::COMMAND) {
       let mut arguments = values.map(Into::into).collect::<Vec<OsString>>();
       Subcommand::Command {
@@ -724,6 +1021,10 @@ impl Config {
         arguments,
         overrides,
       }
+    } else if let Some(words) = matches.get_many::<String>(cmd::COMPLETE) {
+      Subcommand::Complete {
+        words: words.cloned().collect(),
+      }
     } else if let Some(&shell) = matches.get_one::<completions::Shell>(cmd::COMPLETIONS) {
       Subcommand::Completions { shell }
     } else if matches.get_flag(cmd::DUMP) {
@@ -750,12 +1051,21 @@ impl Config {
       Subcommand::Format
     } else if matches.get_flag(cmd::GROUPS) {
       Subcommand::Groups
+    } else if matches.get_flag(cmd::GRAPH) {
+      Subcommand::Graph {
+        overrides,
+        recipes: positional.arguments,
+      }
     } else if matches.get_flag(cmd::INIT) {
       Subcommand::Init
+    } else if matches.get_flag(cmd::LINT) {
+      Subcommand::Lint
     } else if let Some(path) = matches.get_many::<String>(cmd::LIST) {
       Subcommand::List {
         path: Self::parse_module_path(path)?,
       }
+    } else if matches.get_flag(cmd::LSP) {
+      Subcommand::Lsp
     } else if matches.get_flag(cmd::MAN) {
       Subcommand::Man
     } else if let Some(request) = matches.get_one::<String>(cmd::REQUEST) {
@@ -763,6 +1073,10 @@ impl Config {
         request: serde_json::from_str(request)
           .map_err(|source| ConfigError::RequestParse { source })?,
       }
+    } else if matches.get_flag(cmd::SHOW_CONFIG) {
+      Subcommand::ShowConfig {
+        values: layers.values(),
+      }
     } else if let Some(path) = matches.get_many::<String>(cmd::SHOW) {
       Subcommand::Show {
         path: Self::parse_module_path(path)?,
@@ -771,6 +1085,11 @@ impl Config {
       Subcommand::Summary
     } else if matches.get_flag(cmd::VARIABLES) {
       Subcommand::Variables
+    } else if matches.get_flag(cmd::WATCH) {
+      Subcommand::Watch {
+        arguments: positional.arguments,
+        overrides,
+      }
     } else {
       Subcommand::Run {
         arguments: positional.arguments,
@@ -789,49 +1108,55 @@ impl Config {
       allow_missing: matches.get_flag(arg::ALLOW_MISSING),
       ceiling: matches.get_one::<PathBuf>(arg::CEILING).cloned(),
       check: matches.get_flag(arg::CHECK),
-      color: (*matches.get_one::<UseColor>(arg::COLOR).unwrap()).into(),
+      color: color.into(),
       command_color: matches
         .get_one::<CommandColor>(arg::COMMAND_COLOR)
         .copied()
         .map(CommandColor::into),
       cygpath: matches.get_one::<PathBuf>(arg::CYGPATH).unwrap().clone(),
+      default_shell,
+      default_shell_args,
       dotenv_filename: matches
         .get_one::<String>(arg::DOTENV_FILENAME)
         .map(Into::into),
       dotenv_path: matches.get_one::<PathBuf>(arg::DOTENV_PATH).map(Into::into),
+      dotenv_profile: matches.get_one::<String>(arg::DOTENV_PROFILE).cloned(),
       dry_run: matches.get_flag(arg::DRY_RUN),
       dump_format: matches
         .get_one::<DumpFormat>(arg::DUMP_FORMAT)
         .unwrap()
         .clone(),
+      events: matches.get_one::<EventFormat>(arg::EVENTS).copied(),
+      events_file: matches.get_one::<PathBuf>(arg::EVENTS_FILE).cloned(),
       explain,
+      force: matches.get_flag(arg::FORCE),
+      graph_eval: matches.get_flag(arg::GRAPH_EVAL),
+      graph_format: *matches.get_one::<GraphFormat>(arg::GRAPH_FORMAT).unwrap(),
       highlight: !matches.get_flag(arg::NO_HIGHLIGHT),
-      invocation_directory: env::current_dir().context(config_error::CurrentDirContext)?,
-      list_heading: matches.get_one::<String>(arg::LIST_HEADING).unwrap().into(),
-      list_prefix: matches.get_one::<String>(arg::LIST_PREFIX).unwrap().into(),
+      interactive: matches.get_flag(arg::INTERACTIVE),
+      invocation_directory,
+      jobs: matches.get_one::<NonZeroUsize>(arg::JOBS).copied(),
+      keep_going: matches.get_flag(arg::KEEP_GOING),
+      list_heading,
+      list_prefix,
       list_submodules: matches.get_flag(arg::LIST_SUBMODULES),
       load_dotenv: !matches.get_flag(arg::NO_DOTENV),
       no_aliases: matches.get_flag(arg::NO_ALIASES),
       no_dependencies: matches.get_flag(arg::NO_DEPS),
       one: matches.get_flag(arg::ONE),
+      plan: matches.get_flag(arg::PLAN),
+      plan_eval: matches.get_flag(arg::PLAN_EVAL),
+      report: matches.get_one::<Report>(arg::REPORT).cloned(),
       search_config,
-      shell: matches.get_one::<String>(arg::SHELL).map(Into::into),
-      shell_args: if matches.get_flag(arg::CLEAR_SHELL_ARGS) {
-        Some(Vec::new())
-      } else {
-        matches
-          .get_many::<String>(arg::SHELL_ARG)
-          .map(|s| s.map(Into::into).collect())
-      },
+      shell,
+      shell_args,
       shell_command: matches.get_flag(arg::SHELL_COMMAND),
       subcommand,
       tempdir: matches.get_one::<PathBuf>(arg::TEMPDIR).map(Into::into),
-      timestamp: matches.get_flag(arg::TIMESTAMP),
-      timestamp_format: matches
-        .get_one::<String>(arg::TIMESTAMP_FORMAT)
-        .unwrap()
-        .into(),
-      unsorted: matches.get_flag(arg::UNSORTED),
+      timeout: matches.get_one::<Timeout>(arg::TIMEOUT).copied(),
+      timestamp,
+      timestamp_format,
+      unsorted,
       unstable,
       verbosity: if matches.get_flag(arg::QUIET) {
         Verbosity::Quiet
@@ -870,12 +1195,22 @@ mod tests {
       $(color: $color:expr,)?
       $(dry_run: $dry_run:expr,)?
       $(dump_format: $dump_format:expr,)?
+      $(events: $events:expr,)?
+      $(events_file: $events_file:expr,)?
+      $(graph_eval: $graph_eval:expr,)?
+      $(graph_format: $graph_format:expr,)?
       $(highlight: $highlight:expr,)?
+      $(jobs: $jobs:expr,)?
+      $(keep_going: $keep_going:expr,)?
       $(no_dependencies: $no_dependencies:expr,)?
+      $(plan: $plan:expr,)?
+      $(plan_eval: $plan_eval:expr,)?
+      $(report: $report:expr,)?
       $(search_config: $search_config:expr,)?
       $(shell: $shell:expr,)?
       $(shell_args: $shell_args:expr,)?
       $(subcommand: $subcommand:expr,)?
+      $(timeout: $timeout:expr,)?
       $(unsorted: $unsorted:expr,)?
       $(unstable: $unstable:expr,)?
       $(verbosity: $verbosity:expr,)?
@@ -891,12 +1226,22 @@ mod tests {
           $(color: $color,)?
           $(dry_run: $dry_run,)?
           $(dump_format: $dump_format,)?
+          $(events: $events,)?
+          $(events_file: $events_file,)?
+          $(graph_eval: $graph_eval,)?
+          $(graph_format: $graph_format,)?
           $(highlight: $highlight,)?
+          $(jobs: $jobs,)?
+          $(keep_going: $keep_going,)?
           $(no_dependencies: $no_dependencies,)?
+          $(plan: $plan,)?
+          $(plan_eval: $plan_eval,)?
+          $(report: $report,)?
           $(search_config: $search_config,)?
           $(shell: $shell,)?
           $(shell_args: $shell_args,)?
           $(subcommand: $subcommand,)?
+          $(timeout: $timeout,)?
           $(unsorted: $unsorted,)?
           $(unstable: $unstable,)?
           $(verbosity: $verbosity,)?
@@ -1094,6 +1439,35 @@ mod tests {
     highlight: false,
   }
 
+  test! {
+    name: jobs_default,
+    args: [],
+    jobs: None,
+  }
+
+  test! {
+    name: jobs_long,
+    args: ["--jobs", "4"],
+    jobs: NonZeroUsize::new(4),
+  }
+
+  test! {
+    name: jobs_short,
+    args: ["-j", "2"],
+    jobs: NonZeroUsize::new(2),
+  }
+
+  error! {
+    name: jobs_zero,
+    args: ["--jobs", "0"],
+  }
+
+  test! {
+    name: keep_going,
+    args: ["--keep-going"],
+    keep_going: true,
+  }
+
   test! {
     name: no_deps,
     args: ["--no-deps"],
@@ -1106,6 +1480,24 @@ mod tests {
     no_dependencies: true,
   }
 
+  test! {
+    name: plan,
+    args: ["--plan"],
+    plan: true,
+  }
+
+  test! {
+    name: plan_eval,
+    args: ["--plan", "--plan-eval"],
+    plan: true,
+    plan_eval: true,
+  }
+
+  error! {
+    name: plan_eval_without_plan,
+    args: ["--plan-eval"],
+  }
+
   test! {
     name: unsorted_default,
     args: [],
@@ -1142,6 +1534,36 @@ mod tests {
     verbosity: Verbosity::Quiet,
   }
 
+  test! {
+    name: timeout,
+    args: ["--timeout", "1m30s"],
+    timeout: Some(Timeout(std::time::Duration::from_secs(90))),
+  }
+
+  error! {
+    name: timeout_invalid,
+    args: ["--timeout", "30"],
+  }
+
+  test! {
+    name: report,
+    args: ["--report", "junit=report.xml"],
+    report: Some(Report {
+      format: ReportFormat::Junit,
+      path: "report.xml".into(),
+    }),
+  }
+
+  error! {
+    name: report_without_path,
+    args: ["--report", "junit"],
+  }
+
+  error! {
+    name: report_unknown_format,
+    args: ["--report", "xml=report.xml"],
+  }
+
   error! {
     name: dotenv_both_filename_and_path,
     args: ["--dotenv-filename", "foo", "--dotenv-path", "bar"],
@@ -1335,6 +1757,51 @@ mod tests {
     dump_format: DumpFormat::Json,
   }
 
+  test! {
+    name: events,
+    args: ["--events", "json"],
+    events: Some(EventFormat::Json),
+  }
+
+  test! {
+    name: events_file,
+    args: ["--events", "json", "--events-file", "events.jsonl"],
+    events: Some(EventFormat::Json),
+    events_file: Some("events.jsonl".into()),
+  }
+
+  error! {
+    name: events_file_requires_events,
+    args: ["--events-file", "events.jsonl"],
+  }
+
+  test! {
+    name: graph_eval,
+    args: ["--graph", "--graph-eval"],
+    graph_eval: true,
+    subcommand: Subcommand::Graph {
+      overrides: BTreeMap::new(),
+      recipes: Vec::new(),
+    },
+  }
+
+  error! {
+    name: graph_eval_without_graph,
+    args: ["--graph-eval"],
+  }
+
+  test! {
+    name: graph_format,
+    args: ["--graph-format", "mermaid"],
+    graph_format: GraphFormat::Mermaid,
+  }
+
+  test! {
+    name: subcommand_cache_clean,
+    args: ["--cache-clean"],
+    subcommand: Subcommand::CacheClean,
+  }
+
   test! {
     name: subcommand_edit,
     args: ["--edit"],
@@ -1368,6 +1835,15 @@ mod tests {
     },
   }
 
+  test! {
+    name: subcommand_graph,
+    args: ["--graph", "x=y", "foo", "bar::baz"],
+    subcommand: Subcommand::Graph {
+      overrides: map!{"x": "y"},
+      recipes: vec!["foo".to_owned(), "bar::baz".to_owned()],
+    },
+  }
+
   test! {
     name: subcommand_list_long,
     args: ["--list"],
@@ -1416,6 +1892,15 @@ mod tests {
     unstable: true,
   }
 
+  test! {
+    name: subcommand_watch,
+    args: ["--watch", "bar=baz", "foo"],
+    subcommand: Subcommand::Watch {
+      arguments: vec![String::from("foo")],
+      overrides: map!{"bar": "baz"},
+    },
+  }
+
   test! {
     name: arguments,
     args: ["foo", "bar"],
@@ -1697,6 +2182,26 @@ mod tests {
     },
   }
 
+  error! {
+    name: lint_arguments,
+    args: ["--lint", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, cmd::LINT);
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
+  error! {
+    name: lsp_arguments,
+    args: ["--lsp", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, cmd::LSP);
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
   error! {
     name: summary_arguments,
     args: ["--summary", "bar"],
@@ -3,6 +3,14 @@ use super::*;
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(crate)), context(suffix(Context)))]
 pub(crate) enum ConfigError {
+  #[snafu(display("Failed to parse config file `{}` on line {line}: {message}", path.display()))]
+  ConfigFileParse {
+    line: usize,
+    message: String,
+    path: PathBuf,
+  },
+  #[snafu(display("Failed to read config file `{}`: {source}", path.display()))]
+  ConfigFileRead { path: PathBuf, source: io::Error },
   #[snafu(display("Failed to get current directory: {}", source))]
   CurrentDir { source: io::Error },
   #[snafu(display(
@@ -0,0 +1,231 @@
+use {super::*, toml::Spanned};
+
+const PROJECT_FILENAME: &str = ".justrc";
+
+#[derive(Clone, Copy)]
+enum Kind {
+  Bool,
+  Choice(&'static [&'static str]),
+  List,
+  String,
+}
+
+/// Options which may be set in configuration files, and the kind of value
+/// they take
+const KEYS: &[(&str, Kind)] = &[
+  ("chooser", Kind::String),
+  ("color", Kind::Choice(&["always", "auto", "never"])),
+  ("list-heading", Kind::String),
+  ("list-prefix", Kind::String),
+  ("shell", Kind::String),
+  ("shell-arg", Kind::List),
+  ("timestamp", Kind::Bool),
+  ("timestamp-format", Kind::String),
+  ("unsorted", Kind::Bool),
+];
+
+/// Default values for command-line options, read from the user config file,
+/// `$XDG_CONFIG_HOME/just/config.toml`, or a project config file, `.justrc`,
+/// in the invocation directory or one of its ancestors
+///
+/// Config files are TOML documents whose top-level keys are option names, and
+/// whose values are strings, booleans, or arrays of strings.
+#[derive(Debug)]
+pub(crate) struct ConfigFile {
+  pub(crate) source: ConfigSource,
+  values: BTreeMap<String, ConfigValue>,
+}
+
+impl ConfigFile {
+  pub(crate) fn get(&self, key: &str) -> Option<&ConfigValue> {
+    self.values.get(key)
+  }
+
+  fn load(path: PathBuf, source: ConfigSource) -> ConfigResult<Option<Self>> {
+    let src = match fs::read_to_string(&path) {
+      Ok(src) => src,
+      Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+      Err(source) => return Err(ConfigError::ConfigFileRead { path, source }),
+    };
+
+    Self::parse(&path, &src, source).map(Some)
+  }
+
+  pub(crate) fn project(invocation_directory: &Path) -> ConfigResult<Option<Self>> {
+    for directory in invocation_directory.ancestors() {
+      let path = directory.join(PROJECT_FILENAME);
+      if path.is_file() {
+        return Self::load(path.clone(), ConfigSource::Project(path));
+      }
+    }
+
+    Ok(None)
+  }
+
+  pub(crate) fn user() -> ConfigResult<Option<Self>> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+      .map(PathBuf::from)
+      .filter(|path| path.is_absolute())
+      .or_else(dirs::config_dir);
+
+    let Some(config_dir) = config_dir else {
+      return Ok(None);
+    };
+
+    let path = config_dir.join("just").join("config.toml");
+
+    Self::load(path.clone(), ConfigSource::User(path))
+  }
+
+  fn parse(path: &Path, src: &str, source: ConfigSource) -> ConfigResult<Self> {
+    let error = |offset: usize, message: String| ConfigError::ConfigFileParse {
+      line: src[..offset].matches('\n').count() + 1,
+      message,
+      path: path.into(),
+    };
+
+    let table =
+      toml::from_str::<BTreeMap<Spanned<String>, Spanned<toml::Value>>>(src).map_err(|err| {
+        error(
+          err.span().map_or(0, |span| span.start),
+          err.message().to_owned(),
+        )
+      })?;
+
+    let mut values = BTreeMap::new();
+
+    for (key, value) in table {
+      let offset = key.span().start;
+      let error = |message| error(offset, message);
+
+      let Some(&(_, kind)) = KEYS.iter().find(|(name, _kind)| name == key.get_ref()) else {
+        return Err(error(format!("unknown key `{}`", key.get_ref())));
+      };
+
+      let key = key.into_inner();
+
+      let value = match (kind, value.into_inner()) {
+        (Kind::Bool, toml::Value::Boolean(value)) => ConfigValue::Bool(value),
+        (Kind::Choice(choices), toml::Value::String(value))
+          if choices.contains(&value.as_str()) =>
+        {
+          ConfigValue::String(value)
+        }
+        (Kind::String, toml::Value::String(value)) => ConfigValue::String(value),
+        (Kind::List, toml::Value::Array(array)) => ConfigValue::List(
+          array
+            .into_iter()
+            .map(|value| match value {
+              toml::Value::String(value) => Ok(value),
+              _ => Err(error(format!("`{key}` must be an array of strings"))),
+            })
+            .collect::<ConfigResult<Vec<String>>>()?,
+        ),
+        (Kind::Bool, _) => return Err(error(format!("`{key}` must be a boolean"))),
+        (Kind::Choice(choices), _) => {
+          return Err(error(format!(
+            "`{key}` must be one of {}",
+            List::or_ticked(choices)
+          )))
+        }
+        (Kind::List, _) => return Err(error(format!("`{key}` must be an array of strings"))),
+        (Kind::String, _) => return Err(error(format!("`{key}` must be a string"))),
+      };
+
+      values.insert(key, value);
+    }
+
+    Ok(Self { source, values })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(src: &str) -> ConfigResult<ConfigFile> {
+    ConfigFile::parse(Path::new("config.toml"), src, ConfigSource::Default)
+  }
+
+  #[test]
+  fn values() {
+    let file = parse(
+      r#"
+        # comment
+        chooser = 'fzf --multi'
+        color = "always" # comment
+        list-heading = "Recipes:\n\t\"\\"
+        shell-arg = [
+          "-eu", # comment
+          '''-c''',
+        ]
+        unsorted = true
+      "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      file.get("chooser"),
+      Some(&ConfigValue::String("fzf --multi".into()))
+    );
+    assert_eq!(
+      file.get("color"),
+      Some(&ConfigValue::String("always".into()))
+    );
+    assert_eq!(
+      file.get("list-heading"),
+      Some(&ConfigValue::String("Recipes:\n\t\"\\".into()))
+    );
+    assert_eq!(
+      file.get("shell-arg"),
+      Some(&ConfigValue::List(vec!["-eu".into(), "-c".into()]))
+    );
+    assert_eq!(file.get("unsorted"), Some(&ConfigValue::Bool(true)));
+    assert_eq!(file.get("shell"), None);
+  }
+
+  #[test]
+  fn errors() {
+    #[track_caller]
+    fn case(src: &str, line: usize, message: &str) {
+      match parse(src) {
+        Err(ConfigError::ConfigFileParse {
+          line: actual_line,
+          message: actual_message,
+          ..
+        }) => {
+          assert_eq!(actual_line, line);
+          assert_eq!(actual_message, message);
+        }
+        result => panic!("unexpected result: {result:?}"),
+      }
+    }
+
+    case("\nfoo", 2, "expected `.`, `=`");
+    case("foo = true", 1, "unknown key `foo`");
+    case("\nunsorted = 'yes'", 2, "`unsorted` must be a boolean");
+    case(
+      "color = 'red'",
+      1,
+      "`color` must be one of `always`, `auto`, or `never`",
+    );
+    case("shell = ['sh']", 1, "`shell` must be a string");
+    case(
+      "shell-arg = 'x'",
+      1,
+      "`shell-arg` must be an array of strings",
+    );
+    case(
+      "shell-arg = ['x', 1]",
+      1,
+      "`shell-arg` must be an array of strings",
+    );
+    case("shell = 'sh' 'bash'", 1, "expected newline, `#`");
+    case("shell = \"sh", 1, "invalid basic string");
+    case(
+      "shell = 'a'\nshell = 'b'",
+      2,
+      "duplicate key `shell` in document root",
+    );
+  }
+}
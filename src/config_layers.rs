@@ -0,0 +1,127 @@
+use {
+  super::*,
+  clap::{parser::ValueSource, ArgMatches},
+};
+
+/// Resolves options which may be set in configuration files, recording the
+/// effective value of each and where it came from, for `--show-config`
+///
+/// Options set on the command line take precedence over those set by
+/// environment variables, which take precedence over the project config file,
+/// which takes precedence over the user config file.
+pub(crate) struct ConfigLayers<'a> {
+  files: Vec<ConfigFile>,
+  matches: &'a ArgMatches,
+  values: Vec<(String, ConfigValue, ConfigSource)>,
+}
+
+impl<'a> ConfigLayers<'a> {
+  pub(crate) fn new(
+    matches: &'a ArgMatches,
+    invocation_directory: &Path,
+    no_config: bool,
+  ) -> ConfigResult<Self> {
+    let files = if no_config {
+      Vec::new()
+    } else {
+      ConfigFile::project(invocation_directory)?
+        .into_iter()
+        .chain(ConfigFile::user()?)
+        .collect()
+    };
+
+    Ok(Self {
+      files,
+      matches,
+      values: Vec::new(),
+    })
+  }
+
+  pub(crate) fn color(&mut self, key: &str) -> UseColor {
+    let color = self.string(key).unwrap();
+    UseColor::from_str(&color, false).unwrap()
+  }
+
+  /// The value of boolean flag `key`, which is false if `--no-KEY` was passed
+  /// on the command line, since flags set in config files cannot otherwise be
+  /// turned off
+  pub(crate) fn flag(&mut self, key: &str) -> bool {
+    if self.matches.get_flag(&format!("NO-{}", key.to_uppercase())) {
+      self.values.push((
+        key.into(),
+        ConfigValue::Bool(false),
+        ConfigSource::CommandLine,
+      ));
+      return false;
+    }
+
+    let value = ConfigValue::Bool(self.matches.get_flag(&key.to_uppercase()));
+    match self.resolve(key, Some(value)) {
+      Some(ConfigValue::Bool(value)) => value,
+      _ => unreachable!(),
+    }
+  }
+
+  /// The value of `key`, taken from the command line or an environment
+  /// variable if `value_source` indicates that `argument` was set by one of
+  /// them, and otherwise from the first config file which sets it, falling
+  /// back to `argument`, if it is a default value.
+  fn resolve(&mut self, key: &str, argument: Option<ConfigValue>) -> Option<ConfigValue> {
+    let (value, source) = match self.matches.value_source(&key.to_uppercase()) {
+      Some(ValueSource::CommandLine) => (argument?, ConfigSource::CommandLine),
+      Some(ValueSource::EnvVariable) => (
+        argument?,
+        ConfigSource::Environment(format!("JUST_{}", key.to_uppercase().replace('-', "_"))),
+      ),
+      _ => self
+        .files
+        .iter()
+        .find_map(|file| Some((file.get(key)?.clone(), file.source.clone())))
+        .or_else(|| Some((argument?, ConfigSource::Default)))?,
+    };
+
+    self.values.push((key.into(), value.clone(), source));
+
+    Some(value)
+  }
+
+  /// Whether the value of `key`, which has already been resolved, was taken
+  /// from a config file
+  pub(crate) fn set_in_file(&self, key: &str) -> bool {
+    self.values.iter().any(|(name, _value, source)| {
+      name == key && matches!(source, ConfigSource::Project(_) | ConfigSource::User(_))
+    })
+  }
+
+  pub(crate) fn string(&mut self, key: &str) -> Option<String> {
+    let value = self
+      .matches
+      .get_raw(&key.to_uppercase())
+      .and_then(|mut values| values.next())
+      .map(|value| ConfigValue::String(value.to_string_lossy().into_owned()));
+
+    match self.resolve(key, value)? {
+      ConfigValue::String(value) => Some(value),
+      _ => unreachable!(),
+    }
+  }
+
+  pub(crate) fn strings(&mut self, key: &str) -> Option<Vec<String>> {
+    let value = self.matches.get_raw(&key.to_uppercase()).map(|values| {
+      ConfigValue::List(
+        values
+          .map(|value| value.to_string_lossy().into_owned())
+          .collect(),
+      )
+    });
+
+    match self.resolve(key, value)? {
+      ConfigValue::List(values) => Some(values),
+      _ => unreachable!(),
+    }
+  }
+
+  pub(crate) fn values(self) -> Vec<(String, ConfigValue, ConfigSource)> {
+    self.values
+  }
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+/// Where the value of a configurable option came from
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ConfigSource {
+  CommandLine,
+  Default,
+  Environment(String),
+  Project(PathBuf),
+  User(PathBuf),
+}
+
+impl Display for ConfigSource {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::CommandLine => write!(f, "command line"),
+      Self::Default => write!(f, "default"),
+      Self::Environment(variable) => write!(f, "environment variable `{variable}`"),
+      Self::Project(path) => write!(f, "project config `{}`", path.display()),
+      Self::User(path) => write!(f, "user config `{}`", path.display()),
+    }
+  }
+}
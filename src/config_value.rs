@@ -0,0 +1,46 @@
+use super::*;
+
+/// A value in a configuration file
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ConfigValue {
+  Bool(bool),
+  List(Vec<String>),
+  String(String),
+}
+
+impl ConfigValue {
+  fn write_string(f: &mut Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+      match c {
+        '"' => write!(f, "\\\"")?,
+        '\\' => write!(f, "\\\\")?,
+        '\n' => write!(f, "\\n")?,
+        '\r' => write!(f, "\\r")?,
+        '\t' => write!(f, "\\t")?,
+        c if c.is_control() => write!(f, "\\u{:04X}", u32::from(c))?,
+        c => write!(f, "{c}")?,
+      }
+    }
+    write!(f, "\"")
+  }
+}
+
+impl Display for ConfigValue {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Bool(value) => write!(f, "{value}"),
+      Self::List(values) => {
+        write!(f, "[")?;
+        for (i, value) in values.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          Self::write_string(f, value)?;
+        }
+        write!(f, "]")
+      }
+      Self::String(value) => Self::write_string(f, value),
+    }
+  }
+}
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, std::time::Duration};
 
 #[derive(Debug)]
 pub(crate) enum Error<'src> {
@@ -8,10 +8,10 @@ pub(crate) enum Error<'src> {
   },
   ArgumentCountMismatch {
     recipe: &'src str,
-    parameters: Vec<Parameter<'src>>,
     found: usize,
     min: usize,
     max: usize,
+    usage: Box<RecipeUsage<'src>>,
   },
   Assert {
     message: String,
@@ -20,12 +20,20 @@ pub(crate) enum Error<'src> {
     token: Token<'src>,
     output_error: OutputError,
   },
+  CacheIo {
+    path: PathBuf,
+    io_error: io::Error,
+  },
+  ChooserCancelled,
   ChooserInvoke {
     shell_binary: String,
     shell_arguments: String,
     chooser: OsString,
     io_error: io::Error,
   },
+  ChooserIo {
+    io_error: io::Error,
+  },
   ChooserRead {
     chooser: OsString,
     io_error: io::Error,
@@ -91,12 +99,20 @@ pub(crate) enum Error<'src> {
     variable: String,
     suggestion: Option<Suggestion<'src>>,
   },
+  EventsFile {
+    path: PathBuf,
+    io_error: io::Error,
+  },
   ExcessInvocations {
     invocations: usize,
   },
   ExpectedSubmoduleButFoundRecipe {
     path: String,
   },
+  FlagValue {
+    flag: &'src str,
+    recipe: &'src str,
+  },
   FormatCheckFoundDiff,
   FunctionCall {
     function: Name<'src>,
@@ -115,29 +131,64 @@ pub(crate) enum Error<'src> {
   Interrupted {
     signal: Signal,
   },
+  InvalidArgument {
+    argument: String,
+    parameter: &'src str,
+    parameter_type: ParameterType<'src>,
+    recipe: &'src str,
+  },
   Io {
     recipe: &'src str,
     io_error: io::Error,
   },
+  Lint {
+    count: usize,
+  },
   Load {
     path: PathBuf,
     io_error: io::Error,
   },
+  LspIo {
+    io_error: io::Error,
+  },
   MissingImportFile {
     path: Token<'src>,
   },
   MissingModuleFile {
     module: Name<'src>,
   },
+  ModificationTime {
+    path: PathBuf,
+    io_error: io::Error,
+  },
   NoChoosableRecipes,
   NoDefaultRecipe,
   NoRecipes,
   NotConfirmed {
     recipe: &'src str,
   },
+  OptionMissingValue {
+    option: &'src str,
+    recipe: &'src str,
+  },
+  PromptRead {
+    io_error: io::Error,
+    recipe: &'src str,
+  },
+  RecipeFailures {
+    count: usize,
+  },
   RegexCompile {
     source: regex::Error,
   },
+  ReportWrite {
+    path: PathBuf,
+    io_error: io::Error,
+  },
+  RetriesExhausted {
+    attempts: u32,
+    error: Box<Error<'src>>,
+  },
   RuntimeDirIo {
     io_error: io::Error,
     path: PathBuf,
@@ -185,10 +236,18 @@ pub(crate) enum Error<'src> {
     recipe: &'src str,
     io_error: io::Error,
   },
+  Timeout {
+    elapsed: Duration,
+    recipe: &'src str,
+  },
   Unknown {
     recipe: &'src str,
     line_number: Option<usize>,
   },
+  UnknownOption {
+    option: String,
+    recipe: &'src str,
+  },
   UnknownOverrides {
     overrides: Vec<String>,
   },
@@ -228,11 +287,12 @@ impl<'src> Error<'src> {
         ..
       }
       | Self::Interrupted { signal } => Some(signal.code()),
+      Self::RetriesExhausted { error, .. } => error.code(),
       _ => None,
     }
   }
 
-  fn context(&self) -> Option<Token<'src>> {
+  pub(crate) fn context(&self) -> Option<Token<'src>> {
     match self {
       Self::AmbiguousModuleFile { module, .. } | Self::MissingModuleFile { module, .. } => {
         Some(module.token)
@@ -251,14 +311,25 @@ impl<'src> Error<'src> {
     }
   }
 
+  /// The error message, without the `error: ` prefix or source context
+  pub(crate) fn message(&self) -> String {
+    let message = self.color_display(Color::never()).to_string();
+    let message = message.lines().next().unwrap_or_default();
+    message
+      .strip_prefix("error: ")
+      .unwrap_or(message)
+      .to_owned()
+  }
+
   pub(crate) fn print_message(&self) -> bool {
-    !matches!(
-      self,
+    match self {
       Error::Code {
         print_message: false,
         ..
-      }
-    )
+      } => false,
+      Error::RetriesExhausted { error, .. } => error.print_message(),
+      _ => true,
+    }
   }
 }
 
@@ -327,10 +398,15 @@ impl ColorDisplay for Error<'_> {
           }?,
         OutputError::Utf8(utf8_error) => write!(f, "Backtick succeeded but stdout was not utf8: {utf8_error}")?,
       }
+      CacheIo { path, io_error } => {
+        write!(f, "I/O error in cache `{}`: {io_error}", path.display())?;
+      }
+      ChooserCancelled => write!(f, "Chooser was cancelled")?,
       ChooserInvoke { shell_binary, shell_arguments, chooser, io_error} => {
         let chooser = chooser.to_string_lossy();
         write!(f, "Chooser `{shell_binary} {shell_arguments} {chooser}` invocation failed: {io_error}")?;
       }
+      ChooserIo { io_error } => write!(f, "I/O error in built-in chooser: {io_error}")?,
       ChooserRead { chooser, io_error } => {
         let chooser = chooser.to_string_lossy();
         write!(f, "Failed to read output from chooser `{chooser}`: {io_error}")?;
@@ -406,12 +482,18 @@ impl ColorDisplay for Error<'_> {
           write!(f, "\n{suggestion}")?;
         }
       }
+      EventsFile { path, io_error } => {
+        write!(f, "Failed to create events file `{}`: {io_error}", path.display())?;
+      }
       ExcessInvocations { invocations } => {
         write!(f, "Expected 1 command-line recipe invocation but found {invocations}.")?;
       },
       ExpectedSubmoduleButFoundRecipe { path } => {
         write!(f, "Expected submodule at `{path}` but found recipe.")?;
       },
+      FlagValue { flag, recipe } => {
+        write!(f, "Recipe `{recipe}` flag `--{flag}` does not take a value")?;
+      }
       FormatCheckFoundDiff => {
         write!(f, "Formatted justfile differs from original.")?;
       }
@@ -435,6 +517,21 @@ impl ColorDisplay for Error<'_> {
       Interrupted { signal } => {
         write!(f, "Interrupted by {signal}")?;
       }
+      InvalidArgument { argument, parameter, parameter_type, recipe } => {
+        write!(f, "Recipe `{recipe}` parameter `{parameter}` got `{argument}` but must be ")?;
+        match parameter_type {
+          ParameterType::Bool => write!(f, "`true` or `false`")?,
+          ParameterType::Choice(choices) => {
+            let choices = choices.iter().map(|choice| &choice.cooked);
+            if choices.len() == 1 {
+              write!(f, "{}", List::or_ticked(choices))?;
+            } else {
+              write!(f, "one of {}", List::or_ticked(choices))?;
+            }
+          }
+          ParameterType::Int => write!(f, "an integer")?,
+        }
+      }
       Io { recipe, io_error } => {
         match io_error.kind() {
           io::ErrorKind::NotFound => write!(f, "Recipe `{recipe}` could not be run because just could not find the shell: {io_error}"),
@@ -442,18 +539,40 @@ impl ColorDisplay for Error<'_> {
           _ => write!(f, "Recipe `{recipe}` could not be run because of an IO error while launching the shell: {io_error}"),
         }?;
       }
+      Lint { count } => {
+        write!(f, "Found {count} lint {}.", Count("warning", *count))?;
+      }
       Load { io_error, path } => {
         write!(f, "Failed to read justfile at `{}`: {io_error}", path.display())?;
       }
+      LspIo { io_error } => write!(f, "Language server I/O error: {io_error}")?,
       MissingImportFile { .. } => write!(f, "Could not find source file for import.")?,
       MissingModuleFile { module } => write!(f, "Could not find source file for module `{module}`.")?,
+      ModificationTime { path, io_error } => {
+        write!(f, "Failed to get modification time of `{}`: {io_error}", path.display())?;
+      }
       NoChoosableRecipes => write!(f, "Justfile contains no choosable recipes.")?,
       NoDefaultRecipe => write!(f, "Justfile contains no default recipe.")?,
       NoRecipes => write!(f, "Justfile contains no recipes.")?,
       NotConfirmed { recipe } => {
         write!(f, "Recipe `{recipe}` was not confirmed")?;
       }
+      OptionMissingValue { option, recipe } => {
+        write!(f, "Recipe `{recipe}` option `--{option}` requires a value")?;
+      }
+      PromptRead { io_error, recipe } => {
+        write!(f, "Failed to read argument for recipe `{recipe}` from stdin: {io_error}")?;
+      }
+      RecipeFailures { count } => {
+        write!(f, "{count} {} failed.", Count("recipe", *count))?;
+      }
       RegexCompile { source } => write!(f, "{source}")?,
+      ReportWrite { path, io_error } => {
+        write!(f, "Failed to write report `{}`: {io_error}", path.display())?;
+      }
+      RetriesExhausted { attempts, error } => {
+        write!(f, "{} (gave up after {attempts} attempts)", error.message())?;
+      }
       RuntimeDirIo { io_error, path } => {
         write!(f, "I/O error in runtime dir `{}`: {io_error}", path.display())?;
       }
@@ -498,6 +617,9 @@ impl ColorDisplay for Error<'_> {
         write!(f, "Recipe `{recipe}` could not be run because of an IO error while trying to create a temporary \
                    directory or write a file to that directory: {io_error}")?;
       }
+      Timeout { elapsed, recipe } => {
+        write!(f, "Recipe `{recipe}` timed out after {}", self::Timeout(*elapsed))?;
+      }
       Unknown { recipe, line_number} => {
         if let Some(n) = line_number {
           write!(f, "Recipe `{recipe}` failed on line {n} for an unknown reason")?;
@@ -508,6 +630,9 @@ impl ColorDisplay for Error<'_> {
       UnknownSubmodule { path } => {
         write!(f, "Justfile does not contain submodule `{path}`")?;
       }
+      UnknownOption { option, recipe } => {
+        write!(f, "Recipe `{recipe}` does not have option `--{option}`")?;
+      }
       UnknownOverrides { overrides } => {
         let count = Count("Variable", overrides.len());
         let overrides = List::and_ticked(overrides);
@@ -530,15 +655,9 @@ impl ColorDisplay for Error<'_> {
 
     write!(f, "{}", color.message().suffix())?;
 
-    if let ArgumentCountMismatch {
-      recipe, parameters, ..
-    } = self
-    {
+    if let ArgumentCountMismatch { usage, .. } = self {
       writeln!(f)?;
-      write!(f, "{}:\n    just {recipe}", color.message().paint("usage"))?;
-      for param in parameters {
-        write!(f, " {}", param.color_display(color))?;
-      }
+      write!(f, "{}", usage.color_display(color))?;
     }
 
     if let Some(token) = self.context() {
@@ -3,6 +3,7 @@ use super::*;
 pub(crate) struct Evaluator<'src: 'run, 'run> {
   pub(crate) assignments: Option<&'run Table<'src, Assignment<'src>>>,
   pub(crate) context: ExecutionContext<'src, 'run>,
+  pub(crate) functions: FunctionScope<'run, 'src>,
   pub(crate) is_dependency: bool,
   pub(crate) scope: Scope<'src, 'run>,
 }
@@ -14,6 +15,7 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     module: &'run Justfile<'src>,
     overrides: &BTreeMap<String, String>,
     parent: &'run Scope<'src, 'run>,
+    root: &'run Justfile<'src>,
     search: &'run Search,
   ) -> RunResult<'src, Scope<'src, 'run>>
   where
@@ -23,6 +25,8 @@ impl<'src, 'run> Evaluator<'src, 'run> {
       config,
       dotenv,
       module,
+      recipe_failed: None,
+      root,
       search,
     };
 
@@ -31,13 +35,16 @@ impl<'src, 'run> Evaluator<'src, 'run> {
 
     for (name, value) in overrides {
       if let Some(assignment) = module.assignments.get(name) {
+        let value = value.clone().into();
+
         scope.bind(Binding {
           constant: false,
           export: assignment.export,
           file_depth: 0,
           name: assignment.name,
           private: assignment.private,
-          value: value.clone(),
+          secret: assignment.secret,
+          value,
         });
       } else {
         unknown_overrides.push(name.clone());
@@ -53,6 +60,7 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     let mut evaluator = Self {
       context,
       assignments: Some(&module.assignments),
+      functions: FunctionScope::new(root, module),
       scope,
       is_dependency: false,
     };
@@ -64,17 +72,19 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     Ok(evaluator.scope)
   }
 
-  fn evaluate_assignment(&mut self, assignment: &Assignment<'src>) -> RunResult<'src, &str> {
+  fn evaluate_assignment(&mut self, assignment: &Assignment<'src>) -> RunResult<'src, &Value> {
     let name = assignment.name.lexeme();
 
     if !self.scope.bound(name) {
-      let value = self.evaluate_expression(&assignment.value)?;
+      let value = self.evaluate_value(&assignment.value)?;
+
       self.scope.bind(Binding {
         constant: false,
         export: assignment.export,
         file_depth: 0,
         name: assignment.name,
         private: assignment.private,
+        secret: assignment.secret || self.is_secret(&assignment.value),
         value,
       });
     }
@@ -86,17 +96,27 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     &mut self,
     expression: &Expression<'src>,
   ) -> RunResult<'src, String> {
+    Ok(self.evaluate_value(expression)?.into_string())
+  }
+
+  pub(crate) fn evaluate_value(&mut self, expression: &Expression<'src>) -> RunResult<'src, Value> {
+    self
+      .evaluate_value_unredacted(expression)
+      .map_err(|error| self.redact(expression, error))
+  }
+
+  fn evaluate_value_unredacted(&mut self, expression: &Expression<'src>) -> RunResult<'src, Value> {
     match expression {
       Expression::And { lhs, rhs } => {
-        let lhs = self.evaluate_expression(lhs)?;
+        let lhs = self.evaluate_value(lhs)?;
         if lhs.is_empty() {
-          return Ok(String::new());
+          return Ok(String::new().into());
         }
-        self.evaluate_expression(rhs)
+        self.evaluate_value(rhs)
       }
       Expression::Assert { condition, error } => {
         if self.evaluate_condition(condition)? {
-          Ok(String::new())
+          Ok(String::new().into())
         } else {
           Err(Error::Assert {
             message: self.evaluate_expression(error)?,
@@ -104,10 +124,12 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         }
       }
       Expression::Backtick { contents, token } => {
-        if self.context.config.dry_run {
-          Ok(format!("`{contents}`"))
+        if self.context.config.dry_run
+          || (self.context.config.plan && !self.context.config.plan_eval)
+        {
+          Ok(format!("`{contents}`").into())
         } else {
-          Ok(self.run_backtick(contents, token)?)
+          Ok(self.run_backtick(contents, token)?.into())
         }
       }
       Expression::Call { thunk } => {
@@ -135,16 +157,12 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             args: (a, rest),
             ..
           } => {
-            let a = self.evaluate_expression(a)?;
-            let mut rest_evaluated = Vec::new();
-            for arg in rest {
-              rest_evaluated.push(self.evaluate_expression(arg)?);
+            let arguments = self.evaluate_arguments(iter::once(a.as_ref()).chain(rest))?;
+            if let [a, rest @ ..] = arguments.as_slice() {
+              function(function::Context::new(self, thunk.name()), a, rest)
+            } else {
+              Err("expected at least 1 argument but got 0".into())
             }
-            function(
-              function::Context::new(self, thunk.name()),
-              &a,
-              &rest_evaluated,
-            )
           }
           Binary {
             function,
@@ -160,18 +178,16 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             args: ([a, b], rest),
             ..
           } => {
-            let a = self.evaluate_expression(a)?;
-            let b = self.evaluate_expression(b)?;
-            let mut rest_evaluated = Vec::new();
-            for arg in rest {
-              rest_evaluated.push(self.evaluate_expression(arg)?);
+            let arguments =
+              self.evaluate_arguments([a.as_ref(), b.as_ref()].into_iter().chain(rest))?;
+            if let [a, b, rest @ ..] = arguments.as_slice() {
+              function(function::Context::new(self, thunk.name()), a, b, rest)
+            } else {
+              Err(format!(
+                "expected at least 2 arguments but got {}",
+                arguments.len()
+              ))
             }
-            function(
-              function::Context::new(self, thunk.name()),
-              &a,
-              &b,
-              &rest_evaluated,
-            )
           }
           Ternary {
             function,
@@ -183,16 +199,80 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             let c = self.evaluate_expression(c)?;
             function(function::Context::new(self, thunk.name()), &a, &b, &c)
           }
+          UnaryValue { function, arg, .. } => {
+            let arg = self.evaluate_value(arg)?;
+            return function(function::Context::new(self, thunk.name()), arg).map_err(|message| {
+              Error::FunctionCall {
+                function: thunk.name(),
+                message,
+              }
+            });
+          }
+          BinaryValue {
+            function,
+            args: [a, b],
+            ..
+          } => {
+            let a = self.evaluate_value(a)?;
+            let b = self.evaluate_value(b)?;
+            return function(function::Context::new(self, thunk.name()), a, b).map_err(|message| {
+              Error::FunctionCall {
+                function: thunk.name(),
+                message,
+              }
+            });
+          }
+          Filter { function, list, .. } => {
+            let mut filtered = Vec::new();
+            for element in self.evaluate_value(list)?.into_list() {
+              if !self.apply(*function, element.clone())?.is_empty() {
+                filtered.push(element);
+              }
+            }
+            return Ok(Value::List(filtered));
+          }
+          Map { function, list, .. } => {
+            let mut mapped = Vec::new();
+            for element in self.evaluate_value(list)?.into_list() {
+              mapped.extend(self.apply(*function, element)?.into_list());
+            }
+            return Ok(Value::List(mapped));
+          }
+          UserDefined { name, args, path } => {
+            let Some((functions, function)) = self.functions.resolve(path, name.lexeme()) else {
+              return Err(Error::Internal {
+                message: format!(
+                  "attempted to call unknown function `{}`",
+                  thunk.qualified_name()
+                ),
+              });
+            };
+
+            let mut values = Vec::new();
+            for arg in args {
+              values.push(self.evaluate_value(arg)?);
+            }
+
+            return self.call(functions, function, values);
+          }
         };
-        result.map_err(|message| Error::FunctionCall {
-          function: thunk.name(),
-          message,
-        })
+        result
+          .map(Value::from)
+          .map_err(|message| Error::FunctionCall {
+            function: thunk.name(),
+            message,
+          })
       }
       Expression::Concatenation { lhs, rhs } => {
-        let lhs = self.evaluate_expression(lhs)?;
-        let rhs = self.evaluate_expression(rhs)?;
-        Ok(lhs + &rhs)
+        let lhs = self.evaluate_value(lhs)?;
+        let rhs = self.evaluate_value(rhs)?;
+        match (lhs, rhs) {
+          (Value::List(mut lhs), Value::List(rhs)) => {
+            lhs.extend(rhs);
+            Ok(Value::List(lhs))
+          }
+          (lhs, rhs) => Ok((lhs.into_string() + &rhs.into_string()).into()),
+        }
       }
       Expression::Conditional {
         condition,
@@ -200,38 +280,47 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         otherwise,
       } => {
         if self.evaluate_condition(condition)? {
-          self.evaluate_expression(then)
+          self.evaluate_value(then)
         } else {
-          self.evaluate_expression(otherwise)
+          self.evaluate_value(otherwise)
         }
       }
-      Expression::Group { contents } => self.evaluate_expression(contents),
-      Expression::Join { lhs: None, rhs } => Ok("/".to_string() + &self.evaluate_expression(rhs)?),
+      Expression::Group { contents } => self.evaluate_value(contents),
+      Expression::Join { lhs: None, rhs } => {
+        Ok(("/".to_string() + &self.evaluate_expression(rhs)?).into())
+      }
       Expression::Join {
         lhs: Some(lhs),
         rhs,
       } => {
         let lhs = self.evaluate_expression(lhs)?;
         let rhs = self.evaluate_expression(rhs)?;
-        Ok(lhs + "/" + &rhs)
+        Ok((lhs + "/" + &rhs).into())
+      }
+      Expression::List { elements } => {
+        let mut list = Vec::new();
+        for element in elements {
+          list.extend(self.evaluate_value(element)?.into_list());
+        }
+        Ok(Value::List(list))
       }
       Expression::Or { lhs, rhs } => {
-        let lhs = self.evaluate_expression(lhs)?;
+        let lhs = self.evaluate_value(lhs)?;
         if !lhs.is_empty() {
           return Ok(lhs);
         }
-        self.evaluate_expression(rhs)
+        self.evaluate_value(rhs)
       }
-      Expression::StringLiteral { string_literal } => Ok(string_literal.cooked.clone()),
+      Expression::StringLiteral { string_literal } => Ok(string_literal.cooked.clone().into()),
       Expression::Variable { name, .. } => {
         let variable = name.lexeme();
         if let Some(value) = self.scope.value(variable) {
-          Ok(value.to_owned())
+          Ok(value.clone())
         } else if let Some(assignment) = self
           .assignments
           .and_then(|assignments| assignments.get(variable))
         {
-          Ok(self.evaluate_assignment(assignment)?.to_owned())
+          Ok(self.evaluate_assignment(assignment)?.clone())
         } else {
           Err(Error::Internal {
             message: format!("attempted to evaluate undefined variable `{variable}`"),
@@ -241,6 +330,22 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     }
   }
 
+  /// Evaluate the arguments to a variadic function, expanding lists into
+  /// separate arguments
+  fn evaluate_arguments<'a>(
+    &mut self,
+    arguments: impl Iterator<Item = &'a Expression<'src>>,
+  ) -> RunResult<'src, Vec<String>>
+  where
+    'src: 'a,
+  {
+    let mut evaluated = Vec::new();
+    for argument in arguments {
+      evaluated.extend(self.evaluate_value(argument)?.into_list());
+    }
+    Ok(evaluated)
+  }
+
   fn evaluate_condition(&mut self, condition: &Condition<'src>) -> RunResult<'src, bool> {
     let lhs_value = self.evaluate_expression(&condition.lhs)?;
     let rhs_value = self.evaluate_expression(&condition.rhs)?;
@@ -281,6 +386,7 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         &self.context.module.settings,
         self.context.dotenv,
         &self.scope,
+        &BTreeMap::new(),
         &self.context.module.unexports,
       )
       .stdin(Stdio::inherit())
@@ -294,67 +400,213 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     cmd.output_guard_stdout()
   }
 
+  /// Evaluate `line`, returning it, and it with interpolations which refer to
+  /// secrets replaced with `***`, for display
   pub(crate) fn evaluate_line(
     &mut self,
     line: &Line<'src>,
     continued: bool,
-  ) -> RunResult<'src, String> {
+  ) -> RunResult<'src, (String, String)> {
     let mut evaluated = String::new();
+    let mut redacted = String::new();
     for (i, fragment) in line.fragments.iter().enumerate() {
       match fragment {
         Fragment::Text { token } => {
           let lexeme = token.lexeme().replace("{{{{", "{{");
 
-          if i == 0 && continued {
-            evaluated += lexeme.trim_start();
+          let text = if i == 0 && continued {
+            lexeme.trim_start()
           } else {
-            evaluated += &lexeme;
-          }
+            &lexeme
+          };
+
+          evaluated += text;
+          redacted += text;
         }
         Fragment::Interpolation { expression } => {
-          evaluated += &self.evaluate_expression(expression)?;
+          let value = self.evaluate_expression(expression)?;
+
+          if self.is_secret(expression) {
+            redacted += secrets::REDACTED;
+          } else {
+            redacted += &value;
+          }
+
+          evaluated += &value;
         }
       }
     }
-    Ok(evaluated)
+    Ok((evaluated, redacted))
+  }
+
+  /// Whether `expression` refers to a secret variable or parameter
+  pub(crate) fn is_secret(&self, expression: &Expression<'src>) -> bool {
+    expression.variables().any(|variable| {
+      let name = variable.lexeme();
+      match self.scope.binding(name) {
+        Some(binding) => binding.secret,
+        None => self
+          .assignments
+          .and_then(|assignments| assignments.get(name))
+          .is_some_and(|assignment| assignment.secret),
+      }
+    })
+  }
+
+  /// Redact the message of `error`, which occurred while evaluating
+  /// `expression`, if `expression` refers to secrets, since the message may
+  /// contain their values
+  fn redact(&self, expression: &Expression<'src>, error: Error<'src>) -> Error<'src> {
+    match error {
+      Error::Assert { .. } if self.is_secret(expression) => Error::Assert {
+        message: secrets::REDACTED.into(),
+      },
+      Error::FunctionCall { function, .. } if self.is_secret(expression) => Error::FunctionCall {
+        function,
+        message: secrets::REDACTED.into(),
+      },
+      error => error,
+    }
+  }
+
+  /// Apply the function named `function`, which takes a single argument, to
+  /// `argument`, as `map` and `filter` do
+  fn apply(&self, function: Name<'src>, argument: String) -> RunResult<'src, Value> {
+    if let Some((functions, user_function)) = self.functions.resolve(&[], function.lexeme()) {
+      return self.call(functions, user_function, vec![argument.into()]);
+    }
+
+    let context = function::Context::new(self, function);
+
+    let result = match function::get(function.lexeme()) {
+      Some(Function::Unary(f)) => f(context, &argument).map(Value::from),
+      Some(Function::UnaryOpt(f)) => f(context, &argument, None).map(Value::from),
+      Some(Function::UnaryPlus(f)) => f(context, &argument, &[]).map(Value::from),
+      Some(Function::UnaryValue(f)) => f(context, argument.into()),
+      _ => {
+        return Err(Error::Internal {
+          message: format!("attempted to apply function `{function}` to a single argument"),
+        })
+      }
+    };
+
+    result.map_err(|message| Error::FunctionCall { function, message })
+  }
+
+  /// Call a user-defined function, evaluating its body in a scope where only
+  /// its parameters are bound, and where calls are resolved with `functions`,
+  /// the functions visible from the module in which it is defined
+  fn call(
+    &self,
+    functions: FunctionScope<'run, 'src>,
+    function: &UserFunction<'src>,
+    arguments: Vec<Value>,
+  ) -> RunResult<'src, Value> {
+    let mut scope = Scope::root();
+
+    for (parameter, value) in function.parameters.iter().zip(arguments) {
+      scope.bind(Binding {
+        constant: false,
+        export: false,
+        file_depth: 0,
+        name: *parameter,
+        private: false,
+        secret: false,
+        value,
+      });
+    }
+
+    let mut evaluator = Self {
+      assignments: None,
+      context: self.context,
+      functions,
+      is_dependency: self.is_dependency,
+      scope,
+    };
+
+    evaluator.evaluate_value(&function.body)
+  }
+
+  /// Check that `value` is an acceptable argument for `parameter`, without
+  /// including it in the error if it is `secret`
+  fn check(
+    recipe: &Recipe<'src>,
+    parameter: &Parameter<'src>,
+    secret: bool,
+    value: &Value,
+  ) -> RunResult<'src> {
+    let mut result = parameter.check(recipe.name(), value);
+
+    if let Err(Error::InvalidArgument { argument, .. }) = &mut result {
+      if secret {
+        *argument = secrets::REDACTED.into();
+      }
+    }
+
+    result
   }
 
   pub(crate) fn evaluate_parameters(
     context: &ExecutionContext<'src, 'run>,
     is_dependency: bool,
-    arguments: &[String],
-    parameters: &[Parameter<'src>],
+    arguments: &Arguments,
+    recipe: &Recipe<'src>,
     scope: &'run Scope<'src, 'run>,
   ) -> RunResult<'src, (Scope<'src, 'run>, Vec<String>)> {
     let mut evaluator = Self::new(context, is_dependency, scope);
 
     let mut positional = Vec::new();
 
-    let mut rest = arguments;
-    for parameter in parameters {
-      let value = if rest.is_empty() {
-        if let Some(ref default) = parameter.default {
-          let value = evaluator.evaluate_expression(default)?;
-          positional.push(value.clone());
+    let mut rest = arguments.positional.as_slice();
+    for parameter in &recipe.parameters {
+      // parameters are secret if the recipe marks them as secret, or if they
+      // are bound to arguments or defaults which are derived from secrets
+      let mut secret = recipe.is_secret(parameter.name.lexeme());
+      let offset = arguments.positional.len() - rest.len();
+
+      let value = if parameter.kind.is_option() {
+        if let Some(value) = arguments.options.get(parameter.name.lexeme()) {
+          value.clone().into()
+        } else if let Some(ref default) = parameter.default {
+          secret |= evaluator.is_secret(default);
+          evaluator.evaluate_value(default)?
+        } else {
+          "false".into()
+        }
+      } else if rest.is_empty() {
+        if !is_dependency && recipe.prompts(context.config) {
+          let value = evaluator.prompt(recipe, parameter)?;
+          positional.extend(value.clone().into_list());
+          value
+        } else if let Some(ref default) = parameter.default {
+          secret |= evaluator.is_secret(default);
+          let value = evaluator.evaluate_value(default)?;
+          positional.extend(value.clone().into_list());
           value
         } else if parameter.kind == ParameterKind::Star {
-          String::new()
+          Value::List(Vec::new())
         } else {
           return Err(Error::Internal {
             message: "missing parameter without default".to_owned(),
           });
         }
       } else if parameter.kind.is_variadic() {
-        for value in rest {
-          positional.push(value.clone());
-        }
-        let value = rest.to_vec().join(" ");
+        secret |= arguments.secret.range(offset..).next().is_some();
+        positional.extend(rest.iter().cloned());
+        let value = Value::List(rest.to_vec());
         rest = &[];
         value
       } else {
+        secret |= arguments.secret.contains(&offset);
         let value = rest[0].clone();
         positional.push(value.clone());
         rest = &rest[1..];
+        value.into()
+      };
+      Self::check(recipe, parameter, secret, &value)?;
+      let value = if parameter.kind.is_variadic() && !context.module.settings.variadic_lists {
+        value.into_string().into()
+      } else {
         value
       };
       evaluator.scope.bind(Binding {
@@ -363,6 +615,7 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         file_depth: 0,
         name: parameter.name,
         private: false,
+        secret,
         value,
       });
     }
@@ -378,10 +631,96 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     Self {
       assignments: None,
       context: *context,
+      functions: FunctionScope::new(context.root, context.module),
       is_dependency,
       scope: scope.child(),
     }
   }
+
+  /// Prompt for the argument of `parameter`, showing its default and
+  /// choices, if any, until an acceptable argument is entered
+  fn prompt(
+    &mut self,
+    recipe: &Recipe<'src>,
+    parameter: &Parameter<'src>,
+  ) -> RunResult<'src, Value> {
+    let default = parameter
+      .default
+      .as_ref()
+      .map(|default| self.evaluate_value(default))
+      .transpose()?;
+
+    let color = self.context.config.color.stderr();
+
+    let choices = parameter
+      .ty
+      .as_ref()
+      .map(ParameterType::completions)
+      .filter(|choices| !choices.is_empty())
+      .map(|choices| format!(" ({})", choices.join(" | ")))
+      .unwrap_or_default();
+
+    let shown_default = default
+      .as_ref()
+      .map(|default| {
+        let default = if recipe.is_secret(parameter.name.lexeme()) {
+          secrets::REDACTED.into()
+        } else {
+          default.to_string()
+        };
+        format!(" [{}]", color.string().paint(&default))
+      })
+      .unwrap_or_default();
+
+    let prompt = format!(
+      "{} {}{choices}{shown_default}",
+      recipe.spaced_namepath(),
+      color.parameter().paint(&format!(
+        "{}{}",
+        parameter.kind.prefix().unwrap_or_default(),
+        parameter.name,
+      )),
+    );
+
+    loop {
+      eprint!("{prompt}: ");
+
+      let mut line = String::new();
+
+      let read = io::stdin()
+        .read_line(&mut line)
+        .map_err(|io_error| Error::PromptRead {
+          io_error,
+          recipe: recipe.name(),
+        })?;
+
+      if read == 0 {
+        eprintln!();
+        return Err(Error::PromptRead {
+          io_error: io::ErrorKind::UnexpectedEof.into(),
+          recipe: recipe.name(),
+        });
+      }
+
+      let line = line.trim_end_matches(['\r', '\n']);
+
+      let value = match (&default, parameter.kind) {
+        (Some(default), _) if line.is_empty() => return Ok(default.clone()),
+        (None, ParameterKind::Plus) if line.trim().is_empty() => continue,
+        (_, ParameterKind::Plus | ParameterKind::Star) => {
+          Value::List(line.split_whitespace().map(str::to_owned).collect())
+        }
+        _ => line.to_owned().into(),
+      };
+
+      let secret = recipe.is_secret(parameter.name.lexeme());
+
+      match Self::check(recipe, parameter, secret, &value) {
+        Ok(()) => return Ok(value),
+        Err(error) => eprintln!("{}", error.color_display(color)),
+      }
+    }
+  }
 }
 
 #[cfg(test)]
@@ -0,0 +1,53 @@
+use super::*;
+
+/// A run lifecycle event, written by `--events`
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum Event<'a> {
+  ConfirmationAnswered {
+    confirmed: bool,
+    recipe: &'a str,
+  },
+  ConfirmationRequested {
+    recipe: &'a str,
+  },
+  /// A recipe line, or a script recipe's script, finished, or could not be
+  /// run
+  LineFinished {
+    code: Option<i32>,
+    error: Option<String>,
+    line: Option<usize>,
+    recipe: &'a str,
+    signal: Option<i32>,
+  },
+  /// A recipe line, or a script recipe's script, started
+  LineStarted {
+    command: &'a str,
+    line: Option<usize>,
+    recipe: &'a str,
+  },
+  RecipeFinished {
+    arguments: &'a [String],
+    duration: f64,
+    error: Option<String>,
+    recipe: &'a str,
+  },
+  RecipeQueued {
+    arguments: &'a [String],
+    dependency: bool,
+    recipe: &'a str,
+  },
+  /// A recipe was not queued because it already ran, or is already queued,
+  /// with the same arguments
+  RecipeSkipped {
+    arguments: &'a [String],
+    recipe: &'a str,
+  },
+  RecipeStarted {
+    arguments: &'a [String],
+    recipe: &'a str,
+  },
+  SignalCaught {
+    signal: String,
+  },
+}
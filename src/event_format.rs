@@ -0,0 +1,6 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Clone, Copy, ValueEnum)]
+pub(crate) enum EventFormat {
+  Json,
+}
@@ -0,0 +1,60 @@
+use {super::*, std::sync::PoisonError};
+
+/// Writes `Event`s to stderr or a file as JSON lines, if `--events` was passed
+pub(crate) struct Events {
+  writer: Option<Box<dyn Write + Send>>,
+}
+
+#[allow(clippy::arbitrary_source_item_ordering)]
+#[derive(Serialize)]
+struct Record<'a> {
+  time: String,
+  #[serde(flatten)]
+  event: &'a Event<'a>,
+}
+
+impl Events {
+  pub(crate) fn emit(event: &Event) {
+    let mut instance = Self::instance();
+
+    let Some(writer) = &mut instance.writer else {
+      return;
+    };
+
+    let record = Record {
+      time: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+      event,
+    };
+
+    let mut line = serde_json::to_string(&record).unwrap();
+    line.push('\n');
+
+    writer.write_all(line.as_bytes()).ok();
+    writer.flush().ok();
+  }
+
+  pub(crate) fn install(config: &Config) -> RunResult<'static> {
+    let writer: Option<Box<dyn Write + Send>> = match (config.events, &config.events_file) {
+      (None, _) => None,
+      (Some(EventFormat::Json), None) => Some(Box::new(io::stderr())),
+      (Some(EventFormat::Json), Some(path)) => {
+        Some(Box::new(fs::File::create(path).map_err(|io_error| {
+          Error::EventsFile {
+            path: path.clone(),
+            io_error,
+          }
+        })?))
+      }
+    };
+
+    Self::instance().writer = writer;
+
+    Ok(())
+  }
+
+  fn instance() -> MutexGuard<'static, Self> {
+    static INSTANCE: Mutex<Events> = Mutex::new(Events { writer: None });
+
+    INSTANCE.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
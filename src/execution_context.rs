@@ -5,6 +5,10 @@ pub(crate) struct ExecutionContext<'src: 'run, 'run> {
   pub(crate) config: &'run Config,
   pub(crate) dotenv: &'run BTreeMap<String, String>,
   pub(crate) module: &'run Justfile<'src>,
+  /// Whether the recipe whose hook is being run failed
+  pub(crate) recipe_failed: Option<bool>,
+  /// The root module, from which user-defined functions are resolved
+  pub(crate) root: &'run Justfile<'src>,
   pub(crate) search: &'run Search,
 }
 
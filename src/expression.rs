@@ -43,6 +43,8 @@ pub(crate) enum Expression<'src> {
     lhs: Option<Box<Expression<'src>>>,
     rhs: Box<Expression<'src>>,
   },
+  /// `[element, ...]`
+  List { elements: Vec<Expression<'src>> },
   /// `lhs || rhs`
   Or {
     lhs: Box<Expression<'src>>,
@@ -58,6 +60,10 @@ impl<'src> Expression<'src> {
   pub(crate) fn variables<'expression>(&'expression self) -> Variables<'expression, 'src> {
     Variables::new(self)
   }
+
+  pub(crate) fn walk<'expression>(&'expression self) -> Walk<'expression, 'src> {
+    Walk::new(self)
+  }
 }
 
 impl Display for Expression<'_> {
@@ -85,6 +91,16 @@ impl Display for Expression<'_> {
         lhs: Some(lhs),
         rhs,
       } => write!(f, "{lhs} / {rhs}"),
+      Self::List { elements } => {
+        write!(f, "[")?;
+        for (i, element) in elements.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{element}")?;
+        }
+        write!(f, "]")
+      }
       Self::Or { lhs, rhs } => write!(f, "{lhs} || {rhs}"),
       Self::StringLiteral { string_literal } => write!(f, "{string_literal}"),
       Self::Variable { name } => write!(f, "{}", name.lexeme()),
@@ -146,6 +162,14 @@ impl Serialize for Expression<'_> {
         seq.serialize_element(rhs)?;
         seq.end()
       }
+      Self::List { elements } => {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element("list")?;
+        for element in elements {
+          seq.serialize_element(element)?;
+        }
+        seq.end()
+      }
       Self::Or { lhs, rhs } => {
         let mut seq = serializer.serialize_seq(None)?;
         seq.serialize_element("or")?;
@@ -0,0 +1,175 @@
+use {super::*, std::time::SystemTime};
+
+/// Whether a recipe with `[outputs]` or `[cache]` needs to run, and why
+#[derive(Debug, PartialEq)]
+pub(crate) enum Freshness {
+  /// Content hash matches the last successful run
+  Cached,
+  /// Content hash differs from the last successful run
+  Changed,
+  /// `--force` was passed
+  Forced,
+  /// Every output is newer than every input
+  Fresh {
+    input: Option<PathBuf>,
+    output: PathBuf,
+  },
+  /// An output does not exist
+  MissingOutput { output: PathBuf },
+  /// An input is newer than an output
+  NewerInput { input: PathBuf, output: PathBuf },
+  /// No successful run has been cached
+  Uncached,
+}
+
+impl Freshness {
+  pub(crate) fn new<'src>(
+    directory: &Path,
+    force: bool,
+    inputs: &[&str],
+    outputs: &[&str],
+  ) -> RunResult<'src, Self> {
+    if force {
+      return Ok(Self::Forced);
+    }
+
+    let mut oldest = None;
+
+    for output in outputs {
+      let path = directory.join(output);
+
+      let modified = match Self::modified(&path) {
+        Ok(modified) => modified,
+        Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => {
+          return Ok(Self::MissingOutput {
+            output: output.into(),
+          });
+        }
+        Err(io_error) => return Err(Error::ModificationTime { path, io_error }),
+      };
+
+      if oldest
+        .as_ref()
+        .map_or(true, |(oldest, _)| modified < *oldest)
+      {
+        oldest = Some((modified, PathBuf::from(output)));
+      }
+    }
+
+    let Some((oldest, output)) = oldest else {
+      return Err(Error::internal("recipe has no outputs"));
+    };
+
+    let mut newest = None;
+
+    for path in Self::inputs(directory, inputs)? {
+      let modified = Self::modified(&path).map_err(|io_error| Error::ModificationTime {
+        path: path.clone(),
+        io_error,
+      })?;
+
+      if newest
+        .as_ref()
+        .map_or(true, |(newest, _)| modified > *newest)
+      {
+        let input = path.strip_prefix(directory).unwrap_or(&path).into();
+        newest = Some((modified, input));
+      }
+    }
+
+    match newest {
+      Some((newest, input)) if newest > oldest => Ok(Self::NewerInput { input, output }),
+      newest => Ok(Self::Fresh {
+        input: newest.map(|(_, input)| input),
+        output,
+      }),
+    }
+  }
+
+  pub(crate) fn cached(force: bool, previous: Option<&str>, key: &str) -> Self {
+    if force {
+      Self::Forced
+    } else {
+      match previous {
+        Some(previous) if previous == key => Self::Cached,
+        Some(_) => Self::Changed,
+        None => Self::Uncached,
+      }
+    }
+  }
+
+  /// Expand input glob patterns relative to `directory`
+  pub(crate) fn inputs<'src>(directory: &Path, patterns: &[&str]) -> RunResult<'src, Vec<PathBuf>> {
+    let escaped = glob::Pattern::escape(&directory.to_string_lossy());
+
+    let mut inputs = Vec::new();
+
+    for pattern in patterns {
+      let pattern = Path::new(&escaped).join(pattern);
+
+      let paths = glob::glob(&pattern.to_string_lossy())
+        .map_err(|pattern_error| Error::internal(format!("invalid input glob: {pattern_error}")))?;
+
+      for path in paths {
+        inputs.push(path.map_err(|glob_error| Error::ModificationTime {
+          path: glob_error.path().into(),
+          io_error: glob_error.into(),
+        })?);
+      }
+    }
+
+    Ok(inputs)
+  }
+
+  pub(crate) fn is_fresh(&self) -> bool {
+    matches!(self, Self::Cached | Self::Fresh { .. })
+  }
+
+  fn modified(path: &Path) -> io::Result<SystemTime> {
+    fs::metadata(path)?.modified()
+  }
+}
+
+impl Display for Freshness {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Cached => write!(f, "is up to date: content hash matches last successful run"),
+      Self::Changed => write!(
+        f,
+        "is out of date: content hash changed since last successful run"
+      ),
+      Self::Forced => write!(f, "is out of date: `--force` was passed"),
+      Self::Fresh {
+        input: Some(input),
+        output,
+      } => write!(
+        f,
+        "is up to date: output `{}` is newer than input `{}`",
+        output.display(),
+        input.display(),
+      ),
+      Self::Fresh {
+        input: None,
+        output,
+      } => write!(
+        f,
+        "is up to date: output `{}` exists and no inputs were found",
+        output.display(),
+      ),
+      Self::MissingOutput { output } => {
+        write!(
+          f,
+          "is out of date: output `{}` does not exist",
+          output.display()
+        )
+      }
+      Self::NewerInput { input, output } => write!(
+        f,
+        "is out of date: input `{}` is newer than output `{}`",
+        input.display(),
+        output.display(),
+      ),
+      Self::Uncached => write!(f, "is out of date: no successful run is cached"),
+    }
+  }
+}
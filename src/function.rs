@@ -10,6 +10,7 @@ use {
 };
 
 #[allow(clippy::arbitrary_source_item_ordering)]
+#[derive(Clone, Copy)]
 pub(crate) enum Function {
   Nullary(fn(Context) -> FunctionResult),
   Unary(fn(Context, &str) -> FunctionResult),
@@ -18,6 +19,10 @@ pub(crate) enum Function {
   Binary(fn(Context, &str, &str) -> FunctionResult),
   BinaryPlus(fn(Context, &str, &str, &[String]) -> FunctionResult),
   Ternary(fn(Context, &str, &str, &str) -> FunctionResult),
+  UnaryValue(fn(Context, Value) -> ValueResult),
+  BinaryValue(fn(Context, Value, Value) -> ValueResult),
+  Filter,
+  Map,
 }
 
 pub(crate) struct Context<'src: 'run, 'run> {
@@ -31,6 +36,107 @@ impl<'src: 'run, 'run> Context<'src, 'run> {
   }
 }
 
+/// Built-in functions, sorted by name, excluding `_dir` abbreviations
+const FUNCTIONS: &[(&str, Function)] = &[
+  ("absolute_path", Unary(absolute_path)),
+  ("append", Binary(append)),
+  ("arch", Nullary(arch)),
+  ("blake3", Unary(blake3)),
+  ("blake3_file", Unary(blake3_file)),
+  (
+    "cache_directory",
+    Nullary(|_| dir("cache", dirs::cache_dir)),
+  ),
+  ("canonicalize", Unary(canonicalize)),
+  ("capitalize", Unary(capitalize)),
+  ("choose", Binary(choose)),
+  ("clean", Unary(clean)),
+  (
+    "config_directory",
+    Nullary(|_| dir("config", dirs::config_dir)),
+  ),
+  (
+    "config_local_directory",
+    Nullary(|_| dir("local config", dirs::config_local_dir)),
+  ),
+  ("data_directory", Nullary(|_| dir("data", dirs::data_dir))),
+  (
+    "data_local_directory",
+    Nullary(|_| dir("local data", dirs::data_local_dir)),
+  ),
+  ("datetime", Unary(datetime)),
+  ("datetime_utc", Unary(datetime_utc)),
+  ("encode_uri_component", Unary(encode_uri_component)),
+  ("env", UnaryOpt(env)),
+  ("env_var", Unary(env_var)),
+  ("env_var_or_default", Binary(env_var_or_default)),
+  ("error", Unary(error)),
+  (
+    "executable_directory",
+    Nullary(|_| dir("executable", dirs::executable_dir)),
+  ),
+  ("extension", Unary(extension)),
+  ("file_name", Unary(file_name)),
+  ("file_stem", Unary(file_stem)),
+  ("filter", Filter),
+  ("home_directory", Nullary(|_| dir("home", dirs::home_dir))),
+  ("index", BinaryValue(index)),
+  ("invocation_directory", Nullary(invocation_directory)),
+  (
+    "invocation_directory_native",
+    Nullary(invocation_directory_native),
+  ),
+  ("is_dependency", Nullary(is_dependency)),
+  ("join", BinaryPlus(join)),
+  ("just_executable", Nullary(just_executable)),
+  ("just_pid", Nullary(just_pid)),
+  ("justfile", Nullary(justfile)),
+  ("justfile_directory", Nullary(justfile_directory)),
+  ("kebabcase", Unary(kebabcase)),
+  ("len", UnaryValue(len)),
+  ("lowercamelcase", Unary(lowercamelcase)),
+  ("lowercase", Unary(lowercase)),
+  ("map", Map),
+  ("module_directory", Nullary(module_directory)),
+  ("module_file", Nullary(module_file)),
+  ("num_cpus", Nullary(num_cpus)),
+  ("os", Nullary(os)),
+  ("os_family", Nullary(os_family)),
+  ("parent_directory", Unary(parent_directory)),
+  ("path_exists", Unary(path_exists)),
+  ("prepend", Binary(prepend)),
+  ("quote", Unary(quote)),
+  ("read", Unary(read)),
+  ("recipe_failed", Nullary(recipe_failed)),
+  ("replace", Ternary(replace)),
+  ("replace_regex", Ternary(replace_regex)),
+  ("require", Unary(require)),
+  ("semver_matches", Binary(semver_matches)),
+  ("sha256", Unary(sha256)),
+  ("sha256_file", Unary(sha256_file)),
+  ("shell", UnaryPlus(shell)),
+  ("shoutykebabcase", Unary(shoutykebabcase)),
+  ("shoutysnakecase", Unary(shoutysnakecase)),
+  ("snakecase", Unary(snakecase)),
+  ("source_directory", Nullary(source_directory)),
+  ("source_file", Nullary(source_file)),
+  ("split", BinaryValue(split)),
+  ("style", Unary(style)),
+  ("titlecase", Unary(titlecase)),
+  ("trim", Unary(trim)),
+  ("trim_end", Unary(trim_end)),
+  ("trim_end_match", Binary(trim_end_match)),
+  ("trim_end_matches", Binary(trim_end_matches)),
+  ("trim_start", Unary(trim_start)),
+  ("trim_start_match", Binary(trim_start_match)),
+  ("trim_start_matches", Binary(trim_start_matches)),
+  ("uppercamelcase", Unary(uppercamelcase)),
+  ("uppercase", Unary(uppercase)),
+  ("uuid", Nullary(uuid)),
+  ("which", Unary(which)),
+  ("without_extension", Unary(without_extension)),
+];
+
 pub(crate) fn get(name: &str) -> Option<Function> {
   let name = if let Some(prefix) = name.strip_suffix("_dir") {
     format!("{prefix}_directory")
@@ -40,93 +146,25 @@ pub(crate) fn get(name: &str) -> Option<Function> {
     name.into()
   };
 
-  let function = match name.as_str() {
-    "absolute_path" => Unary(absolute_path),
-    "append" => Binary(append),
-    "arch" => Nullary(arch),
-    "blake3" => Unary(blake3),
-    "blake3_file" => Unary(blake3_file),
-    "cache_directory" => Nullary(|_| dir("cache", dirs::cache_dir)),
-    "canonicalize" => Unary(canonicalize),
-    "capitalize" => Unary(capitalize),
-    "choose" => Binary(choose),
-    "clean" => Unary(clean),
-    "config_directory" => Nullary(|_| dir("config", dirs::config_dir)),
-    "config_local_directory" => Nullary(|_| dir("local config", dirs::config_local_dir)),
-    "data_directory" => Nullary(|_| dir("data", dirs::data_dir)),
-    "data_local_directory" => Nullary(|_| dir("local data", dirs::data_local_dir)),
-    "datetime" => Unary(datetime),
-    "datetime_utc" => Unary(datetime_utc),
-    "encode_uri_component" => Unary(encode_uri_component),
-    "env" => UnaryOpt(env),
-    "env_var" => Unary(env_var),
-    "env_var_or_default" => Binary(env_var_or_default),
-    "error" => Unary(error),
-    "executable_directory" => Nullary(|_| dir("executable", dirs::executable_dir)),
-    "extension" => Unary(extension),
-    "file_name" => Unary(file_name),
-    "file_stem" => Unary(file_stem),
-    "home_directory" => Nullary(|_| dir("home", dirs::home_dir)),
-    "invocation_directory" => Nullary(invocation_directory),
-    "invocation_directory_native" => Nullary(invocation_directory_native),
-    "is_dependency" => Nullary(is_dependency),
-    "join" => BinaryPlus(join),
-    "just_executable" => Nullary(just_executable),
-    "just_pid" => Nullary(just_pid),
-    "justfile" => Nullary(justfile),
-    "justfile_directory" => Nullary(justfile_directory),
-    "kebabcase" => Unary(kebabcase),
-    "lowercamelcase" => Unary(lowercamelcase),
-    "lowercase" => Unary(lowercase),
-    "module_directory" => Nullary(module_directory),
-    "module_file" => Nullary(module_file),
-    "num_cpus" => Nullary(num_cpus),
-    "os" => Nullary(os),
-    "os_family" => Nullary(os_family),
-    "parent_directory" => Unary(parent_directory),
-    "path_exists" => Unary(path_exists),
-    "prepend" => Binary(prepend),
-    "quote" => Unary(quote),
-    "read" => Unary(read),
-    "replace" => Ternary(replace),
-    "replace_regex" => Ternary(replace_regex),
-    "require" => Unary(require),
-    "semver_matches" => Binary(semver_matches),
-    "sha256" => Unary(sha256),
-    "sha256_file" => Unary(sha256_file),
-    "shell" => UnaryPlus(shell),
-    "shoutykebabcase" => Unary(shoutykebabcase),
-    "shoutysnakecase" => Unary(shoutysnakecase),
-    "snakecase" => Unary(snakecase),
-    "source_directory" => Nullary(source_directory),
-    "source_file" => Nullary(source_file),
-    "style" => Unary(style),
-    "titlecase" => Unary(titlecase),
-    "trim" => Unary(trim),
-    "trim_end" => Unary(trim_end),
-    "trim_end_match" => Binary(trim_end_match),
-    "trim_end_matches" => Binary(trim_end_matches),
-    "trim_start" => Unary(trim_start),
-    "trim_start_match" => Binary(trim_start_match),
-    "trim_start_matches" => Binary(trim_start_matches),
-    "uppercamelcase" => Unary(uppercamelcase),
-    "uppercase" => Unary(uppercase),
-    "uuid" => Nullary(uuid),
-    "which" => Unary(which),
-    "without_extension" => Unary(without_extension),
-    _ => return None,
-  };
-  Some(function)
+  FUNCTIONS
+    .binary_search_by_key(&name.as_str(), |(name, _)| name)
+    .ok()
+    .map(|i| FUNCTIONS[i].1)
+}
+
+/// Names of all built-in functions, excluding `_dir` abbreviations
+pub(crate) fn names() -> impl Iterator<Item = &'static str> {
+  FUNCTIONS.iter().map(|(name, _)| *name)
 }
 
 impl Function {
   pub(crate) fn argc(&self) -> RangeInclusive<usize> {
     match *self {
       Nullary(_) => 0..=0,
-      Unary(_) => 1..=1,
+      Unary(_) | UnaryValue(_) => 1..=1,
       UnaryOpt(_) => 1..=2,
       UnaryPlus(_) => 1..=usize::MAX,
-      Binary(_) => 2..=2,
+      Binary(_) | BinaryValue(_) | Filter | Map => 2..=2,
       BinaryPlus(_) => 2..=usize::MAX,
       Ternary(_) => 3..=3,
     }
@@ -331,6 +369,23 @@ fn file_stem(_context: Context, path: &str) -> FunctionResult {
     .ok_or_else(|| format!("Could not extract file stem from `{path}`"))
 }
 
+fn index(_context: Context, list: Value, index: Value) -> ValueResult {
+  let list = list.into_list();
+  let index = index.into_string();
+
+  let i = index
+    .parse::<usize>()
+    .map_err(|err| format!("invalid index `{index}`: {err}"))?;
+
+  let len = list.len();
+
+  list
+    .into_iter()
+    .nth(i)
+    .map(Value::from)
+    .ok_or_else(|| format!("index {i} out of range for list of length {len}"))
+}
+
 fn invocation_directory(context: Context) -> FunctionResult {
   Platform::convert_native_path(
     context.evaluator.context.config,
@@ -443,6 +498,15 @@ fn kebabcase(_context: Context, s: &str) -> FunctionResult {
   Ok(s.to_kebab_case())
 }
 
+fn len(_context: Context, value: Value) -> ValueResult {
+  let len = match value {
+    Value::List(list) => list.len(),
+    Value::String(s) => s.chars().count(),
+  };
+
+  Ok(len.to_string().into())
+}
+
 fn lowercamelcase(_context: Context, s: &str) -> FunctionResult {
   Ok(s.to_lower_camel_case())
 }
@@ -512,12 +576,24 @@ fn read(context: Context, filename: &str) -> FunctionResult {
     .map_err(|err| format!("I/O error reading `{filename}`: {err}"))
 }
 
+fn recipe_failed(context: Context) -> FunctionResult {
+  context
+    .evaluator
+    .context
+    .recipe_failed
+    .map(|failed| failed.to_string())
+    .ok_or_else(|| {
+      "`recipe_failed()` may only be called from a `[finally]` or `[on-failure]` hook".into()
+    })
+}
+
 fn replace(_context: Context, s: &str, from: &str, to: &str) -> FunctionResult {
   Ok(s.replace(from, to))
 }
 
 fn require(context: Context, name: &str) -> FunctionResult {
-  crate::which(context, name)?.ok_or_else(|| format!("could not find executable `{name}`"))
+  crate::which(&context.evaluator.context.working_directory(), name)?
+    .ok_or_else(|| format!("could not find executable `{name}`"))
 }
 
 fn replace_regex(_context: Context, s: &str, regex: &str, replacement: &str) -> FunctionResult {
@@ -612,6 +688,23 @@ fn source_file(context: Context) -> FunctionResult {
     })
 }
 
+fn split(_context: Context, s: Value, separator: Value) -> ValueResult {
+  let s = s.into_string();
+  let separator = separator.into_string();
+
+  if separator.is_empty() {
+    return Err("separator may not be empty".into());
+  }
+
+  if s.is_empty() {
+    return Ok(Value::List(Vec::new()));
+  }
+
+  Ok(Value::List(
+    s.split(separator.as_str()).map(str::to_owned).collect(),
+  ))
+}
+
 fn style(context: Context, s: &str) -> FunctionResult {
   match s {
     "command" => Ok(
@@ -671,7 +764,7 @@ fn uuid(_context: Context) -> FunctionResult {
 }
 
 fn which(context: Context, name: &str) -> FunctionResult {
-  Ok(crate::which(context, name)?.unwrap_or_default())
+  Ok(crate::which(&context.evaluator.context.working_directory(), name)?.unwrap_or_default())
 }
 
 fn without_extension(_context: Context, path: &str) -> FunctionResult {
@@ -706,6 +799,25 @@ fn semver_matches(_context: Context, version: &str, requirement: &str) -> Functi
 mod tests {
   use super::*;
 
+  #[test]
+  fn functions_are_sorted() {
+    for window in FUNCTIONS.windows(2) {
+      assert!(
+        window[0].0 < window[1].0,
+        "`{}` and `{}` are out of order",
+        window[0].0,
+        window[1].0,
+      );
+    }
+  }
+
+  #[test]
+  fn every_name_is_found() {
+    for name in super::names() {
+      assert!(get(name).is_some(), "unknown function: {name}");
+    }
+  }
+
   #[test]
   fn dir_not_found() {
     assert_eq!(dir("foo", || None).unwrap_err(), "foo directory not found");
@@ -0,0 +1,101 @@
+use super::*;
+
+/// The user-defined functions visible from a module, as the chain of modules
+/// from the root module to that module
+///
+/// Each link holds the functions defined in that module, and its submodules,
+/// if they are known, so that calls can be resolved in the module in which
+/// they appear, rather than the module from which they are made.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FunctionScope<'run, 'src> {
+  modules: Vec<(
+    &'run Table<'src, UserFunction<'src>>,
+    Option<&'run Table<'src, Justfile<'src>>>,
+  )>,
+}
+
+impl<'run, 'src> FunctionScope<'run, 'src> {
+  /// The functions visible from `module`, which is a submodule of `root`
+  pub(crate) fn new(root: &'run Justfile<'src>, module: &Justfile<'src>) -> Self {
+    let mut scope = Self::default().child(&root.functions, Some(&root.modules));
+
+    let mut justfile = root;
+    for name in module
+      .module_path
+      .split("::")
+      .filter(|name| !name.is_empty())
+    {
+      justfile = justfile
+        .modules
+        .get(name)
+        .expect("failed to retrieve module");
+      scope = scope.child(&justfile.functions, Some(&justfile.modules));
+    }
+
+    scope
+  }
+
+  /// The functions visible from a submodule of this scope's innermost module,
+  /// which defines `functions` and has `modules` as submodules
+  pub(crate) fn child(
+    &self,
+    functions: &'run Table<'src, UserFunction<'src>>,
+    modules: Option<&'run Table<'src, Justfile<'src>>>,
+  ) -> Self {
+    let mut child = self.clone();
+    child.modules.push((functions, modules));
+    child
+  }
+
+  /// The functions defined in the innermost module of this scope, used to
+  /// identify functions which share a name but are defined in different
+  /// modules
+  pub(crate) fn innermost(&self) -> *const Table<'src, UserFunction<'src>> {
+    self
+      .modules
+      .last()
+      .map_or(ptr::null(), |(functions, _modules)| {
+        ptr::from_ref(*functions)
+      })
+  }
+
+  /// The function called with `path` and `name`, e.g., `foo::bar()`, and the
+  /// scope of the module in which it is defined
+  ///
+  /// Functions called without a path are looked up in the innermost module
+  /// and then its ancestors, and functions called with a path are looked up
+  /// in the submodule it names.
+  pub(crate) fn resolve(
+    &self,
+    path: &[Name<'src>],
+    name: &str,
+  ) -> Option<(Self, &'run UserFunction<'src>)> {
+    if path.is_empty() {
+      for (i, (functions, _modules)) in self.modules.iter().enumerate().rev() {
+        if let Some(function) = functions.get(name) {
+          return Some((
+            Self {
+              modules: self.modules[..=i].to_vec(),
+            },
+            function,
+          ));
+        }
+      }
+
+      return None;
+    }
+
+    let mut scope = self.clone();
+
+    for module in path {
+      let (_functions, modules) = scope.modules.last()?;
+      let justfile = modules.as_ref()?.get(module.lexeme())?;
+      scope = scope.child(&justfile.functions, Some(&justfile.modules));
+    }
+
+    let (functions, _modules) = scope.modules.last()?;
+    let function = functions.get(name)?;
+
+    Some((scope, function))
+  }
+}
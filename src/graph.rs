@@ -0,0 +1,284 @@
+use super::*;
+
+/// An edge from a recipe to one of its dependencies
+struct Edge<'src, 'run> {
+  dependency: &'run Recipe<'src>,
+  label: Option<String>,
+  recipe: &'run Recipe<'src>,
+  subsequent: bool,
+}
+
+/// The dependency graph of a set of recipes, printed by `--graph`
+pub(crate) struct Graph<'src, 'run> {
+  edges: Vec<Edge<'src, 'run>>,
+  format: GraphFormat,
+  recipes: BTreeMap<&'run str, &'run Recipe<'src>>,
+}
+
+impl<'src, 'run> Graph<'src, 'run> {
+  pub(crate) fn new(
+    config: &'run Config,
+    dotenv: &'run BTreeMap<String, String>,
+    justfile: &'run Justfile<'src>,
+    recipes: &[String],
+    scopes: &BTreeMap<String, (&'run Justfile<'src>, &'run Scope<'src, 'run>)>,
+    search: &'run Search,
+  ) -> RunResult<'src, Self> {
+    let mut stack = Vec::new();
+
+    if recipes.is_empty() {
+      let mut modules = vec![justfile];
+      while let Some(module) = modules.pop() {
+        stack.extend(module.recipes.values().map(Arc::as_ref));
+        modules.extend(module.modules.values());
+      }
+    } else {
+      for recipe in recipes {
+        stack.push(Self::resolve(justfile, recipe)?);
+      }
+    }
+
+    let mut reachable = BTreeMap::new();
+    while let Some(recipe) = stack.pop() {
+      if reachable.insert(recipe.namepath(), recipe).is_none() {
+        stack.extend(
+          recipe
+            .dependencies
+            .iter()
+            .map(|dependency| dependency.recipe.as_ref()),
+        );
+      }
+    }
+
+    let mut edges = Vec::new();
+
+    for recipe in reachable.values() {
+      let context;
+      let mut evaluator = None;
+
+      if config.graph_eval {
+        let (module, scope) = scopes[recipe.module_path()];
+
+        context = ExecutionContext {
+          config,
+          dotenv,
+          module,
+          recipe_failed: None,
+          root: justfile,
+          search,
+        };
+
+        evaluator = Some(Evaluator::new(&context, true, scope));
+      }
+
+      for (i, dependency) in recipe.dependencies.iter().enumerate() {
+        let label = if dependency.arguments.is_empty() {
+          None
+        } else {
+          Some(
+            dependency
+              .arguments
+              .iter()
+              .map(|argument| Self::label(evaluator.as_mut(), recipe, argument))
+              .collect::<Vec<String>>()
+              .join(" "),
+          )
+        };
+
+        edges.push(Edge {
+          dependency: &dependency.recipe,
+          label,
+          recipe,
+          subsequent: i >= recipe.priors,
+        });
+      }
+    }
+
+    Ok(Self {
+      edges,
+      format: config.graph_format,
+      recipes: reachable,
+    })
+  }
+
+  fn escape(&self, s: &str) -> String {
+    match self.format {
+      GraphFormat::Dot => s.replace('\\', "\\\\").replace('"', "\\\""),
+      GraphFormat::Mermaid => s.replace('"', "#quot;"),
+    }
+  }
+
+  /// Label a dependency argument with its source, or, with `--graph-eval`,
+  /// with its value, unless its value depends on the recipe's parameters or
+  /// cannot be evaluated
+  fn label(
+    evaluator: Option<&mut Evaluator<'src, 'run>>,
+    recipe: &Recipe<'src>,
+    argument: &Expression<'src>,
+  ) -> String {
+    let Some(evaluator) = evaluator else {
+      return argument.to_string();
+    };
+
+    let parameterized = argument.variables().any(|variable| {
+      recipe
+        .parameters
+        .iter()
+        .any(|parameter| parameter.name.lexeme() == variable.lexeme())
+    });
+
+    if parameterized {
+      return argument.to_string();
+    }
+
+    match evaluator.evaluate_expression(argument) {
+      Ok(value) if value.contains('\'') => format!("{value:?}"),
+      Ok(value) => format!("'{value}'"),
+      Err(_) => argument.to_string(),
+    }
+  }
+
+  /// Recipes grouped by module, with recipes in the root module first
+  fn modules(&self) -> BTreeMap<&'run str, Vec<&'run Recipe<'src>>> {
+    let mut modules = BTreeMap::<&str, Vec<&Recipe>>::new();
+
+    for recipe in self.recipes.values() {
+      modules
+        .entry(recipe.module_path())
+        .or_default()
+        .push(recipe);
+    }
+
+    modules
+  }
+
+  fn resolve(justfile: &'run Justfile<'src>, path: &str) -> RunResult<'src, &'run Recipe<'src>> {
+    let module_path =
+      ModulePath::try_from([path].as_slice()).map_err(|()| Error::UnknownRecipe {
+        recipe: path.into(),
+        suggestion: None,
+      })?;
+
+    let (name, modules) = module_path.path.split_last().unwrap();
+
+    let mut module = justfile;
+    for (i, name) in modules.iter().enumerate() {
+      module = module
+        .modules
+        .get(name)
+        .ok_or_else(|| Error::UnknownSubmodule {
+          path: modules[..=i].join("::"),
+        })?;
+    }
+
+    module.get_recipe(name).ok_or_else(|| Error::UnknownRecipe {
+      recipe: path.into(),
+      suggestion: module.suggest_recipe(name),
+    })
+  }
+
+  fn write_dot(&self, f: &mut Formatter) -> fmt::Result {
+    writeln!(f, "digraph justfile {{")?;
+
+    for (module, recipes) in self.modules() {
+      if module.is_empty() {
+        for recipe in recipes {
+          writeln!(f, "  \"{}\"", recipe.namepath())?;
+        }
+      } else {
+        writeln!(f, "  subgraph \"cluster_{module}\" {{")?;
+        writeln!(f, "    label = \"{module}\"")?;
+        for recipe in recipes {
+          writeln!(f, "    \"{}\"", recipe.namepath())?;
+        }
+        writeln!(f, "  }}")?;
+      }
+    }
+
+    for edge in &self.edges {
+      write!(
+        f,
+        "  \"{}\" -> \"{}\"",
+        edge.recipe.namepath(),
+        edge.dependency.namepath(),
+      )?;
+
+      let mut attributes = Vec::new();
+
+      if let Some(label) = &edge.label {
+        attributes.push(format!("label = \"{}\"", self.escape(label)));
+      }
+
+      if edge.subsequent {
+        attributes.push("style = dashed".into());
+      }
+
+      if !attributes.is_empty() {
+        write!(f, " [{}]", attributes.join(", "))?;
+      }
+
+      writeln!(f)?;
+    }
+
+    writeln!(f, "}}")
+  }
+
+  fn write_mermaid(&self, f: &mut Formatter) -> fmt::Result {
+    let ids = self
+      .recipes
+      .keys()
+      .enumerate()
+      .map(|(i, namepath)| (*namepath, format!("n{i}")))
+      .collect::<BTreeMap<&str, String>>();
+
+    writeln!(f, "flowchart LR")?;
+
+    for (i, (module, recipes)) in self.modules().into_iter().enumerate() {
+      let indent = if module.is_empty() {
+        "  "
+      } else {
+        writeln!(f, "  subgraph m{i} [\"{module}\"]")?;
+        "    "
+      };
+
+      for recipe in recipes {
+        writeln!(
+          f,
+          "{indent}{}[\"{}\"]",
+          ids[recipe.namepath()],
+          recipe.namepath(),
+        )?;
+      }
+
+      if !module.is_empty() {
+        writeln!(f, "  end")?;
+      }
+    }
+
+    for edge in &self.edges {
+      write!(
+        f,
+        "  {} {}",
+        ids[edge.recipe.namepath()],
+        if edge.subsequent { "-.->" } else { "-->" },
+      )?;
+
+      if let Some(label) = &edge.label {
+        write!(f, "|\"{}\"|", self.escape(label))?;
+      }
+
+      writeln!(f, " {}", ids[edge.dependency.namepath()])?;
+    }
+
+    Ok(())
+  }
+}
+
+impl Display for Graph<'_, '_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self.format {
+      GraphFormat::Dot => self.write_dot(f),
+      GraphFormat::Mermaid => self.write_mermaid(f),
+    }
+  }
+}
@@ -0,0 +1,7 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Clone, Copy, ValueEnum)]
+pub(crate) enum GraphFormat {
+  Dot,
+  Mermaid,
+}
@@ -4,8 +4,12 @@ use super::*;
 #[derive(Debug, Clone)]
 pub(crate) enum Item<'src> {
   Alias(Alias<'src, Namepath<'src>>),
-  Assignment(Assignment<'src>),
+  Assignment {
+    allowed: BTreeSet<Lint>,
+    assignment: Assignment<'src>,
+  },
   Comment(&'src str),
+  Function(UserFunction<'src>),
   Import {
     absolute: Option<PathBuf>,
     optional: bool,
@@ -21,7 +25,10 @@ pub(crate) enum Item<'src> {
     relative: Option<StringLiteral<'src>>,
   },
   Recipe(UnresolvedRecipe<'src>),
-  Set(Set<'src>),
+  Set {
+    allowed: BTreeSet<Lint>,
+    set: Set<'src>,
+  },
   Unexport {
     name: Name<'src>,
   },
@@ -31,8 +38,9 @@ impl Display for Item<'_> {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     match self {
       Self::Alias(alias) => write!(f, "{alias}"),
-      Self::Assignment(assignment) => write!(f, "{assignment}"),
+      Self::Assignment { assignment, .. } => write!(f, "{assignment}"),
       Self::Comment(comment) => write!(f, "{comment}"),
+      Self::Function(function) => write!(f, "{function}"),
       Self::Import {
         relative, optional, ..
       } => {
@@ -75,7 +83,7 @@ impl Display for Item<'_> {
         Ok(())
       }
       Self::Recipe(recipe) => write!(f, "{}", recipe.color_display(Color::never())),
-      Self::Set(set) => write!(f, "{set}"),
+      Self::Set { set, .. } => write!(f, "{set}"),
       Self::Unexport { name } => write!(f, "unexport {name}"),
     }
   }
@@ -1,18 +1,24 @@
-use {super::*, serde::Serialize};
+use {
+  super::*,
+  serde::{Serialize, Serializer},
+};
 
 #[derive(Debug)]
 struct Invocation<'src: 'run, 'run> {
-  arguments: Vec<&'run str>,
+  arguments: Arguments,
   recipe: &'run Recipe<'src>,
 }
 
 #[derive(Debug, PartialEq, Serialize)]
 pub(crate) struct Justfile<'src> {
   pub(crate) aliases: Table<'src, Alias<'src>>,
+  #[serde(serialize_with = "serialize_assignments")]
   pub(crate) assignments: Table<'src, Assignment<'src>>,
   #[serde(rename = "first", serialize_with = "keyed::serialize_option")]
   pub(crate) default: Option<Arc<Recipe<'src>>>,
   pub(crate) doc: Option<String>,
+  #[serde(skip_serializing_if = "Table::is_empty")]
+  pub(crate) functions: Table<'src, UserFunction<'src>>,
   pub(crate) groups: Vec<StringLiteral<'src>>,
   #[serde(skip)]
   pub(crate) loaded: Vec<PathBuf>,
@@ -27,11 +33,22 @@ pub(crate) struct Justfile<'src> {
   pub(crate) unexports: HashSet<String>,
   #[serde(skip)]
   pub(crate) unstable_features: BTreeSet<UnstableFeature>,
-  pub(crate) warnings: Vec<Warning>,
+  pub(crate) warnings: Vec<Warning<'src>>,
   #[serde(skip)]
   pub(crate) working_directory: PathBuf,
 }
 
+fn serialize_assignments<'src, S: Serializer>(
+  assignments: &Table<'src, Assignment<'src>>,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  serializer.collect_map(
+    assignments
+      .iter()
+      .map(|(name, assignment)| (name, assignment.redacted())),
+  )
+}
+
 impl<'src> Justfile<'src> {
   fn find_suggestion(
     input: &str,
@@ -84,11 +101,13 @@ impl<'src> Justfile<'src> {
     config: &'run Config,
     dotenv: &'run BTreeMap<String, String>,
     overrides: &BTreeMap<String, String>,
-    root: &'run Scope<'src, 'run>,
+    parent: &'run Scope<'src, 'run>,
+    root: &'run Self,
     scopes: &mut BTreeMap<String, (&'run Justfile<'src>, &'run Scope<'src, 'run>)>,
     search: &'run Search,
   ) -> RunResult<'src> {
-    let scope = Evaluator::evaluate_assignments(config, dotenv, self, overrides, root, search)?;
+    let scope =
+      Evaluator::evaluate_assignments(config, dotenv, self, overrides, parent, root, search)?;
 
     let scope = arena.alloc(scope);
     scopes.insert(self.module_path.clone(), (self, scope));
@@ -100,6 +119,7 @@ impl<'src> Justfile<'src> {
         dotenv,
         &BTreeMap::new(),
         scope,
+        root,
         scopes,
         search,
       )?;
@@ -114,6 +134,29 @@ impl<'src> Justfile<'src> {
     search: &Search,
     overrides: &BTreeMap<String, String>,
     arguments: &[String],
+  ) -> RunResult<'src> {
+    self.run_inner(config, search, overrides, arguments, None)
+  }
+
+  /// Run recipes given as argument groups, rather than as positional
+  /// arguments, which may be ambiguous if recipes take optional arguments
+  pub(crate) fn run_groups(
+    &self,
+    config: &Config,
+    search: &Search,
+    overrides: &BTreeMap<String, String>,
+    groups: Vec<ArgumentGroup>,
+  ) -> RunResult<'src> {
+    self.run_inner(config, search, overrides, &[], Some(groups))
+  }
+
+  fn run_inner(
+    &self,
+    config: &Config,
+    search: &Search,
+    overrides: &BTreeMap<String, String>,
+    arguments: &[String],
+    groups: Option<Vec<ArgumentGroup>>,
   ) -> RunResult<'src> {
     let unknown_overrides = overrides
       .keys()
@@ -127,24 +170,36 @@ impl<'src> Justfile<'src> {
       });
     }
 
-    let dotenv = if config.load_dotenv {
+    let (dotenv, dotenv_sources) = if config.load_dotenv {
       load_dotenv(config, &self.settings, &search.working_directory)?
     } else {
-      BTreeMap::new()
+      (BTreeMap::new(), Vec::new())
     };
 
     let root = Scope::root();
     let arena = Arena::new();
     let mut scopes = BTreeMap::new();
-    self.evaluate_scopes(
-      &arena,
-      config,
-      &dotenv,
-      overrides,
-      &root,
-      &mut scopes,
-      search,
-    )?;
+
+    // `--graph` only evaluates assignments with `--graph-eval`, so that
+    // backticks are not run
+    if !matches!(config.subcommand, Subcommand::Graph { .. }) || config.graph_eval {
+      self.evaluate_scopes(
+        &arena,
+        config,
+        &dotenv,
+        overrides,
+        &root,
+        self,
+        &mut scopes,
+        search,
+      )?;
+    }
+
+    if let Subcommand::Graph { recipes, .. } = &config.subcommand {
+      let graph = Graph::new(config, &dotenv, self, recipes, &scopes, search)?;
+      print!("{graph}");
+      return Ok(());
+    }
 
     let scope = scopes.get(&self.module_path).unwrap().1;
 
@@ -166,7 +221,13 @@ impl<'src> Justfile<'src> {
 
         let scope = scope.child();
 
-        command.export(&self.settings, &dotenv, &scope, &self.unexports);
+        command.export(
+          &self.settings,
+          &dotenv,
+          &scope,
+          &BTreeMap::new(),
+          &self.unexports,
+        );
 
         let (result, caught) = command.status_guard();
 
@@ -176,6 +237,12 @@ impl<'src> Justfile<'src> {
           io_error,
         })?;
 
+        // signals are forwarded to the command, so report that `just` was
+        // interrupted, rather than that the command failed
+        if let Some(signal) = caught {
+          return Err(Error::Interrupted { signal });
+        }
+
         if !status.success() {
           return Err(Error::CommandStatus {
             binary: binary.clone(),
@@ -184,16 +251,16 @@ impl<'src> Justfile<'src> {
           });
         }
 
-        if let Some(signal) = caught {
-          return Err(Error::Interrupted { signal });
-        }
-
         return Ok(());
       }
       Subcommand::Evaluate { variable, .. } => {
         if let Some(variable) = variable {
-          if let Some(value) = scope.value(variable) {
-            print!("{value}");
+          if let Some(binding) = scope.binding(variable) {
+            if binding.secret {
+              print!("{}", secrets::REDACTED);
+            } else {
+              print!("{}", binding.value);
+            }
           } else {
             return Err(Error::EvalUnknownVariable {
               suggestion: self.suggest_variable(variable),
@@ -205,12 +272,39 @@ impl<'src> Justfile<'src> {
 
           for binding in scope.bindings() {
             if !binding.private {
-              println!(
-                "{0:1$} := \"{2}\"",
-                binding.name.lexeme(),
-                width,
-                binding.value
-              );
+              let value = if binding.secret {
+                secrets::REDACTED.into()
+              } else {
+                binding.value.to_string()
+              };
+
+              println!("{0:1$} := \"{2}\"", binding.name.lexeme(), width, value);
+            }
+          }
+
+          let mut separate = scope.bindings().any(|binding| !binding.private);
+
+          for (path, names) in &dotenv_sources {
+            if names.is_empty() {
+              continue;
+            }
+
+            if separate {
+              println!();
+            }
+
+            separate = true;
+
+            println!(
+              "# {}",
+              path
+                .strip_prefix(&search.working_directory)
+                .unwrap_or(path)
+                .display()
+            );
+
+            for name in names {
+              println!("{name}=\"{}\"", dotenv[name]);
             }
           }
         }
@@ -220,14 +314,38 @@ impl<'src> Justfile<'src> {
       _ => {}
     }
 
-    let arguments = arguments.iter().map(String::as_str).collect::<Vec<&str>>();
+    let groups = if let Some(groups) = groups {
+      groups
+    } else {
+      let arguments = arguments.iter().map(String::as_str).collect::<Vec<&str>>();
+      ArgumentParser::parse_arguments(config, self, &arguments)?
+    };
 
-    let groups = ArgumentParser::parse_arguments(self, &arguments)?;
+    if groups.iter().any(|group| group.help) {
+      for group in groups.iter().filter(|group| group.help) {
+        println!(
+          "{}",
+          self
+            .usage(&group.path, 0)
+            .color_display(config.color.stdout())
+        );
+      }
+      return Ok(());
+    }
 
     let mut invocations = Vec::<Invocation>::new();
 
     for group in &groups {
-      invocations.push(self.invocation(&group.arguments, &group.path, 0)?);
+      let arguments = Arguments {
+        options: group.options.clone(),
+        positional: group.arguments.clone(),
+        secret: BTreeSet::new(),
+      };
+      invocations.push(self.invocation(arguments, &group.path, 0)?);
+    }
+
+    for invocation in &invocations {
+      invocation.recipe.check_arguments(&invocation.arguments)?;
     }
 
     if config.one && invocations.len() > 1 {
@@ -236,26 +354,18 @@ impl<'src> Justfile<'src> {
       });
     }
 
-    let ran = Ran::default();
-    for invocation in invocations {
-      Self::run_recipe(
-        &invocation
-          .arguments
-          .iter()
-          .copied()
-          .map(str::to_string)
-          .collect::<Vec<String>>(),
-        config,
-        &dotenv,
-        false,
-        &ran,
-        invocation.recipe,
-        &scopes,
-        search,
-      )?;
+    let invocations = invocations
+      .into_iter()
+      .map(|invocation| (invocation.recipe, invocation.arguments))
+      .collect();
+
+    if config.plan {
+      let plan = Plan::new(config, &dotenv, invocations, self, &scopes, search)?;
+      print!("{plan}");
+      return Ok(());
     }
 
-    Ok(())
+    Scheduler::run(config, &dotenv, invocations, self, &scopes, search)
   }
 
   pub(crate) fn check_unstable(&self, config: &Config) -> RunResult<'src> {
@@ -284,22 +394,28 @@ impl<'src> Justfile<'src> {
 
   fn invocation<'run>(
     &'run self,
-    arguments: &[&'run str],
+    arguments: Arguments,
     path: &'run [String],
     position: usize,
   ) -> RunResult<'src, Invocation<'src, 'run>> {
     if position + 1 == path.len() {
       let recipe = self.get_recipe(&path[position]).unwrap();
-      Ok(Invocation {
-        arguments: arguments.into(),
-        recipe,
-      })
+      Ok(Invocation { arguments, recipe })
     } else {
       let module = self.modules.get(&path[position]).unwrap();
       module.invocation(arguments, path, position + 1)
     }
   }
 
+  fn usage(&self, path: &[String], position: usize) -> RecipeUsage<'src> {
+    if position + 1 == path.len() {
+      RecipeUsage::new(self, self.get_recipe(&path[position]).unwrap())
+    } else {
+      let module = self.modules.get(&path[position]).unwrap();
+      module.usage(path, position + 1)
+    }
+  }
+
   pub(crate) fn is_submodule(&self) -> bool {
     self.name.is_some()
   }
@@ -308,136 +424,6 @@ impl<'src> Justfile<'src> {
     self.name.map(|name| name.lexeme()).unwrap_or_default()
   }
 
-  fn run_recipe(
-    arguments: &[String],
-    config: &Config,
-    dotenv: &BTreeMap<String, String>,
-    is_dependency: bool,
-    ran: &Ran,
-    recipe: &Recipe<'src>,
-    scopes: &BTreeMap<String, (&Justfile<'src>, &Scope<'src, '_>)>,
-    search: &Search,
-  ) -> RunResult<'src> {
-    let mutex = ran.mutex(recipe, arguments);
-
-    let mut guard = mutex.lock().unwrap();
-
-    if *guard {
-      return Ok(());
-    }
-
-    if !config.yes && !recipe.confirm()? {
-      return Err(Error::NotConfirmed {
-        recipe: recipe.name(),
-      });
-    }
-
-    let (module, scope) = scopes
-      .get(recipe.module_path())
-      .expect("failed to retrieve scope for module");
-
-    let context = ExecutionContext {
-      config,
-      dotenv,
-      module,
-      search,
-    };
-
-    let (outer, positional) = Evaluator::evaluate_parameters(
-      &context,
-      is_dependency,
-      arguments,
-      &recipe.parameters,
-      scope,
-    )?;
-
-    let scope = outer.child();
-
-    let mut evaluator = Evaluator::new(&context, true, &scope);
-
-    Self::run_dependencies(
-      config,
-      &context,
-      recipe.priors(),
-      dotenv,
-      &mut evaluator,
-      ran,
-      recipe,
-      scopes,
-      search,
-    )?;
-
-    recipe.run(&context, &scope, &positional, is_dependency)?;
-
-    Self::run_dependencies(
-      config,
-      &context,
-      recipe.subsequents(),
-      dotenv,
-      &mut evaluator,
-      &Ran::default(),
-      recipe,
-      scopes,
-      search,
-    )?;
-
-    *guard = true;
-
-    Ok(())
-  }
-
-  fn run_dependencies<'run>(
-    config: &Config,
-    context: &ExecutionContext<'src, 'run>,
-    dependencies: &[Dependency<'src>],
-    dotenv: &BTreeMap<String, String>,
-    evaluator: &mut Evaluator<'src, 'run>,
-    ran: &Ran,
-    recipe: &Recipe<'src>,
-    scopes: &BTreeMap<String, (&Justfile<'src>, &Scope<'src, 'run>)>,
-    search: &Search,
-  ) -> RunResult<'src> {
-    if context.config.no_dependencies {
-      return Ok(());
-    }
-
-    let mut evaluated = Vec::new();
-    for Dependency { recipe, arguments } in dependencies {
-      let arguments = arguments
-        .iter()
-        .map(|argument| evaluator.evaluate_expression(argument))
-        .collect::<RunResult<Vec<String>>>()?;
-      evaluated.push((recipe, arguments));
-    }
-
-    if recipe.is_parallel() {
-      thread::scope::<_, RunResult>(|thread_scope| {
-        let mut handles = Vec::new();
-        for (recipe, arguments) in evaluated {
-          handles.push(thread_scope.spawn(move || {
-            Self::run_recipe(
-              &arguments, config, dotenv, true, ran, recipe, scopes, search,
-            )
-          }));
-        }
-        for handle in handles {
-          handle
-            .join()
-            .map_err(|_| Error::internal("parallel dependency thread panicked"))??;
-        }
-        Ok(())
-      })?;
-    } else {
-      for (recipe, arguments) in evaluated {
-        Self::run_recipe(
-          &arguments, config, dotenv, true, ran, recipe, scopes, search,
-        )?;
-      }
-    }
-
-    Ok(())
-  }
-
   pub(crate) fn modules(&self, config: &Config) -> Vec<&Justfile> {
     let mut modules = self.modules.values().collect::<Vec<&Justfile>>();
 
@@ -653,13 +639,14 @@ mod tests {
     args: ["a", "b", "c"],
     error: ArgumentCountMismatch {
       recipe,
-      parameters,
       found,
       min,
       max,
+      usage,
     },
     check: {
-      let param_names = parameters
+      let param_names = usage
+        .parameters
         .iter()
         .map(|p| p.name.lexeme())
         .collect::<Vec<&str>>();
@@ -677,13 +664,14 @@ mod tests {
     args: ["a", "B", "C"],
     error: ArgumentCountMismatch {
       recipe,
-      parameters,
       found,
       min,
       max,
+      usage,
     },
     check: {
-      let param_names = parameters
+      let param_names = usage
+        .parameters
         .iter()
         .map(|p| p.name.lexeme())
         .collect::<Vec<&str>>();
@@ -701,13 +689,14 @@ mod tests {
     args: ["a"],
     error: ArgumentCountMismatch {
       recipe,
-      parameters,
       found,
       min,
       max,
+      usage,
     },
     check: {
-      let param_names = parameters
+      let param_names = usage
+        .parameters
         .iter()
         .map(|p| p.name.lexeme())
         .collect::<Vec<&str>>();
@@ -725,13 +714,14 @@ mod tests {
     args: ["a", "b"],
     error: ArgumentCountMismatch {
       recipe,
-      parameters,
       found,
       min,
       max,
+      usage,
     },
     check: {
-      let param_names = parameters
+      let param_names = usage
+        .parameters
         .iter()
         .map(|p| p.name.lexeme())
         .collect::<Vec<&str>>();
@@ -749,13 +739,14 @@ mod tests {
     args: ["a"],
     error: ArgumentCountMismatch {
       recipe,
-      parameters,
       found,
       min,
       max,
+      usage,
     },
     check: {
-      let param_names = parameters
+      let param_names = usage
+        .parameters
         .iter()
         .map(|p| p.name.lexeme())
         .collect::<Vec<&str>>();
@@ -7,7 +7,9 @@ pub(crate) enum Keyword {
   AllowDuplicateRecipes,
   AllowDuplicateVariables,
   Assert,
+  Bool,
   DotenvFilename,
+  DotenvFiles,
   DotenvLoad,
   DotenvOverride,
   DotenvPath,
@@ -16,20 +18,24 @@ pub(crate) enum Keyword {
   Export,
   Fallback,
   False,
+  Fn,
   If,
   IgnoreComments,
   Import,
+  Int,
   Mod,
   NoExitMessage,
   PositionalArguments,
   Quiet,
   ScriptInterpreter,
+  Secret,
   Set,
   Shell,
   Tempdir,
   True,
   Unexport,
   Unstable,
+  VariadicLists,
   WindowsPowershell,
   WindowsShell,
   WorkingDirectory,
@@ -484,6 +484,7 @@ impl<'src> Lexer<'src> {
       '*' => self.lex_single(Asterisk),
       '+' => self.lex_single(Plus),
       ',' => self.lex_single(Comma),
+      '-' if self.rest_starts_with("--") => self.lex_double(MinusMinus),
       '/' => self.lex_single(Slash),
       ':' => self.lex_colon(),
       '=' => self.lex_choices(
@@ -500,7 +501,7 @@ impl<'src> Lexer<'src> {
       ']' => self.lex_delimiter(BracketR),
       '`' | '"' | '\'' => self.lex_string(),
       '{' => self.lex_delimiter(BraceL),
-      '|' => self.lex_digraph('|', '|', BarBar),
+      '|' => self.lex_choices('|', &[('|', BarBar)], Some(Bar)),
       '}' => self.lex_delimiter(BraceR),
       _ if Self::is_identifier_start(start) => self.lex_identifier(),
       _ => {
@@ -977,6 +978,7 @@ mod tests {
       At => "@",
       BangEquals => "!=",
       BangTilde => "!~",
+      Bar => "|",
       BarBar => "||",
       BraceL => "{",
       BraceR => "}",
@@ -995,6 +997,7 @@ mod tests {
       Indent => "  ",
       InterpolationEnd => "}}",
       InterpolationStart => "{{",
+      MinusMinus => "--",
       ParenL => "(",
       ParenR => ")",
       Plus => "+",
@@ -9,13 +9,15 @@ pub(crate) use {
     alias::Alias,
     alias_style::AliasStyle,
     analyzer::Analyzer,
-    argument_parser::ArgumentParser,
+    argument_parser::{ArgumentGroup, ArgumentParser},
+    arguments::Arguments,
     assignment::Assignment,
     assignment_resolver::AssignmentResolver,
     ast::Ast,
     attribute::{Attribute, AttributeDiscriminant},
     attribute_set::AttributeSet,
     binding::Binding,
+    cache::Cache,
     color::Color,
     color_display::ColorDisplay,
     command_color::CommandColor,
@@ -28,6 +30,10 @@ pub(crate) use {
     conditional_operator::ConditionalOperator,
     config::Config,
     config_error::ConfigError,
+    config_file::ConfigFile,
+    config_layers::ConfigLayers,
+    config_source::ConfigSource,
+    config_value::ConfigValue,
     constants::constants,
     count::Count,
     delimiter::Delimiter,
@@ -36,11 +42,18 @@ pub(crate) use {
     enclosure::Enclosure,
     error::Error,
     evaluator::Evaluator,
+    event::Event,
+    event_format::EventFormat,
+    events::Events,
     execution_context::ExecutionContext,
     executor::Executor,
     expression::Expression,
     fragment::Fragment,
+    freshness::Freshness,
     function::Function,
+    function_scope::FunctionScope,
+    graph::Graph,
+    graph_format::GraphFormat,
     interpreter::Interpreter,
     item::Item,
     justfile::Justfile,
@@ -48,9 +61,12 @@ pub(crate) use {
     keyword::Keyword,
     lexer::Lexer,
     line::Line,
+    lint::Lint,
+    linter::Linter,
     list::List,
     load_dotenv::load_dotenv,
     loader::Loader,
+    lsp::Lsp,
     module_path::ModulePath,
     name::Name,
     namepath::Namepath,
@@ -58,7 +74,10 @@ pub(crate) use {
     output_error::OutputError,
     parameter::Parameter,
     parameter_kind::ParameterKind,
+    parameter_type::ParameterType,
     parser::Parser,
+    picker::Picker,
+    plan::Plan,
     platform::Platform,
     platform_interface::PlatformInterface,
     position::Position,
@@ -68,6 +87,11 @@ pub(crate) use {
     recipe::Recipe,
     recipe_resolver::RecipeResolver,
     recipe_signature::RecipeSignature,
+    recipe_usage::RecipeUsage,
+    report::{Report, ReportCase, ReportFailure},
+    report_format::ReportFormat,
+    retry::Retry,
+    scheduler::Scheduler,
     scope::Scope,
     search::Search,
     search_config::SearchConfig,
@@ -87,15 +111,20 @@ pub(crate) use {
     suggestion::Suggestion,
     table::Table,
     thunk::Thunk,
+    timeout::Timeout,
     token::Token,
     token_kind::TokenKind,
     unresolved_dependency::UnresolvedDependency,
     unresolved_recipe::UnresolvedRecipe,
     unstable_feature::UnstableFeature,
     use_color::UseColor,
+    user_function::UserFunction,
+    value::Value,
     variables::Variables,
     verbosity::Verbosity,
+    walk::Walk,
     warning::Warning,
+    watcher::Watcher,
     which::which,
   },
   camino::Utf8Path,
@@ -114,7 +143,7 @@ pub(crate) use {
   std::{
     borrow::Cow,
     cmp,
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     env,
     ffi::OsString,
     fmt::{self, Debug, Display, Formatter},
@@ -122,12 +151,14 @@ pub(crate) use {
     io::{self, Write},
     iter::{self, FromIterator},
     mem,
+    num::NonZeroUsize,
     ops::Deref,
     ops::{Index, Range, RangeInclusive},
     path::{self, Path, PathBuf},
     process::{self, Command, ExitStatus, Stdio},
-    str::{self, Chars},
-    sync::{Arc, LazyLock, Mutex, MutexGuard, OnceLock},
+    ptr, slice,
+    str::{self, Chars, FromStr},
+    sync::{Arc, Condvar, LazyLock, Mutex, MutexGuard, OnceLock},
     thread, vec,
   },
   strum::{Display, EnumDiscriminants, EnumString, IntoStaticStr},
@@ -137,10 +168,7 @@ pub(crate) use {
 };
 
 #[cfg(test)]
-pub(crate) use {
-  crate::{node::Node, tree::Tree},
-  std::slice,
-};
+pub(crate) use crate::{node::Node, tree::Tree};
 
 pub use crate::run::run;
 
@@ -156,6 +184,7 @@ type ConfigResult<T> = Result<T, ConfigError>;
 type FunctionResult = Result<String, String>;
 type RunResult<'a, T = ()> = Result<T, Error<'a>>;
 type SearchResult<T> = Result<T, SearchError>;
+type ValueResult = Result<Value, String>;
 
 #[cfg(test)]
 #[macro_use]
@@ -185,12 +214,14 @@ mod alias;
 mod alias_style;
 mod analyzer;
 mod argument_parser;
+mod arguments;
 mod assignment;
 mod assignment_resolver;
 mod ast;
 mod attribute;
 mod attribute_set;
 mod binding;
+mod cache;
 mod color;
 mod color_display;
 mod command_color;
@@ -204,6 +235,10 @@ mod condition;
 mod conditional_operator;
 mod config;
 mod config_error;
+mod config_file;
+mod config_layers;
+mod config_source;
+mod config_value;
 mod constants;
 mod count;
 mod delimiter;
@@ -212,11 +247,18 @@ mod dump_format;
 mod enclosure;
 mod error;
 mod evaluator;
+mod event;
+mod event_format;
+mod events;
 mod execution_context;
 mod executor;
 mod expression;
 mod fragment;
+mod freshness;
 mod function;
+mod function_scope;
+mod graph;
+mod graph_format;
 mod interpreter;
 mod item;
 mod justfile;
@@ -224,9 +266,12 @@ mod keyed;
 mod keyword;
 mod lexer;
 mod line;
+mod lint;
+mod linter;
 mod list;
 mod load_dotenv;
 mod loader;
+mod lsp;
 mod module_path;
 mod name;
 mod namepath;
@@ -234,7 +279,10 @@ mod ordinal;
 mod output_error;
 mod parameter;
 mod parameter_kind;
+mod parameter_type;
 mod parser;
+mod picker;
+mod plan;
 mod platform;
 mod platform_interface;
 mod position;
@@ -244,11 +292,17 @@ mod range_ext;
 mod recipe;
 mod recipe_resolver;
 mod recipe_signature;
+mod recipe_usage;
+mod report;
+mod report_format;
+mod retry;
 mod run;
+mod scheduler;
 mod scope;
 mod search;
 mod search_config;
 mod search_error;
+mod secrets;
 mod set;
 mod setting;
 mod settings;
@@ -266,6 +320,7 @@ mod subcommand;
 mod suggestion;
 mod table;
 mod thunk;
+mod timeout;
 mod token;
 mod token_kind;
 mod unindent;
@@ -273,7 +328,11 @@ mod unresolved_dependency;
 mod unresolved_recipe;
 mod unstable_feature;
 mod use_color;
+mod user_function;
+mod value;
 mod variables;
 mod verbosity;
+mod walk;
 mod warning;
+mod watcher;
 mod which;
@@ -0,0 +1,43 @@
+use super::*;
+
+/// A lint which may be silenced with `[allow(…)]`
+#[derive(
+  Clone, Copy, Debug, EnumString, Eq, IntoStaticStr, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Lint {
+  DeprecatedFunction,
+  ParameterShadowsVariable,
+  RecipeShadowsAlias,
+  SettingOverriddenByImport,
+  UnreachableRecipe,
+  UnusedParameter,
+  UnusedVariable,
+}
+
+impl Lint {
+  pub(crate) fn name(self) -> &'static str {
+    self.into()
+  }
+}
+
+impl Display for Lint {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn name() {
+    assert_eq!(Lint::UnusedVariable.name(), "unused-variable");
+    assert_eq!(
+      "unused-variable".parse::<Lint>().unwrap(),
+      Lint::UnusedVariable
+    );
+  }
+}
@@ -0,0 +1,282 @@
+use super::*;
+
+const LINUX: u8 = 1 << 0;
+const MACOS: u8 = 1 << 1;
+const OPENBSD: u8 = 1 << 2;
+const OTHER_UNIX: u8 = 1 << 3;
+const WINDOWS: u8 = 1 << 4;
+const ALL: u8 = LINUX | MACOS | OPENBSD | OTHER_UNIX | WINDOWS;
+
+/// Lints the items of a single module, including recipes which are not
+/// enabled on the current platform
+pub(crate) struct Linter<'run, 'src> {
+  aliases: Vec<&'run Alias<'src, Namepath<'src>>>,
+  assignments: Vec<(&'run Assignment<'src>, &'run BTreeSet<Lint>)>,
+  functions: Vec<&'run UserFunction<'src>>,
+  recipes: Vec<&'run UnresolvedRecipe<'src>>,
+  settings: &'run Settings<'src>,
+  variables: &'run Table<'src, Assignment<'src>>,
+  warnings: Vec<Warning<'src>>,
+}
+
+impl<'run, 'src> Linter<'run, 'src> {
+  pub(crate) fn lint(
+    asts: &[&'run Ast<'src>],
+    settings: &'run Settings<'src>,
+    variables: &'run Table<'src, Assignment<'src>>,
+  ) -> Vec<Warning<'src>> {
+    let mut linter = Self {
+      aliases: Vec::new(),
+      assignments: Vec::new(),
+      functions: Vec::new(),
+      recipes: Vec::new(),
+      settings,
+      variables,
+      warnings: Vec::new(),
+    };
+
+    for item in asts.iter().flat_map(|ast| &ast.items) {
+      match item {
+        Item::Alias(alias) => linter.aliases.push(alias),
+        Item::Assignment {
+          allowed,
+          assignment,
+        } => linter.assignments.push((assignment, allowed)),
+        Item::Function(function) => linter.functions.push(function),
+        Item::Recipe(recipe) => linter.recipes.push(recipe),
+        _ => {}
+      }
+    }
+
+    linter.deprecated_functions();
+    linter.settings_overridden_by_imports(asts);
+    linter.unused_variables();
+
+    for recipe in linter.recipes.clone() {
+      linter.recipe(recipe);
+    }
+
+    linter
+      .warnings
+      .sort_by_key(|warning| (warning.token().path, warning.token().offset));
+
+    linter.warnings
+  }
+
+  fn deprecated_functions(&mut self) {
+    let assignments = self
+      .assignments
+      .iter()
+      .filter(|(_, allowed)| !allowed.contains(&Lint::DeprecatedFunction))
+      .map(|(assignment, _)| &assignment.value);
+
+    let functions = self
+      .functions
+      .iter()
+      .filter(|function| !function.allowed.contains(&Lint::DeprecatedFunction))
+      .map(|function| &function.body);
+
+    let recipes = self
+      .recipes
+      .iter()
+      .filter(|recipe| !recipe.attributes.allows(Lint::DeprecatedFunction))
+      .flat_map(|recipe| Self::expressions(recipe));
+
+    let mut warnings = Vec::new();
+
+    for expression in assignments.chain(functions).chain(recipes) {
+      for expression in expression.walk() {
+        let Expression::Call { thunk } = expression else {
+          continue;
+        };
+
+        let function = thunk.name();
+
+        let replacement = match function.lexeme() {
+          "env_var" | "env_var_or_default" => "env",
+          _ => continue,
+        };
+
+        warnings.push(Warning::DeprecatedFunction {
+          function,
+          replacement,
+        });
+      }
+    }
+
+    self.warnings.extend(warnings);
+  }
+
+  /// Expressions which are evaluated when running `recipe`
+  fn expressions<'a>(
+    recipe: &'a UnresolvedRecipe<'src>,
+  ) -> impl Iterator<Item = &'a Expression<'src>> {
+    let defaults = recipe
+      .parameters
+      .iter()
+      .filter_map(|parameter| parameter.default.as_ref());
+
+    let arguments = recipe
+      .dependencies
+      .iter()
+      .flat_map(|dependency| &dependency.arguments);
+
+    let interpolations = recipe
+      .body
+      .iter()
+      .flat_map(|line| &line.fragments)
+      .filter_map(|fragment| match fragment {
+        Fragment::Interpolation { expression } => Some(expression),
+        Fragment::Text { .. } => None,
+      });
+
+    let env = recipe.env().into_iter().map(|(_key, value)| value);
+
+    defaults.chain(arguments).chain(env).chain(interpolations)
+  }
+
+  fn platforms(recipe: &UnresolvedRecipe) -> u8 {
+    let mut platforms = 0;
+
+    for attribute in &recipe.attributes {
+      platforms |= match attribute {
+        Attribute::Linux => LINUX,
+        Attribute::Macos => MACOS,
+        Attribute::Openbsd => OPENBSD,
+        Attribute::Unix => LINUX | MACOS | OPENBSD | OTHER_UNIX,
+        Attribute::Windows => WINDOWS,
+        _ => 0,
+      };
+    }
+
+    if platforms == 0 {
+      ALL
+    } else {
+      platforms
+    }
+  }
+
+  fn recipe(&mut self, recipe: &UnresolvedRecipe<'src>) {
+    let platforms = Self::platforms(recipe);
+
+    if platforms != ALL && !recipe.attributes.allows(Lint::RecipeShadowsAlias) {
+      for alias in &self.aliases {
+        if alias.name.lexeme() == recipe.name.lexeme()
+          && !alias.attributes.allows(Lint::RecipeShadowsAlias)
+        {
+          self.warnings.push(Warning::RecipeShadowsAlias {
+            alias: alias.name,
+            recipe: recipe.name,
+          });
+        }
+      }
+    }
+
+    if platforms != ALL && !recipe.attributes.allows(Lint::UnreachableRecipe) {
+      let shadowed_by = self.recipes.iter().find(|other| {
+        other.name.lexeme() == recipe.name.lexeme()
+          && other.name.path == recipe.name.path
+          && other.name.offset > recipe.name.offset
+          && Self::platforms(other) & platforms == platforms
+      });
+
+      if let Some(shadowed_by) = shadowed_by {
+        self.warnings.push(Warning::UnreachableRecipe {
+          recipe: recipe.name,
+          shadowed_by: shadowed_by.name,
+        });
+      }
+    }
+
+    let used = Self::expressions(recipe)
+      .flat_map(Expression::variables)
+      .map(|variable| variable.lexeme())
+      .collect::<HashSet<&str>>();
+
+    let positional_arguments = self.settings.positional_arguments
+      || recipe
+        .attributes
+        .contains(AttributeDiscriminant::PositionalArguments);
+
+    for parameter in &recipe.parameters {
+      let name = parameter.name.lexeme();
+
+      if self.variables.contains_key(name)
+        && !recipe.attributes.allows(Lint::ParameterShadowsVariable)
+      {
+        self.warnings.push(Warning::ParameterShadowsVariable {
+          parameter: parameter.name,
+          recipe: recipe.name.lexeme(),
+        });
+      }
+
+      if !used.contains(name)
+        && !parameter.export
+        && !positional_arguments
+        && !self.settings.export
+        && !recipe.attributes.allows(Lint::UnusedParameter)
+      {
+        self.warnings.push(Warning::UnusedParameter {
+          parameter: parameter.name,
+          recipe: recipe.name.lexeme(),
+        });
+      }
+    }
+  }
+
+  /// Settings in imported files apply to the whole module, overriding the
+  /// defaults of the importing justfile
+  fn settings_overridden_by_imports(&mut self, asts: &[&Ast<'src>]) {
+    for item in asts.iter().skip(1).flat_map(|ast| &ast.items) {
+      if let Item::Set { allowed, set } = item {
+        if !allowed.contains(&Lint::SettingOverriddenByImport) {
+          self
+            .warnings
+            .push(Warning::SettingOverriddenByImport { setting: set.name });
+        }
+      }
+    }
+  }
+
+  fn unused_variables(&mut self) {
+    if self.settings.export {
+      return;
+    }
+
+    let mut used = HashSet::new();
+
+    for (assignment, _) in &self.assignments {
+      used.extend(
+        assignment
+          .value
+          .variables()
+          .map(|variable| variable.lexeme()),
+      );
+    }
+
+    for recipe in &self.recipes {
+      used.extend(
+        Self::expressions(recipe)
+          .flat_map(Expression::variables)
+          .map(|variable| variable.lexeme())
+          .filter(|variable| {
+            !recipe
+              .parameters
+              .iter()
+              .any(|parameter| parameter.name.lexeme() == *variable)
+          }),
+      );
+    }
+
+    for (assignment, allowed) in &self.assignments {
+      if !assignment.export
+        && !used.contains(assignment.name.lexeme())
+        && !allowed.contains(&Lint::UnusedVariable)
+      {
+        self.warnings.push(Warning::UnusedVariable {
+          variable: assignment.name,
+        });
+      }
+    }
+  }
+}
@@ -1,10 +1,15 @@
 use super::*;
 
+const PROFILE: &str = "{{profile}}";
+
 pub(crate) fn load_dotenv(
   config: &Config,
   settings: &Settings,
   working_directory: &Path,
-) -> RunResult<'static, BTreeMap<String, String>> {
+) -> RunResult<'static, (BTreeMap<String, String>, Vec<(PathBuf, Vec<String>)>)> {
+  let mut dotenv = BTreeMap::new();
+  let mut sources = Vec::new();
+
   let dotenv_filename = config
     .dotenv_filename
     .as_ref()
@@ -20,14 +25,47 @@ pub(crate) fn load_dotenv(
     && !settings.dotenv_required
     && dotenv_filename.is_none()
     && dotenv_path.is_none()
+    && settings.dotenv_files.is_none()
   {
-    return Ok(BTreeMap::new());
+    return Ok((dotenv, sources));
+  }
+
+  if let (None, None, Some(files)) = (
+    &config.dotenv_filename,
+    &config.dotenv_path,
+    &settings.dotenv_files,
+  ) {
+    let mut found = false;
+
+    for file in files {
+      let file = if file.contains(PROFILE) {
+        let Some(profile) = &config.dotenv_profile else {
+          continue;
+        };
+        file.replace(PROFILE, profile)
+      } else {
+        file.clone()
+      };
+
+      let path = working_directory.join(file);
+      if path.is_file() {
+        load_from_file(&path, settings, &mut dotenv, &mut sources)?;
+        found = true;
+      }
+    }
+
+    return if found || !settings.dotenv_required {
+      Ok((dotenv, sources))
+    } else {
+      Err(Error::DotenvRequired)
+    };
   }
 
   if let Some(path) = dotenv_path {
     let path = working_directory.join(path);
     if path.is_file() {
-      return load_from_file(&path, settings);
+      load_from_file(&path, settings, &mut dotenv, &mut sources)?;
+      return Ok((dotenv, sources));
     }
   }
 
@@ -36,28 +74,68 @@ pub(crate) fn load_dotenv(
   for directory in working_directory.ancestors() {
     let path = directory.join(filename);
     if path.is_file() {
-      return load_from_file(&path, settings);
+      load_from_file(&path, settings, &mut dotenv, &mut sources)?;
+      return Ok((dotenv, sources));
     }
   }
 
   if settings.dotenv_required {
     Err(Error::DotenvRequired)
   } else {
-    Ok(BTreeMap::new())
+    Ok((dotenv, sources))
   }
 }
 
+/// Load variables from the file at `path`, overriding those loaded from
+/// earlier files, and record `path` as the source of the variables it sets
 fn load_from_file(
   path: &Path,
   settings: &Settings,
-) -> RunResult<'static, BTreeMap<String, String>> {
-  let iter = dotenvy::from_path_iter(path)?;
-  let mut dotenv = BTreeMap::new();
+  dotenv: &mut BTreeMap<String, String>,
+  sources: &mut Vec<(PathBuf, Vec<String>)>,
+) -> RunResult<'static> {
+  // `dotenvy` only expands `${VAR}` using the environment and variables
+  // defined earlier in the same file, so variables loaded from earlier files
+  // are prepended as quoted assignments, and skipped when read back.
+  let mut prefix = String::new();
+  for (key, value) in dotenv.iter() {
+    let mut escaped = String::new();
+    for c in value.chars() {
+      match c {
+        '\\' | '"' | '$' => {
+          escaped.push('\\');
+          escaped.push(c);
+        }
+        '\n' => escaped.push_str("\\n"),
+        _ => escaped.push(c),
+      }
+    }
+    prefix.push_str(key);
+    prefix.push_str("=\"");
+    prefix.push_str(&escaped);
+    prefix.push_str("\"\n");
+  }
+
+  let file = fs::File::open(path).map_err(dotenvy::Error::Io)?;
+
+  let iter =
+    dotenvy::from_read_iter(io::Read::chain(io::Cursor::new(prefix), file)).skip(dotenv.len());
+
+  let mut names = Vec::new();
   for result in iter {
     let (key, value) = result?;
     if settings.dotenv_override || env::var_os(&key).is_none() {
+      for (_path, names) in sources.iter_mut() {
+        names.retain(|name| *name != key);
+      }
+      if !names.contains(&key) {
+        names.push(key.clone());
+      }
       dotenv.insert(key, value);
     }
   }
-  Ok(dotenv)
+
+  sources.push((path.into(), names));
+
+  Ok(())
 }
@@ -1,6 +1,7 @@
 use super::*;
 
 pub(crate) struct Loader {
+  buffers: HashMap<PathBuf, String>,
   paths: Arena<PathBuf>,
   srcs: Arena<String>,
 }
@@ -8,20 +9,30 @@ pub(crate) struct Loader {
 impl Loader {
   pub(crate) fn new() -> Self {
     Self {
+      buffers: HashMap::new(),
       srcs: Arena::new(),
       paths: Arena::new(),
     }
   }
 
+  /// Load `path` from `src` instead of from disk. Used by the language server
+  /// for documents with unsaved changes.
+  pub(crate) fn buffer(&mut self, path: PathBuf, src: String) {
+    self.buffers.insert(path, src);
+  }
+
   pub(crate) fn load<'src>(
     &'src self,
     root: &Path,
     path: &Path,
   ) -> RunResult<'src, (&'src Path, &'src str)> {
-    let src = fs::read_to_string(path).map_err(|io_error| Error::Load {
-      path: path.into(),
-      io_error,
-    })?;
+    let src = match self.buffers.get(path) {
+      Some(src) => src.clone(),
+      None => fs::read_to_string(path).map_err(|io_error| Error::Load {
+        path: path.into(),
+        io_error,
+      })?,
+    };
 
     let relative = path.strip_prefix(root.parent().unwrap()).unwrap_or(path);
 
@@ -0,0 +1,654 @@
+use {
+  super::*,
+  percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS},
+  serde_json::{json, Value as Json},
+  std::io::BufRead,
+};
+
+const URI: &AsciiSet = &CONTROLS
+  .add(b' ')
+  .add(b'"')
+  .add(b'#')
+  .add(b'%')
+  .add(b'<')
+  .add(b'>')
+  .add(b'?')
+  .add(b'`')
+  .add(b'{')
+  .add(b'}');
+
+/// A language server, speaking JSON-RPC over stdin and stdout.
+///
+/// Open documents are kept in memory, and recompiled on every request, since
+/// justfiles are small and compilation is fast.
+#[derive(Default)]
+pub(crate) struct Lsp {
+  documents: BTreeMap<String, String>,
+}
+
+/// The item that a name in a document refers to
+enum Definition<'src, 'run> {
+  Alias {
+    alias: &'run Alias<'src>,
+    module: &'run Justfile<'src>,
+  },
+  Assignment(&'run Assignment<'src>),
+  File(PathBuf),
+  Function(&'run UserFunction<'src>),
+  FunctionParameter(Name<'src>),
+  Module(&'run Justfile<'src>),
+  Parameter(&'run Parameter<'src>),
+  Recipe {
+    module: &'run Justfile<'src>,
+    recipe: &'run Recipe<'src>,
+  },
+}
+
+impl Definition<'_, '_> {
+  fn hover(&self) -> Option<String> {
+    match self {
+      Self::Alias { alias, module } => Some(
+        RecipeUsage::new(module, &alias.target)
+          .color_display(Color::never())
+          .to_string(),
+      ),
+      Self::Assignment(assignment) => Some(assignment.to_string()),
+      Self::File(_) | Self::FunctionParameter(_) => None,
+      Self::Function(function) => Some(function.to_string()),
+      Self::Module(module) => module.doc.clone(),
+      Self::Parameter(parameter) => Some(parameter.color_display(Color::never()).to_string()),
+      Self::Recipe { module, recipe } => Some(
+        RecipeUsage::new(module, recipe)
+          .color_display(Color::never())
+          .to_string(),
+      ),
+    }
+  }
+
+  fn location(&self, root: &Path) -> Json {
+    let token = match self {
+      Self::Alias { alias, .. } => alias.name.token,
+      Self::Assignment(assignment) => assignment.name.token,
+      Self::File(path) => {
+        return json!({
+          "uri": Lsp::uri(path),
+          "range": Lsp::range_empty(),
+        })
+      }
+      Self::Function(function) => function.name.token,
+      Self::FunctionParameter(name) => name.token,
+      Self::Module(module) => return Definition::File(module.source.clone()).location(root),
+      Self::Parameter(parameter) => parameter.name.token,
+      Self::Recipe { recipe, .. } => recipe.name.token,
+    };
+
+    json!({
+      "uri": Lsp::uri(&root.parent().unwrap().join(token.path)),
+      "range": Lsp::range(token),
+    })
+  }
+}
+
+impl Lsp {
+  fn completion(&self, uri: &str) -> Json {
+    let mut items = function::names()
+      .map(|name| json!({ "label": name, "kind": 3, "detail": "built-in function" }))
+      .collect::<Vec<Json>>();
+
+    if let Some(path) = Self::path(uri) {
+      let loader = self.loader();
+      if let Ok(compilation) = Compiler::compile(&loader, &path) {
+        let justfile = &compilation.justfile;
+
+        for function in justfile.functions.values() {
+          items.push(json!({
+            "label": function.name.lexeme(),
+            "kind": 3,
+            "detail": function.to_string(),
+          }));
+        }
+
+        for assignment in justfile.assignments.values() {
+          items.push(json!({
+            "label": assignment.name.lexeme(),
+            "kind": 6,
+            "detail": assignment.to_string(),
+          }));
+        }
+      }
+    }
+
+    Json::Array(items)
+  }
+
+  fn definition(&self, uri: &str, position: &Json) -> Json {
+    self
+      .resolve(uri, position, |root, definition| {
+        Some(definition.location(root))
+      })
+      .unwrap_or(Json::Null)
+  }
+
+  fn diagnostic(root: &Path, error: &Error) -> Json {
+    let mut message = error.color_display(Color::never()).to_string();
+    let mut range = Self::range_empty();
+
+    if let Some(token) = error.context() {
+      let context = format!("\n{}", token.color_display(Color::never()));
+
+      if let Some(stripped) = message.strip_suffix(&context) {
+        message = stripped.into();
+      }
+
+      if root.parent().unwrap().join(token.path) == root {
+        range = Self::range(token);
+      } else {
+        message = format!(
+          "{}:{}:{}: {}",
+          token.path.display(),
+          token.line.ordinal(),
+          token.column.ordinal(),
+          message.strip_prefix("error: ").unwrap_or(&message),
+        );
+      }
+    }
+
+    json!({
+      "range": range,
+      "severity": 1,
+      "source": "just",
+      "message": message.strip_prefix("error: ").unwrap_or(&message),
+    })
+  }
+
+  fn diagnostics(&self, uri: &str) -> Json {
+    let mut diagnostics = Vec::new();
+
+    if let Some(path) = Self::path(uri) {
+      let loader = self.loader();
+      match Compiler::compile(&loader, &path) {
+        Ok(compilation) => {
+          for warning in &compilation.justfile.warnings {
+            let token = warning.token();
+
+            if path.parent().unwrap().join(token.path) == path {
+              diagnostics.push(json!({
+                "range": Self::range(token),
+                "severity": 2,
+                "source": "just",
+                "code": warning.lint(),
+                "message": warning.to_string(),
+              }));
+            }
+          }
+        }
+        Err(error) => diagnostics.push(Self::diagnostic(&path, &error)),
+      }
+    }
+
+    Self::notification(
+      "textDocument/publishDiagnostics",
+      json!({
+        "uri": uri,
+        "diagnostics": diagnostics,
+      }),
+    )
+  }
+
+  /// Find the definition of the name or path at `offset` in the root source
+  /// file of `compilation`.
+  fn find<'src: 'run, 'run>(
+    compilation: &'run Compilation<'src>,
+    offset: usize,
+  ) -> Option<Definition<'src, 'run>> {
+    let justfile = &compilation.justfile;
+
+    let token = Lexer::lex(Path::new(""), compilation.root_src())
+      .ok()?
+      .into_iter()
+      .find(|token| {
+        matches!(token.kind, TokenKind::Identifier | TokenKind::StringToken)
+          && token.offset <= offset
+          && offset <= token.offset + token.length
+      })?;
+
+    for item in &compilation.root_ast().items {
+      match item {
+        Item::Alias(alias) => {
+          if let Some(definition) = Self::find_path(justfile, &alias.target, token) {
+            return Some(definition);
+          }
+        }
+        Item::Function(function) if function.name.line == token.line => {
+          if let Some(parameter) = function
+            .parameters
+            .iter()
+            .find(|parameter| parameter.lexeme() == token.lexeme())
+          {
+            return Some(Definition::FunctionParameter(*parameter));
+          }
+        }
+        Item::Import {
+          absolute: Some(absolute),
+          path,
+          ..
+        } if path.offset == token.offset => return Some(Definition::File(absolute.clone())),
+        Item::Module {
+          absolute: Some(absolute),
+          name,
+          ..
+        } if name.offset == token.offset => return Some(Definition::File(absolute.clone())),
+        Item::Recipe(recipe) => {
+          for dependency in &recipe.dependencies {
+            if let Some(definition) = Self::find_path(justfile, &dependency.recipe, token) {
+              return Some(definition);
+            }
+          }
+
+          let end = recipe
+            .body
+            .last()
+            .map_or(recipe.name.line, |line| line.number);
+
+          if recipe.name.offset != token.offset && (recipe.name.line..=end).contains(&token.line) {
+            if let Some(parameter) = recipe
+              .parameters
+              .iter()
+              .find(|parameter| parameter.name.lexeme() == token.lexeme())
+            {
+              return Some(Definition::Parameter(parameter));
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+
+    if token.kind != TokenKind::Identifier {
+      return None;
+    }
+
+    let name = token.lexeme();
+
+    justfile
+      .assignments
+      .get(name)
+      .map(Definition::Assignment)
+      .or_else(|| justfile.functions.get(name).map(Definition::Function))
+      .or_else(|| {
+        justfile.recipes.get(name).map(|recipe| Definition::Recipe {
+          module: justfile,
+          recipe,
+        })
+      })
+      .or_else(|| {
+        justfile.aliases.get(name).map(|alias| Definition::Alias {
+          alias,
+          module: justfile,
+        })
+      })
+      .or_else(|| justfile.modules.get(name).map(Definition::Module))
+  }
+
+  /// Find the recipe or module referred to by the component of `path` at
+  /// `token`, if any.
+  fn find_path<'src: 'run, 'run>(
+    justfile: &'run Justfile<'src>,
+    path: &Namepath<'src>,
+    token: Token<'src>,
+  ) -> Option<Definition<'src, 'run>> {
+    let index = path.iter().position(|name| name.offset == token.offset)?;
+
+    let mut module = justfile;
+    for name in path.iter().take(index) {
+      module = module.modules.get(name.lexeme())?;
+    }
+
+    let name = path.iter().nth(index)?.lexeme();
+
+    if index + 1 == path.components() {
+      module
+        .recipes
+        .get(name)
+        .map(|recipe| Definition::Recipe { module, recipe })
+    } else {
+      module.modules.get(name).map(Definition::Module)
+    }
+  }
+
+  fn handle(&mut self, message: &Json) -> Vec<Json> {
+    let method = message["method"].as_str().unwrap_or_default();
+    let params = &message["params"];
+    let uri = params["textDocument"]["uri"]
+      .as_str()
+      .unwrap_or_default()
+      .to_owned();
+
+    let result = match method {
+      "initialize" => json!({
+        "capabilities": {
+          "completionProvider": {},
+          "definitionProvider": true,
+          "hoverProvider": true,
+          "textDocumentSync": 1,
+        },
+        "serverInfo": {
+          "name": env!("CARGO_PKG_NAME"),
+          "version": env!("CARGO_PKG_VERSION"),
+        },
+      }),
+      "shutdown" => Json::Null,
+      "textDocument/completion" => self.completion(&uri),
+      "textDocument/definition" => self.definition(&uri, &params["position"]),
+      "textDocument/didChange" => {
+        if let Some(text) = params["contentChanges"]
+          .as_array()
+          .and_then(|changes| changes.last())
+          .and_then(|change| change["text"].as_str())
+        {
+          self.documents.insert(uri.clone(), text.into());
+        }
+        return vec![self.diagnostics(&uri)];
+      }
+      "textDocument/didClose" => {
+        self.documents.remove(&uri);
+        return vec![Self::notification(
+          "textDocument/publishDiagnostics",
+          json!({
+            "uri": uri,
+            "diagnostics": [],
+          }),
+        )];
+      }
+      "textDocument/didOpen" => {
+        let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+        self.documents.insert(uri.clone(), text.into());
+        return vec![self.diagnostics(&uri)];
+      }
+      "textDocument/hover" => self.hover(&uri, &params["position"]),
+      _ => {
+        return message
+          .get("id")
+          .map(|id| {
+            json!({
+              "jsonrpc": "2.0",
+              "id": id,
+              "error": {
+                "code": -32601,
+                "message": format!("Method not found: {method}"),
+              },
+            })
+          })
+          .into_iter()
+          .collect();
+      }
+    };
+
+    message
+      .get("id")
+      .map(|id| {
+        json!({
+          "jsonrpc": "2.0",
+          "id": id,
+          "result": result,
+        })
+      })
+      .into_iter()
+      .collect()
+  }
+
+  fn hover(&self, uri: &str, position: &Json) -> Json {
+    self
+      .resolve(uri, position, |_, definition| {
+        definition.hover().map(|value| {
+          json!({
+            "contents": {
+              "kind": "plaintext",
+              "value": value,
+            },
+          })
+        })
+      })
+      .unwrap_or(Json::Null)
+  }
+
+  fn loader(&self) -> Loader {
+    let mut loader = Loader::new();
+
+    for (uri, text) in &self.documents {
+      if let Some(path) = Self::path(uri) {
+        loader.buffer(path, text.clone());
+      }
+    }
+
+    loader
+  }
+
+  fn notification(method: &str, params: Json) -> Json {
+    json!({
+      "jsonrpc": "2.0",
+      "method": method,
+      "params": params,
+    })
+  }
+
+  /// Convert an LSP position, in UTF-16 code units, to a byte offset
+  fn offset(src: &str, position: &Json) -> Option<usize> {
+    let line = usize::try_from(position["line"].as_u64()?).ok()?;
+    let character = usize::try_from(position["character"].as_u64()?).ok()?;
+
+    let start = if line == 0 {
+      0
+    } else {
+      src.match_indices('\n').nth(line - 1)?.0 + 1
+    };
+
+    let mut units = 0;
+    for (i, c) in src[start..].char_indices() {
+      if units >= character || c == '\n' {
+        return Some(start + i);
+      }
+      units += c.len_utf16();
+    }
+
+    Some(src.len())
+  }
+
+  /// Convert a `file` URI to a path. On Windows, paths starting with a drive
+  /// letter appear in URIs as `file:///C:/…`, so the leading slash is removed.
+  fn path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let path = percent_decode_str(path).decode_utf8().ok()?;
+    let path = if cfg!(windows) {
+      Self::strip_drive_slash(&path).unwrap_or(&path)
+    } else {
+      &path
+    };
+    Some(path.into())
+  }
+
+  /// Convert a byte offset to an LSP position, in UTF-16 code units
+  fn position(src: &str, offset: usize) -> Json {
+    let before = &src[..offset];
+    let start = before.rfind('\n').map_or(0, |i| i + 1);
+    json!({
+      "line": before.matches('\n').count(),
+      "character": src[start..offset].encode_utf16().count(),
+    })
+  }
+
+  fn range(token: Token) -> Json {
+    json!({
+      "start": Self::position(token.src, token.offset),
+      "end": Self::position(token.src, token.offset + token.length),
+    })
+  }
+
+  fn range_empty() -> Json {
+    json!({
+      "start": { "line": 0, "character": 0 },
+      "end": { "line": 0, "character": 0 },
+    })
+  }
+
+  fn read(reader: &mut impl BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut length = None;
+
+    loop {
+      let mut line = String::new();
+
+      if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+      }
+
+      let line = line.trim_end();
+
+      if line.is_empty() {
+        break;
+      }
+
+      if let Some((name, value)) = line.split_once(':') {
+        if name.eq_ignore_ascii_case("content-length") {
+          length = value.trim().parse::<usize>().ok();
+        }
+      }
+    }
+
+    let length = length.ok_or_else(|| {
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        "message is missing `Content-Length` header",
+      )
+    })?;
+
+    let mut content = vec![0; length];
+    reader.read_exact(&mut content)?;
+
+    Ok(Some(content))
+  }
+
+  /// Compile the document at `uri`, find the definition at `position`, and
+  /// pass it to `f`
+  fn resolve(
+    &self,
+    uri: &str,
+    position: &Json,
+    f: impl FnOnce(&Path, Definition) -> Option<Json>,
+  ) -> Option<Json> {
+    let path = Self::path(uri)?;
+    let loader = self.loader();
+    let compilation = Compiler::compile(&loader, &path).ok()?;
+    let offset = Self::offset(compilation.root_src(), position)?;
+    f(&path, Self::find(&compilation, offset)?)
+  }
+
+  pub(crate) fn run() -> RunResult<'static> {
+    Self::default()
+      .serve(&mut io::stdin().lock(), &mut io::stdout().lock())
+      .map_err(|io_error| Error::LspIo { io_error })
+  }
+
+  fn serve(&mut self, reader: &mut impl BufRead, writer: &mut impl Write) -> io::Result<()> {
+    while let Some(content) = Self::read(reader)? {
+      let message = match serde_json::from_slice::<Json>(&content) {
+        Ok(message) => message,
+        Err(err) => {
+          Self::write(
+            writer,
+            &json!({
+              "jsonrpc": "2.0",
+              "id": null,
+              "error": {
+                "code": -32700,
+                "message": err.to_string(),
+              },
+            }),
+          )?;
+          continue;
+        }
+      };
+
+      if message["method"] == "exit" {
+        break;
+      }
+
+      for response in self.handle(&message) {
+        Self::write(writer, &response)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// If `path` is a slash followed by a drive letter, as in `/C:/foo`,
+  /// return it without the slash
+  fn strip_drive_slash(path: &str) -> Option<&str> {
+    let rest = path.strip_prefix('/')?;
+    let mut chars = rest.chars();
+    (chars.next()?.is_ascii_alphabetic() && chars.next()? == ':').then_some(rest)
+  }
+
+  fn uri(path: &Path) -> String {
+    let mut path = path.to_string_lossy().into_owned();
+
+    if cfg!(windows) {
+      path = path.replace('\\', "/");
+      if Self::strip_drive_slash(&format!("/{path}")).is_some() {
+        path.insert(0, '/');
+      }
+    }
+
+    format!("file://{}", utf8_percent_encode(&path, URI))
+  }
+
+  fn write(writer: &mut impl Write, message: &Json) -> io::Result<()> {
+    let content = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn path() {
+    #[cfg(unix)]
+    assert_eq!(
+      Lsp::path("file:///foo/bar%20baz/justfile").unwrap(),
+      Path::new("/foo/bar baz/justfile"),
+    );
+
+    #[cfg(windows)]
+    assert_eq!(
+      Lsp::path("file:///C:/foo/bar%20baz/justfile").unwrap(),
+      Path::new("C:/foo/bar baz/justfile"),
+    );
+
+    assert_eq!(Lsp::path("untitled:Untitled-1"), None);
+  }
+
+  #[test]
+  fn strip_drive_slash() {
+    assert_eq!(Lsp::strip_drive_slash("/C:/foo"), Some("C:/foo"));
+    assert_eq!(Lsp::strip_drive_slash("/c:"), Some("c:"));
+    assert_eq!(Lsp::strip_drive_slash("/foo"), None);
+    assert_eq!(Lsp::strip_drive_slash("C:/foo"), None);
+  }
+
+  #[test]
+  fn uri() {
+    #[cfg(unix)]
+    assert_eq!(
+      Lsp::uri(Path::new("/foo/bar baz/justfile")),
+      "file:///foo/bar%20baz/justfile",
+    );
+
+    #[cfg(windows)]
+    assert_eq!(
+      Lsp::uri(Path::new(r"C:\foo\bar baz\justfile")),
+      "file:///C:/foo/bar%20baz/justfile",
+    );
+  }
+}
@@ -19,8 +19,9 @@ impl<'src> Node<'src> for Item<'src> {
   fn tree(&self) -> Tree<'src> {
     match self {
       Self::Alias(alias) => alias.tree(),
-      Self::Assignment(assignment) => assignment.tree(),
+      Self::Assignment { assignment, .. } => assignment.tree(),
       Self::Comment(comment) => comment.tree(),
+      Self::Function(function) => function.tree(),
       Self::Import {
         relative, optional, ..
       } => {
@@ -53,7 +54,7 @@ impl<'src> Node<'src> for Item<'src> {
         tree
       }
       Self::Recipe(recipe) => recipe.tree(),
-      Self::Set(set) => set.tree(),
+      Self::Set { set, .. } => set.tree(),
       Self::Unexport { name } => {
         let mut unexport = Tree::atom(Keyword::Unexport.lexeme());
         unexport.push_mut(name.lexeme().replace('-', "_"));
@@ -63,6 +64,20 @@ impl<'src> Node<'src> for Item<'src> {
   }
 }
 
+impl<'src> Node<'src> for UserFunction<'src> {
+  fn tree(&self) -> Tree<'src> {
+    Tree::atom(Keyword::Fn.lexeme())
+      .push(self.name.lexeme())
+      .push(Tree::list(
+        self
+          .parameters
+          .iter()
+          .map(|parameter| Tree::atom(parameter.lexeme())),
+      ))
+      .push(self.body.tree())
+  }
+}
+
 impl<'src> Node<'src> for Namepath<'src> {
   fn tree(&self) -> Tree<'src> {
     match self.components() {
@@ -88,15 +103,19 @@ impl<'src> Node<'src> for Alias<'src, Namepath<'src>> {
 
 impl<'src> Node<'src> for Assignment<'src> {
   fn tree(&self) -> Tree<'src> {
+    let mut tree = Tree::atom("assignment");
+
     if self.export {
-      Tree::atom("assignment")
-        .push("#")
-        .push(Keyword::Export.lexeme())
-    } else {
-      Tree::atom("assignment")
+      tree.push_mut("#");
+      tree.push_mut(Keyword::Export.lexeme());
     }
-    .push(self.name.lexeme())
-    .push(self.value.tree())
+
+    if self.secret {
+      tree.push_mut("#");
+      tree.push_mut(Keyword::Secret.lexeme());
+    }
+
+    tree.push(self.name.lexeme()).push(self.value.tree())
   }
 }
 
@@ -118,7 +137,7 @@ impl<'src> Node<'src> for Expression<'src> {
         let mut tree = Tree::atom("call");
         match thunk {
           Nullary { name, .. } => tree.push_mut(name.lexeme()),
-          Unary { name, arg, .. } => {
+          Unary { name, arg, .. } | UnaryValue { name, arg, .. } => {
             tree.push_mut(name.lexeme());
             tree.push_mut(arg.tree());
           }
@@ -144,6 +163,9 @@ impl<'src> Node<'src> for Expression<'src> {
           }
           Binary {
             name, args: [a, b], ..
+          }
+          | BinaryValue {
+            name, args: [a, b], ..
           } => {
             tree.push_mut(name.lexeme());
             tree.push_mut(a.tree());
@@ -171,6 +193,26 @@ impl<'src> Node<'src> for Expression<'src> {
             tree.push_mut(b.tree());
             tree.push_mut(c.tree());
           }
+          Filter {
+            name,
+            function,
+            list,
+          }
+          | Map {
+            name,
+            function,
+            list,
+          } => {
+            tree.push_mut(name.lexeme());
+            tree.push_mut(list.tree());
+            tree.push_mut(function.lexeme());
+          }
+          UserDefined { args, .. } => {
+            tree.push_mut(thunk.qualified_name());
+            for arg in args {
+              tree.push_mut(arg.tree());
+            }
+          }
         }
         tree
       }
@@ -194,6 +236,7 @@ impl<'src> Node<'src> for Expression<'src> {
         lhs: Some(lhs),
         rhs,
       } => Tree::atom("/").push(lhs.tree()).push(rhs.tree()),
+      Self::List { elements } => Tree::atom("list").extend(elements.iter().map(Node::tree)),
       Self::Or { lhs, rhs } => Tree::atom("||").push(lhs.tree()).push(rhs.tree()),
       Self::StringLiteral {
         string_literal: StringLiteral { cooked, .. },
@@ -222,8 +265,12 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
       let mut params = Tree::atom("params");
 
       for parameter in &self.parameters {
-        if let Some(prefix) = parameter.kind.prefix() {
-          params.push_mut(prefix);
+        match parameter.kind {
+          ParameterKind::Flag => params.push_mut("flag"),
+          ParameterKind::Option => params.push_mut("option"),
+          ParameterKind::Plus => params.push_mut("+"),
+          ParameterKind::Singular => {}
+          ParameterKind::Star => params.push_mut("*"),
         }
 
         params.push_mut(parameter.tree());
@@ -271,6 +318,10 @@ impl<'src> Node<'src> for Parameter<'src> {
   fn tree(&self) -> Tree<'src> {
     let mut children = vec![Tree::atom(self.name.lexeme())];
 
+    if let Some(ty) = &self.ty {
+      children.push(Tree::list([Tree::atom("type"), ty.tree()]));
+    }
+
     if let Some(default) = &self.default {
       children.push(default.tree());
     }
@@ -279,6 +330,18 @@ impl<'src> Node<'src> for Parameter<'src> {
   }
 }
 
+impl<'src> Node<'src> for ParameterType<'src> {
+  fn tree(&self) -> Tree<'src> {
+    match self {
+      Self::Bool => Tree::atom("bool"),
+      Self::Choice(choices) => {
+        Tree::list(choices.iter().map(|choice| Tree::string(&choice.cooked)))
+      }
+      Self::Int => Tree::atom("int"),
+    }
+  }
+}
+
 impl<'src> Node<'src> for Line<'src> {
   fn tree(&self) -> Tree<'src> {
     Tree::list(self.fragments.iter().map(Node::tree))
@@ -311,10 +374,16 @@ impl<'src> Node<'src> for Set<'src> {
       | Setting::PositionalArguments(value)
       | Setting::Quiet(value)
       | Setting::Unstable(value)
+      | Setting::VariadicLists(value)
       | Setting::WindowsPowerShell(value)
       | Setting::IgnoreComments(value) => {
         set.push_mut(value.to_string());
       }
+      Setting::DotenvFiles(files) => {
+        for file in files {
+          set.push_mut(Tree::string(&file.cooked));
+        }
+      }
       Setting::ScriptInterpreter(Interpreter { command, arguments })
       | Setting::Shell(Interpreter { command, arguments })
       | Setting::WindowsShell(Interpreter { command, arguments }) => {
@@ -335,7 +404,7 @@ impl<'src> Node<'src> for Set<'src> {
   }
 }
 
-impl<'src> Node<'src> for Warning {
+impl<'src> Node<'src> for Warning<'src> {
   fn tree(&self) -> Tree<'src> {
     unreachable!()
   }
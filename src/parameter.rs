@@ -11,11 +11,34 @@ pub(crate) struct Parameter<'src> {
   pub(crate) kind: ParameterKind,
   /// The parameter name
   pub(crate) name: Name<'src>,
+  /// An optional type, which arguments must conform to
+  #[serde(rename = "type")]
+  pub(crate) ty: Option<ParameterType<'src>>,
 }
 
-impl Parameter<'_> {
+impl<'src> Parameter<'src> {
   pub(crate) fn is_required(&self) -> bool {
-    self.default.is_none() && self.kind != ParameterKind::Star
+    self.default.is_none() && matches!(self.kind, ParameterKind::Plus | ParameterKind::Singular)
+  }
+
+  /// Check that every element of `value` conforms to this parameter's type
+  pub(crate) fn check(&self, recipe: &'src str, value: &Value) -> RunResult<'src> {
+    let Some(ty) = &self.ty else {
+      return Ok(());
+    };
+
+    for argument in value.clone().into_list() {
+      if !ty.accepts(&argument) {
+        return Err(Error::InvalidArgument {
+          argument,
+          parameter: self.name.lexeme(),
+          parameter_type: ty.clone(),
+          recipe,
+        });
+      }
+    }
+
+    Ok(())
   }
 }
 
@@ -28,6 +51,9 @@ impl ColorDisplay for Parameter<'_> {
       write!(f, "$")?;
     }
     write!(f, "{}", color.parameter().paint(self.name.lexeme()))?;
+    if let Some(ref ty) = self.ty {
+      write!(f, ": {}", color.annotation().paint(&ty.to_string()))?;
+    }
     if let Some(ref default) = self.default {
       write!(f, "={}", color.string().paint(&default.to_string()))?;
     }
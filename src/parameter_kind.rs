@@ -4,6 +4,10 @@ use super::*;
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum ParameterKind {
+  /// …a flag, `true` if passed as `--name` and `false` otherwise
+  Flag,
+  /// …an option, passed as `--name=value` or `--name value`
+  Option,
   /// …variadic, accepting one or more arguments
   Plus,
   /// …singular, accepting a single argument
@@ -16,12 +20,17 @@ impl ParameterKind {
   pub(crate) fn prefix(self) -> Option<&'static str> {
     match self {
       Self::Singular => None,
+      Self::Flag | Self::Option => Some("--"),
       Self::Plus => Some("+"),
       Self::Star => Some("*"),
     }
   }
 
+  pub(crate) fn is_option(self) -> bool {
+    matches!(self, Self::Flag | Self::Option)
+  }
+
   pub(crate) fn is_variadic(self) -> bool {
-    self != Self::Singular
+    matches!(self, Self::Plus | Self::Star)
   }
 }
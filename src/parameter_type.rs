@@ -0,0 +1,54 @@
+use super::*;
+
+/// The type of a recipe parameter, which arguments must conform to
+#[derive(PartialEq, Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ParameterType<'src> {
+  /// `true` or `false`
+  Bool,
+  /// One of a fixed set of strings
+  Choice(Vec<StringLiteral<'src>>),
+  /// A decimal integer
+  Int,
+}
+
+impl ParameterType<'_> {
+  pub(crate) fn accepts(&self, value: &str) -> bool {
+    match self {
+      Self::Bool => value == "true" || value == "false",
+      Self::Choice(choices) => choices.iter().any(|choice| choice.cooked == value),
+      Self::Int => value.parse::<i64>().is_ok(),
+    }
+  }
+
+  /// Values which may be offered as shell completions
+  pub(crate) fn completions(&self) -> Vec<&str> {
+    match self {
+      Self::Bool => vec!["true", "false"],
+      Self::Choice(choices) => choices
+        .iter()
+        .map(|choice| choice.cooked.as_str())
+        .collect(),
+      Self::Int => Vec::new(),
+    }
+  }
+}
+
+impl Display for ParameterType<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Bool => write!(f, "bool"),
+      Self::Choice(choices) => {
+        write!(f, "(")?;
+        for (i, choice) in choices.iter().enumerate() {
+          if i > 0 {
+            write!(f, " | ")?;
+          }
+          write!(f, "{choice}")?;
+        }
+        write!(f, ")")
+      }
+      Self::Int => write!(f, "int"),
+    }
+  }
+}
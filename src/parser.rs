@@ -342,9 +342,22 @@ impl<'run, 'src> Parser<'run, 'src> {
           }
           Some(Keyword::Export) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
             self.presume_keyword(Keyword::Export)?;
-            items.push(Item::Assignment(
-              self.parse_assignment(true, take_attributes())?,
-            ));
+            items.push(self.parse_assignment(true, false, take_attributes())?);
+          }
+          Some(Keyword::Export)
+            if self.next_are(&[Identifier, Identifier, Identifier, ColonEquals])
+              && self
+                .rest()
+                .nth(1)
+                .is_some_and(|token| Keyword::Secret == token.lexeme()) =>
+          {
+            self.presume_keyword(Keyword::Export)?;
+            self.presume_keyword(Keyword::Secret)?;
+            items.push(self.parse_assignment(true, true, take_attributes())?);
+          }
+          Some(Keyword::Fn) if self.next_are(&[Identifier, Identifier, ParenL]) => {
+            let attributes = take_attributes();
+            items.push(Item::Function(self.parse_function(&attributes)?));
           }
           Some(Keyword::Unexport)
             if self.next_are(&[Identifier, Identifier, Eof])
@@ -424,6 +437,10 @@ impl<'run, 'src> Parser<'run, 'src> {
               relative,
             });
           }
+          Some(Keyword::Secret) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
+            self.presume_keyword(Keyword::Secret)?;
+            items.push(self.parse_assignment(false, true, take_attributes())?);
+          }
           Some(Keyword::Set)
             if self.next_are(&[Identifier, Identifier, ColonEquals])
               || self.next_are(&[Identifier, Identifier, Comment, Eof])
@@ -431,13 +448,11 @@ impl<'run, 'src> Parser<'run, 'src> {
               || self.next_are(&[Identifier, Identifier, Eof])
               || self.next_are(&[Identifier, Identifier, Eol]) =>
           {
-            items.push(Item::Set(self.parse_set()?));
+            items.push(self.parse_set(take_attributes())?);
           }
           _ => {
             if self.next_are(&[Identifier, ColonEquals]) {
-              items.push(Item::Assignment(
-                self.parse_assignment(false, take_attributes())?,
-              ));
+              items.push(self.parse_assignment(false, false, take_attributes())?);
             } else {
               let doc = pop_doc_comment(&mut items, eol_since_last_comment);
               items.push(Item::Recipe(self.parse_recipe(
@@ -496,7 +511,11 @@ impl<'run, 'src> Parser<'run, 'src> {
     let target = self.parse_namepath()?;
     self.expect_eol()?;
 
-    attributes.ensure_valid_attributes("Alias", *name, &[AttributeDiscriminant::Private])?;
+    attributes.ensure_valid_attributes(
+      "Alias",
+      *name,
+      &[AttributeDiscriminant::Allow, AttributeDiscriminant::Private],
+    )?;
 
     Ok(Alias {
       attributes,
@@ -509,8 +528,9 @@ impl<'run, 'src> Parser<'run, 'src> {
   fn parse_assignment(
     &mut self,
     export: bool,
+    secret: bool,
     attributes: AttributeSet<'src>,
-  ) -> CompileResult<'src, Assignment<'src>> {
+  ) -> CompileResult<'src, Item<'src>> {
     let name = self.parse_name()?;
     self.presume(ColonEquals)?;
     let value = self.parse_expression()?;
@@ -518,15 +538,58 @@ impl<'run, 'src> Parser<'run, 'src> {
 
     let private = attributes.contains(AttributeDiscriminant::Private);
 
-    attributes.ensure_valid_attributes("Assignment", *name, &[AttributeDiscriminant::Private])?;
+    attributes.ensure_valid_attributes(
+      "Assignment",
+      *name,
+      &[AttributeDiscriminant::Allow, AttributeDiscriminant::Private],
+    )?;
+
+    Ok(Item::Assignment {
+      allowed: attributes.allowed(),
+      assignment: Assignment {
+        constant: false,
+        export,
+        file_depth: self.file_depth,
+        name,
+        private: private || name.lexeme().starts_with('_'),
+        secret,
+        value,
+      },
+    })
+  }
 
-    Ok(Assignment {
-      constant: false,
-      export,
-      file_depth: self.file_depth,
+  /// Parse a user-defined function, e.g. `fn slug(s) := lowercase(s)`
+  fn parse_function(
+    &mut self,
+    attributes: &AttributeSet<'src>,
+  ) -> CompileResult<'src, UserFunction<'src>> {
+    self.presume_keyword(Keyword::Fn)?;
+    let name = self.parse_name()?;
+
+    attributes.ensure_valid_attributes("Function", *name, &[AttributeDiscriminant::Allow])?;
+
+    self.presume(ParenL)?;
+
+    let mut parameters = Vec::new();
+
+    while !self.next_is(ParenR) {
+      parameters.push(self.parse_name()?);
+
+      if !self.accepted(Comma)? {
+        break;
+      }
+    }
+
+    self.expect(ParenR)?;
+    self.expect(ColonEquals)?;
+    let body = self.parse_expression()?;
+    self.expect_eol()?;
+
+    Ok(UserFunction {
+      allowed: attributes.allowed(),
+      body,
       name,
-      private: private || name.lexeme().starts_with('_'),
-      value,
+      parameters,
     })
   }
 
@@ -649,6 +712,18 @@ impl<'run, 'src> Parser<'run, 'src> {
     })
   }
 
+  /// Check if the next token is a colon introducing a parameter type, which is
+  /// distinguished from the colon ending a recipe's parameters by being
+  /// followed by another colon on the same line
+  fn next_is_parameter_type(&mut self) -> bool {
+    self.next_is(Colon)
+      && self
+        .rest()
+        .skip(1)
+        .take_while(|token| !matches!(token.kind, Eol | Eof))
+        .any(|token| token.kind == Colon)
+  }
+
   // Check if the next tokens are a shell-expanded string, i.e., `x"foo"`.
   //
   // This function skips initial whitespace tokens, but thereafter is
@@ -698,7 +773,28 @@ impl<'run, 'src> Parser<'run, 'src> {
         self.expect(ParenR)?;
         Ok(Expression::Assert { condition, error })
       } else {
-        let name = self.parse_name()?;
+        let mut name = self.parse_name()?;
+
+        let mut path = Vec::new();
+
+        while self.accepted(ColonColon)? {
+          path.push(name);
+          name = self.parse_name()?;
+        }
+
+        if !path.is_empty() {
+          if !self.next_is(ParenL) {
+            return Err(self.unexpected_token()?);
+          }
+
+          return Ok(Expression::Call {
+            thunk: Thunk::UserDefined {
+              args: self.parse_sequence()?,
+              name,
+              path,
+            },
+          });
+        }
 
         if self.next_is(ParenL) {
           let arguments = self.parse_sequence()?;
@@ -714,6 +810,22 @@ impl<'run, 'src> Parser<'run, 'src> {
           Ok(Expression::Variable { name })
         }
       }
+    } else if self.next_is(BracketL) {
+      self.presume(BracketL)?;
+
+      let mut elements = Vec::new();
+
+      while !self.next_is(BracketR) {
+        elements.push(self.parse_expression()?);
+
+        if !self.accepted(Comma)? {
+          break;
+        }
+      }
+
+      self.expect(BracketR)?;
+
+      Ok(Expression::List { elements })
     } else if self.next_is(ParenL) {
       self.presume(ParenL)?;
       let contents = self.parse_expression()?.into();
@@ -910,8 +1022,18 @@ impl<'run, 'src> Parser<'run, 'src> {
 
     let mut positional = Vec::new();
 
-    while self.next_is(Identifier) || self.next_is(Dollar) {
-      positional.push(self.parse_parameter(ParameterKind::Singular)?);
+    loop {
+      if self.next_is(Identifier) || self.next_is(Dollar) {
+        positional.push(self.parse_parameter(ParameterKind::Singular)?);
+      } else if self.accepted(MinusMinus)? {
+        let mut option = self.parse_parameter(ParameterKind::Option)?;
+        if option.default.is_none() {
+          option.kind = ParameterKind::Flag;
+        }
+        positional.push(option);
+      } else {
+        break;
+      }
     }
 
     let kind = if self.accepted(Plus)? {
@@ -993,6 +1115,30 @@ impl<'run, 'src> Parser<'run, 'src> {
       );
     }
 
+    if attributes.contains(AttributeDiscriminant::Inputs)
+      && !attributes.contains(AttributeDiscriminant::Outputs)
+      && !attributes.contains(AttributeDiscriminant::Cache)
+    {
+      return Err(name.error(CompileErrorKind::InputsWithoutOutputsAttribute {
+        recipe: name.lexeme(),
+      }));
+    }
+
+    if let Some(Attribute::Secret(secrets)) = attributes.get(AttributeDiscriminant::Secret) {
+      for secret in secrets {
+        if !positional
+          .iter()
+          .chain(&variadic)
+          .any(|parameter| parameter.name.lexeme() == secret.cooked)
+        {
+          return Err(name.error(CompileErrorKind::UnknownSecretParameter {
+            parameter: secret.cooked.clone(),
+            recipe: name.lexeme(),
+          }));
+        }
+      }
+    }
+
     let private =
       name.lexeme().starts_with('_') || attributes.contains(AttributeDiscriminant::Private);
 
@@ -1027,6 +1173,13 @@ impl<'run, 'src> Parser<'run, 'src> {
 
     let name = self.parse_name()?;
 
+    let ty = if self.next_is_parameter_type() {
+      self.presume(Colon)?;
+      Some(self.parse_parameter_type()?)
+    } else {
+      None
+    };
+
     let default = if self.accepted(Equals)? {
       Some(self.parse_value()?)
     } else {
@@ -1038,9 +1191,38 @@ impl<'run, 'src> Parser<'run, 'src> {
       export,
       kind,
       name,
+      ty,
     })
   }
 
+  /// Parse a parameter type, either `bool`, `int`, or a parenthesized list of
+  /// choices separated by `|`
+  fn parse_parameter_type(&mut self) -> CompileResult<'src, ParameterType<'src>> {
+    if self.accepted(ParenL)? {
+      let mut choices = vec![self.parse_string_literal()?];
+
+      while self.accepted(Bar)? {
+        choices.push(self.parse_string_literal()?);
+      }
+
+      self.expect(ParenR)?;
+
+      return Ok(ParameterType::Choice(choices));
+    }
+
+    let name = self.parse_name()?;
+
+    if Keyword::Bool == name.lexeme() {
+      Ok(ParameterType::Bool)
+    } else if Keyword::Int == name.lexeme() {
+      Ok(ParameterType::Int)
+    } else {
+      Err(name.error(CompileErrorKind::UnknownParameterType {
+        parameter_type: name.lexeme(),
+      }))
+    }
+  }
+
   /// Parse the body of a recipe
   fn parse_body(&mut self) -> CompileResult<'src, Vec<Line<'src>>> {
     let mut lines = Vec::new();
@@ -1103,9 +1285,14 @@ impl<'run, 'src> Parser<'run, 'src> {
   }
 
   /// Parse a setting
-  fn parse_set(&mut self) -> CompileResult<'src, Set<'src>> {
+  fn parse_set(&mut self, attributes: AttributeSet<'src>) -> CompileResult<'src, Item<'src>> {
     self.presume_keyword(Keyword::Set)?;
     let name = Name::from_identifier(self.presume(Identifier)?);
+
+    attributes.ensure_valid_attributes("Setting", *name, &[AttributeDiscriminant::Allow])?;
+
+    let allowed = attributes.allowed();
+
     let lexeme = name.lexeme();
     let Some(keyword) = Keyword::from_lexeme(lexeme) else {
       return Err(name.error(CompileErrorKind::UnknownSetting {
@@ -1130,18 +1317,23 @@ impl<'run, 'src> Parser<'run, 'src> {
       Keyword::PositionalArguments => Some(Setting::PositionalArguments(self.parse_set_bool()?)),
       Keyword::Quiet => Some(Setting::Quiet(self.parse_set_bool()?)),
       Keyword::Unstable => Some(Setting::Unstable(self.parse_set_bool()?)),
+      Keyword::VariadicLists => Some(Setting::VariadicLists(self.parse_set_bool()?)),
       Keyword::WindowsPowershell => Some(Setting::WindowsPowerShell(self.parse_set_bool()?)),
       _ => None,
     };
 
     if let Some(value) = set_bool {
-      return Ok(Set { name, value });
+      return Ok(Item::Set {
+        allowed,
+        set: Set { name, value },
+      });
     }
 
     self.expect(ColonEquals)?;
 
     let set_value = match keyword {
       Keyword::DotenvFilename => Some(Setting::DotenvFilename(self.parse_string_literal()?)),
+      Keyword::DotenvFiles => Some(Setting::DotenvFiles(self.parse_string_list()?)),
       Keyword::DotenvPath => Some(Setting::DotenvPath(self.parse_string_literal()?)),
       Keyword::ScriptInterpreter => Some(Setting::ScriptInterpreter(self.parse_interpreter()?)),
       Keyword::Shell => Some(Setting::Shell(self.parse_interpreter()?)),
@@ -1152,7 +1344,10 @@ impl<'run, 'src> Parser<'run, 'src> {
     };
 
     if let Some(value) = set_value {
-      return Ok(Set { name, value });
+      return Ok(Item::Set {
+        allowed,
+        set: Set { name, value },
+      });
     }
 
     Err(name.error(CompileErrorKind::UnknownSetting {
@@ -1183,6 +1378,25 @@ impl<'run, 'src> Parser<'run, 'src> {
     Ok(Interpreter { arguments, command })
   }
 
+  /// Parse list of string literals, i.e., `['.env', '.env.local']`
+  fn parse_string_list(&mut self) -> CompileResult<'src, Vec<StringLiteral<'src>>> {
+    self.expect(BracketL)?;
+
+    let mut list = Vec::new();
+
+    while !self.next_is(BracketR) {
+      list.push(self.parse_string_literal()?);
+
+      if !self.accepted(Comma)? {
+        break;
+      }
+    }
+
+    self.expect(BracketR)?;
+
+    Ok(list)
+  }
+
   /// Item attributes, i.e., `[macos]` or `[confirm: "warning!"]`
   fn parse_attributes(&mut self) -> CompileResult<'src, Option<(Token<'src>, AttributeSet<'src>)>> {
     let mut attributes = BTreeMap::new();
@@ -1197,6 +1411,7 @@ impl<'run, 'src> Parser<'run, 'src> {
         let name = self.parse_name()?;
 
         let mut arguments = Vec::new();
+        let mut value = None;
 
         if self.accepted(Colon)? {
           arguments.push(self.parse_string_literal()?);
@@ -1207,11 +1422,17 @@ impl<'run, 'src> Parser<'run, 'src> {
             if !self.accepted(Comma)? {
               break;
             }
+
+            // the second argument to `env` is an expression
+            if name.lexeme() == "env" {
+              value = Some(self.parse_expression()?);
+              break;
+            }
           }
           self.expect(ParenR)?;
         }
 
-        let attribute = Attribute::new(name, arguments)?;
+        let attribute = Attribute::new(name, arguments, value)?;
 
         let first = attributes.get(&attribute).or_else(|| {
           if attribute.repeatable() {
@@ -1430,6 +1651,24 @@ mod tests {
     tree: (justfile (assignment #export x "hello")),
   }
 
+  test! {
+    name: secret,
+    text: r#"secret x := "hello""#,
+    tree: (justfile (assignment #secret x "hello")),
+  }
+
+  test! {
+    name: export_secret,
+    text: r#"export secret x := "hello""#,
+    tree: (justfile (assignment #export #secret x "hello")),
+  }
+
+  test! {
+    name: secret_as_variable_name,
+    text: r#"export secret := "hello""#,
+    tree: (justfile (assignment #export secret "hello")),
+  }
+
   test! {
     name: export_equals,
     text: r#"export x := "hello""#,
@@ -1503,6 +1742,36 @@ mod tests {
     tree: (justfile (assignment x (call env_var_or_default y z))),
   }
 
+  test! {
+    name: call_user_defined,
+    text: "x := slug(y, z)",
+    tree: (justfile (assignment x (call slug y z))),
+  }
+
+  test! {
+    name: list,
+    text: "x := ['a', y, ['b']]",
+    tree: (justfile (assignment x (list "a" y (list "b")))),
+  }
+
+  test! {
+    name: list_empty,
+    text: "x := []",
+    tree: (justfile (assignment x (list))),
+  }
+
+  test! {
+    name: list_multi_line,
+    text: "x := [\n  'a',\n  'b',\n]",
+    tree: (justfile (assignment x (list "a" "b"))),
+  }
+
+  test! {
+    name: call_map,
+    text: "x := map(y, uppercase)",
+    tree: (justfile (assignment x (call map y uppercase))),
+  }
+
   test! {
     name: call_trailing_comma,
     text: "x := env_var(y,)",
@@ -1585,6 +1854,48 @@ mod tests {
     tree: (justfile (recipe foo (params +(bar ((+ baz bob)))))),
   }
 
+  test! {
+    name: recipe_parameter_type_bool,
+    text: "foo bar: bool:",
+    tree: (justfile (recipe foo (params (bar (type bool))))),
+  }
+
+  test! {
+    name: recipe_parameter_type_int_default,
+    text: r#"foo bar: int="1":"#,
+    tree: (justfile (recipe foo (params (bar (type int) "1")))),
+  }
+
+  test! {
+    name: recipe_parameter_type_choice,
+    text: r#"foo bar: ("a" | 'b') +baz: ("c"):"#,
+    tree: (justfile (recipe foo (params (bar (type ("a" "b"))) +(baz (type ("c")))))),
+  }
+
+  test! {
+    name: recipe_flag,
+    text: "foo --bar:",
+    tree: (justfile (recipe foo (params flag(bar)))),
+  }
+
+  test! {
+    name: recipe_option,
+    text: r#"foo --bar="baz":"#,
+    tree: (justfile (recipe foo (params option(bar "baz")))),
+  }
+
+  test! {
+    name: recipe_options_interleaved_with_positional,
+    text: r#"foo a --b c --d: int="1" +f:"#,
+    tree: (justfile (recipe foo (params (a) flag(b) (c) option(d (type int) "1") +(f)))),
+  }
+
+  test! {
+    name: recipe_parameter_colon_dependency,
+    text: "foo bar: int",
+    tree: (justfile (recipe foo (params (bar)) (deps int))),
+  }
+
   test! {
     name: recipe_dependency_single,
     text: "foo: bar",
@@ -2100,6 +2411,30 @@ mod tests {
     tree: (justfile (recipe a (params (b) (c)) (body ((b) " " (c))))),
   }
 
+  test! {
+    name: user_defined_function,
+    text: "fn slug(s) := lowercase(s)",
+    tree: (justfile (fn slug (s) (call lowercase s))),
+  }
+
+  test! {
+    name: user_defined_function_multiple_parameters,
+    text: "fn join3(a, b, c,) := a + b + c",
+    tree: (justfile (fn join3 (a b c) (+ a (+ b c)))),
+  }
+
+  test! {
+    name: user_defined_function_no_parameters,
+    text: "fn greeting() := 'hello'",
+    tree: (justfile (fn greeting () "hello")),
+  }
+
+  test! {
+    name: recipe_named_fn,
+    text: "fn:",
+    tree: (justfile (recipe fn)),
+  }
+
   test! {
     name: unary_functions,
     text: "
@@ -2307,6 +2642,18 @@ mod tests {
     tree: (justfile (set positional_arguments false)),
   }
 
+  test! {
+    name: set_dotenv_files,
+    text: "set dotenv-files := ['.env', \".env.local\",]",
+    tree: (justfile (set dotenv_files ".env" ".env.local")),
+  }
+
+  test! {
+    name: set_dotenv_files_empty,
+    text: "set dotenv-files := []",
+    tree: (justfile (set dotenv_files)),
+  }
+
   test! {
     name: set_shell_no_arguments,
     text: "set shell := ['tclsh']",
@@ -2343,6 +2690,18 @@ mod tests {
     tree: (justfile (set shell "bash" "-cu" "-l")),
   }
 
+  test! {
+    name: set_variadic_lists_implicit,
+    text: "set variadic-lists",
+    tree: (justfile (set variadic_lists true)),
+  }
+
+  test! {
+    name: set_variadic_lists_false,
+    text: "set variadic-lists := false",
+    tree: (justfile (set variadic_lists false)),
+  }
+
   test! {
     name: set_windows_powershell_implicit,
     text: "set windows-powershell",
@@ -2457,6 +2816,36 @@ mod tests {
     tree: (justfile (assignment foo (assert (if a != b c d) == "abc" "error"))),
   }
 
+  error! {
+    name:   recipe_parameter_type_unknown,
+    input:  "foo bar: float:",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  5,
+    kind:   UnknownParameterType { parameter_type: "float" },
+  }
+
+  error! {
+    name:   recipe_parameter_type_choice_unterminated,
+    input:  "foo bar: ('a' 'b'):",
+    offset: 14,
+    line:   0,
+    column: 14,
+    width:  3,
+    kind:   UnexpectedToken { expected: vec![Bar, ParenR], found: StringToken },
+  }
+
+  error! {
+    name:   recipe_option_after_variadic,
+    input:  "foo +bar --baz:",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  2,
+    kind:   UnexpectedToken { expected: vec![Colon, Equals], found: MinusMinus },
+  }
+
   error! {
     name:   alias_syntax_multiple_rhs,
     input:  "alias foo := bar baz",
@@ -2505,7 +2894,7 @@ mod tests {
     column: 5,
     width:  1,
     kind:   UnexpectedToken{
-      expected: vec![Asterisk, Colon, Dollar, Equals, Identifier, Plus],
+      expected: vec![Asterisk, Colon, Dollar, Equals, Identifier, MinusMinus, Plus],
       found:    Eol
     },
   }
@@ -2520,6 +2909,7 @@ mod tests {
     kind:   UnexpectedToken {
       expected: vec![
         Backtick,
+        BracketL,
         Identifier,
         ParenL,
         StringToken,
@@ -2538,6 +2928,7 @@ mod tests {
     kind:   UnexpectedToken {
       expected: vec![
         Backtick,
+        BracketL,
         Identifier,
         ParenL,
         StringToken,
@@ -2582,6 +2973,7 @@ mod tests {
     kind: UnexpectedToken{
       expected: vec![
         Backtick,
+        BracketL,
         Identifier,
         ParenL,
         ParenR,
@@ -2602,6 +2994,7 @@ mod tests {
     kind:   UnexpectedToken{
       expected: vec![
         Backtick,
+        BracketL,
         Identifier,
         ParenL,
         ParenR,
@@ -2640,7 +3033,7 @@ mod tests {
     column: 8,
     width:  0,
     kind:   UnexpectedToken {
-      expected: vec![Asterisk, Colon, Dollar, Equals, Identifier, Plus],
+      expected: vec![Asterisk, Colon, Dollar, Equals, Identifier, MinusMinus, Plus],
       found:    Eof
     },
   }
@@ -2795,33 +3188,13 @@ mod tests {
   }
 
   error! {
-    name:   unknown_function,
-    input:  "a := foo()",
+    name:   map_without_function_name,
+    input:  "x := map(y, 'uppercase')",
     offset: 5,
     line:   0,
     column: 5,
     width:  3,
-    kind:   UnknownFunction{function: "foo"},
-  }
-
-  error! {
-    name:   unknown_function_in_interpolation,
-    input:  "a:\n echo {{bar()}}",
-    offset: 11,
-    line:   1,
-    column: 8,
-    width:  3,
-    kind:   UnknownFunction{function: "bar"},
-  }
-
-  error! {
-    name:   unknown_function_in_default,
-    input:  "a f=baz():",
-    offset: 4,
-    line:   0,
-    column: 4,
-    width:  3,
-    kind:   UnknownFunction{function: "baz"},
+    kind:   ExpectedFunctionName{function: "map"},
   }
 
   error! {
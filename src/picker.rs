@@ -0,0 +1,367 @@
+use {
+  super::*,
+  std::io::{BufRead, BufReader, IsTerminal, Read},
+};
+
+/// Maximum number of list rows drawn at once
+const HEIGHT: usize = 12;
+
+#[derive(Debug, PartialEq)]
+enum Key {
+  Backspace,
+  Cancel,
+  Char(char),
+  Down,
+  Enter,
+  Tab,
+  Up,
+}
+
+/// A recipe in the picker list, under the group header `group`. Recipes in
+/// multiple groups have one entry in each.
+struct Entry<'src, 'run> {
+  group: Option<String>,
+  name: String,
+  recipe: &'run Recipe<'src>,
+}
+
+/// Built-in chooser, used by `--choose` when no chooser is configured and
+/// `fzf` is not installed
+///
+/// Keystrokes are read from stdin, and if stderr is a terminal, the recipes
+/// matching the query, under their group headers, and the documentation of
+/// the highlighted recipe are drawn to stderr.
+pub(crate) struct Picker<'src, 'run> {
+  color: Color,
+  cursor: usize,
+  draw: bool,
+  entries: Vec<Entry<'src, 'run>>,
+  query: String,
+  rendered: usize,
+  selected: BTreeSet<String>,
+}
+
+impl<'src, 'run> Picker<'src, 'run> {
+  pub(crate) fn new(config: &Config, recipes: &[&'run Recipe<'src>]) -> Self {
+    let mut entries = Vec::new();
+
+    for recipe in recipes {
+      if recipe.groups().is_empty() {
+        entries.push(Entry {
+          group: None,
+          name: recipe.spaced_namepath(),
+          recipe,
+        });
+      }
+    }
+
+    let groups = recipes
+      .iter()
+      .flat_map(|recipe| recipe.groups())
+      .collect::<BTreeSet<String>>();
+
+    for group in groups {
+      for recipe in recipes {
+        if recipe.groups().contains(&group) {
+          entries.push(Entry {
+            group: Some(group.clone()),
+            name: recipe.spaced_namepath(),
+            recipe,
+          });
+        }
+      }
+    }
+
+    Self {
+      color: config.color.stderr(),
+      cursor: 0,
+      draw: io::stderr().is_terminal(),
+      entries,
+      query: String::new(),
+      rendered: 0,
+      selected: BTreeSet::new(),
+    }
+  }
+
+  /// Entries matching the query
+  fn matches(&self) -> Vec<&Entry<'src, 'run>> {
+    self
+      .entries
+      .iter()
+      .filter(|entry| fuzzy_match(&self.query, &entry.name))
+      .collect()
+  }
+
+  /// Read keystrokes from `input` until a selection is made, returning the
+  /// selected recipes in list order, or `None` if the picker is cancelled
+  pub(crate) fn pick(
+    mut self,
+    input: &mut BufReader<impl Read>,
+  ) -> io::Result<Option<Vec<&'run Recipe<'src>>>> {
+    loop {
+      self.render()?;
+
+      match read_key(input)? {
+        Key::Backspace => {
+          self.query.pop();
+          self.cursor = 0;
+        }
+        Key::Cancel => {
+          self.clear()?;
+          return Ok(None);
+        }
+        Key::Char(c) => {
+          self.query.push(c);
+          self.cursor = 0;
+        }
+        Key::Down => self.cursor += 1,
+        Key::Enter => {
+          self.clear()?;
+          return Ok(self.selection());
+        }
+        Key::Tab => {
+          if let Some(entry) = self.matches().get(self.cursor) {
+            let name = entry.name.clone();
+            if !self.selected.remove(&name) {
+              self.selected.insert(name);
+            }
+          }
+          self.cursor += 1;
+        }
+        Key::Up => self.cursor = self.cursor.saturating_sub(1),
+      }
+
+      self.cursor = self.cursor.min(self.matches().len().saturating_sub(1));
+    }
+  }
+
+  /// The recipes which were selected with tab, or if there are none, the
+  /// highlighted recipe
+  fn selection(&self) -> Option<Vec<&'run Recipe<'src>>> {
+    let matches = self.matches();
+
+    if self.selected.is_empty() {
+      return matches.get(self.cursor).map(|entry| vec![entry.recipe]);
+    }
+
+    let mut recipes = Vec::new();
+
+    for entry in &self.entries {
+      if self.selected.contains(&entry.name) && !recipes.contains(&entry.recipe) {
+        recipes.push(entry.recipe);
+      }
+    }
+
+    Some(recipes)
+  }
+
+  fn clear(&mut self) -> io::Result<()> {
+    if self.rendered > 0 {
+      let mut stderr = io::stderr().lock();
+      write!(stderr, "\x1b[{}A\r\x1b[J", self.rendered)?;
+      stderr.flush()?;
+      self.rendered = 0;
+    }
+    Ok(())
+  }
+
+  fn render(&mut self) -> io::Result<()> {
+    if !self.draw {
+      return Ok(());
+    }
+
+    let matches = self.matches();
+
+    let mut rows = Vec::new();
+    let mut cursor_row = 0;
+    let mut group = None;
+
+    for (i, entry) in matches.iter().enumerate() {
+      if entry.group.as_ref() != group {
+        if let Some(name) = &entry.group {
+          rows.push(format!(
+            "  {}",
+            self.color.group().paint(&format!("[{name}]"))
+          ));
+        }
+        group = entry.group.as_ref();
+      }
+
+      if i == self.cursor {
+        cursor_row = rows.len();
+      }
+
+      rows.push(format!(
+        "{}{}{}",
+        if i == self.cursor { "> " } else { "  " },
+        if self.selected.contains(&entry.name) {
+          "* "
+        } else {
+          "  "
+        },
+        entry.name,
+      ));
+    }
+
+    let start = cursor_row.saturating_sub(HEIGHT - 1);
+
+    let mut lines = vec![format!("> {}", self.query)];
+
+    lines.extend(rows.into_iter().skip(start).take(HEIGHT));
+
+    if let Some(entry) = matches.get(self.cursor) {
+      lines.push(String::new());
+      lines.push(
+        RecipeSignature {
+          name: &entry.name,
+          recipe: entry.recipe,
+        }
+        .color_display(self.color)
+        .to_string(),
+      );
+      if let Some(doc) = entry.recipe.doc() {
+        for line in doc.lines() {
+          lines.push(self.color.doc().paint(&format!("# {line}")).to_string());
+        }
+      }
+    }
+
+    self.clear()?;
+
+    let mut stderr = io::stderr().lock();
+    for line in &lines {
+      writeln!(stderr, "{line}")?;
+    }
+    stderr.flush()?;
+
+    self.rendered = lines.len();
+
+    Ok(())
+  }
+}
+
+/// Whether the characters of `query` appear in `candidate` in order,
+/// ignoring case
+fn fuzzy_match(query: &str, candidate: &str) -> bool {
+  let mut candidate = candidate.chars().flat_map(char::to_lowercase);
+  query
+    .chars()
+    .flat_map(char::to_lowercase)
+    .all(|c| candidate.any(|candidate| candidate == c))
+}
+
+fn read_byte(input: &mut impl BufRead) -> io::Result<Option<u8>> {
+  let mut byte = [0];
+  loop {
+    match input.read(&mut byte) {
+      Ok(0) => return Ok(None),
+      Ok(_) => return Ok(Some(byte[0])),
+      Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+      Err(error) => return Err(error),
+    }
+  }
+}
+
+fn read_key(input: &mut BufReader<impl Read>) -> io::Result<Key> {
+  loop {
+    let Some(byte) = read_byte(input)? else {
+      return Ok(Key::Cancel);
+    };
+
+    let key = match byte {
+      // ctrl-c, ctrl-d, ctrl-g
+      3 | 4 | 7 => Key::Cancel,
+      b'\t' => Key::Tab,
+      b'\n' => Key::Enter,
+      // carriage returns are sent by terminals which don't translate them to
+      // newlines, and are followed by newlines in input from windows, which
+      // are consumed if they have already been read, so that they are not
+      // read as a second key, or as the first line of input after the picker
+      b'\r' => {
+        if input.buffer().first() == Some(&b'\n') {
+          input.consume(1);
+        }
+        Key::Enter
+      }
+      // ctrl-n
+      14 => Key::Down,
+      // ctrl-p
+      16 => Key::Up,
+      // escape, followed by `[` or `O` for arrow keys
+      0x1b => match read_byte(input)? {
+        Some(b'[' | b'O') => match read_byte(input)? {
+          Some(b'A') => Key::Up,
+          Some(b'B') => Key::Down,
+          _ => continue,
+        },
+        _ => Key::Cancel,
+      },
+      // backspace, ctrl-h
+      0x7f | 8 => Key::Backspace,
+      byte if byte < 0x20 => continue,
+      byte => {
+        let len = match byte {
+          0xf0.. => 4,
+          0xe0.. => 3,
+          0xc0.. => 2,
+          _ => 1,
+        };
+
+        let mut bytes = vec![byte];
+        for _ in 1..len {
+          match read_byte(input)? {
+            Some(byte) => bytes.push(byte),
+            None => return Ok(Key::Cancel),
+          }
+        }
+
+        match str::from_utf8(&bytes).ok().and_then(|s| s.chars().next()) {
+          Some(c) => Key::Char(c),
+          None => continue,
+        }
+      }
+    };
+
+    return Ok(key);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn fuzzy() {
+    assert!(fuzzy_match("", "foo"));
+    assert!(fuzzy_match("fo", "foo"));
+    assert!(fuzzy_match("bz", "bar baz"));
+    assert!(fuzzy_match("BB", "bar baz"));
+    assert!(!fuzzy_match("of", "foo"));
+    assert!(!fuzzy_match("fooo", "foo"));
+  }
+
+  #[test]
+  fn keys() {
+    #[track_caller]
+    fn case(input: &str, expected: &[Key]) {
+      let mut input = BufReader::new(input.as_bytes());
+      let mut actual = Vec::new();
+      while !input.fill_buf().unwrap().is_empty() {
+        actual.push(read_key(&mut input).unwrap());
+      }
+      assert_eq!(actual, expected);
+    }
+
+    case("aé", &[Key::Char('a'), Key::Char('é')]);
+    case("\t\n\r\n", &[Key::Tab, Key::Enter, Key::Enter]);
+    case("\r\r\n\n", &[Key::Enter, Key::Enter, Key::Enter]);
+    case("\x1b[A\x1b[B\x1bOB", &[Key::Up, Key::Down, Key::Down]);
+    case(
+      "\x10\x0e\x7f\x08",
+      &[Key::Up, Key::Down, Key::Backspace, Key::Backspace],
+    );
+    case("\x03", &[Key::Cancel]);
+    case("\x1bq", &[Key::Cancel]);
+    case("\x01x", &[Key::Char('x')]);
+  }
+}
@@ -0,0 +1,329 @@
+use super::*;
+
+/// A recipe in a plan
+struct Step<'src: 'run, 'run> {
+  depth: usize,
+  directory: PathBuf,
+  kind: StepKind,
+  note: Option<&'static str>,
+  order: Option<usize>,
+  parameters: Vec<(Option<&'src str>, String)>,
+  recipe: &'run Recipe<'src>,
+  shell: String,
+}
+
+#[derive(Clone, Copy)]
+enum StepKind {
+  Finally,
+  Invocation,
+  OnFailure,
+  Prior,
+  Subsequent,
+}
+
+/// The recipes that running a set of invocations would run, in order, printed
+/// by `--plan`
+///
+/// Recipes are visited in the order in which the scheduler would run them
+/// without `--jobs`, and are deduplicated with `Ran` in the same way, so
+/// priors which have already run are not run again, and subsequents and
+/// hooks are registered in a fresh `Ran`.
+pub(crate) struct Plan<'src: 'run, 'run> {
+  config: &'run Config,
+  dotenv: &'run BTreeMap<String, String>,
+  justfile: &'run Justfile<'src>,
+  order: usize,
+  scopes: &'run BTreeMap<String, (&'run Justfile<'src>, &'run Scope<'src, 'run>)>,
+  search: &'run Search,
+  steps: Vec<Step<'src, 'run>>,
+}
+
+impl<'src: 'run, 'run> Plan<'src, 'run> {
+  pub(crate) fn new(
+    config: &'run Config,
+    dotenv: &'run BTreeMap<String, String>,
+    invocations: Vec<(&'run Recipe<'src>, Arguments)>,
+    justfile: &'run Justfile<'src>,
+    scopes: &'run BTreeMap<String, (&'run Justfile<'src>, &'run Scope<'src, 'run>)>,
+    search: &'run Search,
+  ) -> RunResult<'src, Self> {
+    let mut plan = Self {
+      config,
+      dotenv,
+      justfile,
+      order: 0,
+      scopes,
+      search,
+      steps: Vec::new(),
+    };
+
+    let mut ran = Ran::default();
+
+    for (recipe, arguments) in invocations {
+      plan.visit(&mut ran, recipe, arguments, 0, StepKind::Invocation)?;
+    }
+
+    Ok(plan)
+  }
+
+  fn visit(
+    &mut self,
+    ran: &mut Ran,
+    recipe: &'run Recipe<'src>,
+    arguments: Arguments,
+    depth: usize,
+    kind: StepKind,
+  ) -> RunResult<'src> {
+    let (module, module_scope) = self
+      .scopes
+      .get(recipe.module_path())
+      .expect("failed to retrieve scope for module");
+
+    let context = ExecutionContext {
+      config: self.config,
+      dotenv: self.dotenv,
+      module,
+      recipe_failed: match kind {
+        StepKind::Finally => Some(false),
+        StepKind::OnFailure => Some(true),
+        StepKind::Invocation | StepKind::Prior | StepKind::Subsequent => None,
+      },
+      root: self.justfile,
+      search: self.search,
+    };
+
+    let mut step = Step {
+      depth,
+      directory: recipe
+        .working_directory(&context)
+        .unwrap_or_else(|| self.config.invocation_directory.clone()),
+      kind,
+      note: None,
+      order: None,
+      parameters: Vec::new(),
+      recipe,
+      shell: Self::shell(self.config, module, recipe),
+    };
+
+    if ran.get(recipe, &arguments).is_some() {
+      step.note = Some("already run");
+      step.parameters = recipe
+        .redact_arguments(&arguments)
+        .into_iter()
+        .map(|argument| (None, argument))
+        .collect();
+      self.steps.push(step);
+      return Ok(());
+    }
+
+    ran.insert(recipe, &arguments, 0);
+
+    let (outer, _positional) = Evaluator::evaluate_parameters(
+      &context,
+      !matches!(kind, StepKind::Invocation),
+      &arguments,
+      recipe,
+      module_scope,
+    )?;
+
+    step.parameters = recipe
+      .parameters
+      .iter()
+      .map(|parameter| {
+        let name = parameter.name.lexeme();
+        let binding = outer.binding(name).unwrap();
+        let value = if binding.secret {
+          secrets::REDACTED.into()
+        } else {
+          binding.value.to_string()
+        };
+        (Some(name), value)
+      })
+      .collect();
+
+    if let StepKind::OnFailure = kind {
+      step.note = Some("if failed");
+      self.steps.push(step);
+      return Ok(());
+    }
+
+    let index = self.steps.len();
+    self.steps.push(step);
+
+    for (prior, arguments) in self.evaluate_dependencies(&context, recipe.priors(), &outer)? {
+      self.visit(ran, prior, arguments, depth + 1, StepKind::Prior)?;
+    }
+
+    self.order += 1;
+    self.steps[index].order = Some(self.order);
+
+    for (kind, discriminant) in [
+      (StepKind::OnFailure, AttributeDiscriminant::OnFailure),
+      (StepKind::Finally, AttributeDiscriminant::Finally),
+    ] {
+      if let Some(Attribute::Finally(hooks) | Attribute::OnFailure(hooks)) =
+        recipe.attributes.get(discriminant)
+      {
+        let mut ran = Ran::default();
+        for hook in hooks {
+          let hook = module.recipes.get(hook.cooked.as_str()).unwrap().as_ref();
+          self.visit(&mut ran, hook, Arguments::default(), depth + 1, kind)?;
+        }
+      }
+    }
+
+    let mut ran = Ran::default();
+
+    for (subsequent, arguments) in
+      self.evaluate_dependencies(&context, recipe.subsequents(), &outer)?
+    {
+      self.visit(
+        &mut ran,
+        subsequent,
+        arguments,
+        depth + 1,
+        StepKind::Subsequent,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  fn evaluate_dependencies(
+    &self,
+    context: &ExecutionContext<'src, 'run>,
+    dependencies: &'run [Dependency<'src>],
+    outer: &Scope<'src, 'run>,
+  ) -> RunResult<'src, Vec<(&'run Recipe<'src>, Arguments)>> {
+    if self.config.no_dependencies {
+      return Ok(Vec::new());
+    }
+
+    let scope = outer.child();
+
+    let mut evaluator = Evaluator::new(context, true, &scope);
+
+    let mut evaluated = Vec::new();
+    for Dependency { recipe, arguments } in dependencies {
+      let mut evaluated_arguments = Arguments::default();
+      for (i, argument) in arguments.iter().enumerate() {
+        if evaluator.is_secret(argument) {
+          evaluated_arguments.secret.insert(i);
+        }
+        evaluated_arguments
+          .positional
+          .push(evaluator.evaluate_expression(argument)?);
+      }
+      evaluated.push((recipe.as_ref(), evaluated_arguments));
+    }
+
+    Ok(evaluated)
+  }
+
+  /// The command that the recipe's lines or script would be run with
+  fn shell(config: &Config, module: &Justfile, recipe: &Recipe) -> String {
+    if let Some(Attribute::Script(interpreter)) =
+      recipe.attributes.get(AttributeDiscriminant::Script)
+    {
+      let interpreter = interpreter
+        .as_ref()
+        .or(module.settings.script_interpreter.as_ref())
+        .unwrap_or_else(|| Interpreter::default_script_interpreter());
+
+      iter::once(&interpreter.command)
+        .chain(&interpreter.arguments)
+        .map(|argument| argument.cooked.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ")
+    } else if recipe.shebang {
+      match recipe.body.first().and_then(|line| line.fragments.first()) {
+        Some(Fragment::Text { token }) => token.lexeme().trim_end().into(),
+        _ => "#!".into(),
+      }
+    } else {
+      let (command, arguments) = module.settings.shell(config);
+      iter::once(command)
+        .chain(arguments)
+        .collect::<Vec<&str>>()
+        .join(" ")
+    }
+  }
+}
+
+impl Display for Plan<'_, '_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let labels = self
+      .steps
+      .iter()
+      .map(|step| {
+        let order = match step.order {
+          Some(order) => format!("{order}. "),
+          None => "-  ".into(),
+        };
+
+        let kind = match step.kind {
+          StepKind::Finally => "finally ",
+          StepKind::OnFailure => "on-failure ",
+          StepKind::Subsequent => "&& ",
+          StepKind::Invocation | StepKind::Prior => "",
+        };
+
+        let mut label = format!(
+          "{}{order}{kind}{}",
+          "  ".repeat(step.depth),
+          step.recipe.namepath(),
+        );
+
+        for (name, value) in &step.parameters {
+          label.push(' ');
+
+          if let Some(name) = name {
+            label.push_str(name);
+            label.push('=');
+          }
+
+          let value = if value.contains('\'') {
+            format!("{value:?}")
+          } else {
+            format!("'{value}'")
+          };
+
+          label.push_str(&value);
+        }
+
+        label
+      })
+      .collect::<Vec<String>>();
+
+    let width = self
+      .steps
+      .iter()
+      .zip(&labels)
+      .filter(|(step, _label)| step.note.is_none())
+      .map(|(_step, label)| label.chars().count())
+      .max()
+      .unwrap_or_default();
+
+    let shell_width = self
+      .steps
+      .iter()
+      .map(|step| step.shell.chars().count())
+      .max()
+      .unwrap_or_default();
+
+    for (step, label) in self.steps.iter().zip(&labels) {
+      if let Some(note) = step.note {
+        writeln!(f, "{label} ({note})")?;
+      } else {
+        writeln!(
+          f,
+          "{label:width$}  {:shell_width$}  {}",
+          step.shell,
+          step.directory.display(),
+        )?;
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -64,4 +64,45 @@ impl PlatformInterface for Platform {
 
     Ok(())
   }
+
+  fn with_raw_terminal<T>(f: impl FnOnce() -> T) -> io::Result<T> {
+    use {
+      nix::sys::termios::{self, LocalFlags, SetArg, SpecialCharacterIndices, Termios},
+      std::io::{IsTerminal, Stdin},
+    };
+
+    /// Restores the original terminal attributes when dropped, so that they
+    /// are restored even if `f` panics
+    struct Restore {
+      original: Termios,
+      stdin: Stdin,
+    }
+
+    impl Drop for Restore {
+      fn drop(&mut self) {
+        termios::tcsetattr(&self.stdin, SetArg::TCSANOW, &self.original).ok();
+      }
+    }
+
+    let stdin = io::stdin();
+
+    if !stdin.is_terminal() {
+      return Ok(f());
+    }
+
+    let original = termios::tcgetattr(&stdin)?;
+
+    let mut raw = original.clone();
+    raw
+      .local_flags
+      .remove(LocalFlags::ECHO | LocalFlags::ICANON | LocalFlags::ISIG);
+    raw.control_chars[SpecialCharacterIndices::VMIN as usize] = 1;
+    raw.control_chars[SpecialCharacterIndices::VTIME as usize] = 0;
+
+    termios::tcsetattr(&stdin, SetArg::TCSANOW, &raw)?;
+
+    let _restore = Restore { original, stdin };
+
+    Ok(f())
+  }
 }
@@ -82,4 +82,65 @@ impl PlatformInterface for Platform {
     ctrlc::set_handler(move || handler(Signal::Interrupt))
       .map_err(|source| Error::SignalHandlerInstall { source })
   }
+
+  fn with_raw_terminal<T>(f: impl FnOnce() -> T) -> io::Result<T> {
+    use windows_sys::Win32::{
+      Foundation::HANDLE,
+      System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, CONSOLE_MODE, ENABLE_ECHO_INPUT,
+        ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT, ENABLE_VIRTUAL_TERMINAL_INPUT, STD_INPUT_HANDLE,
+      },
+    };
+
+    /// Restores the original console mode when dropped, so that it is
+    /// restored even if `f` panics
+    struct Restore {
+      handle: HANDLE,
+      original: CONSOLE_MODE,
+    }
+
+    impl Drop for Restore {
+      fn drop(&mut self) {
+        // SAFETY:
+        //
+        // `handle` is the console input handle, for which `original` was
+        // read with `GetConsoleMode`.
+        unsafe {
+          SetConsoleMode(self.handle, self.original);
+        }
+      }
+    }
+
+    // SAFETY:
+    //
+    // `GetStdHandle` has no preconditions.
+    let handle = unsafe { GetStdHandle(STD_INPUT_HANDLE) };
+
+    let mut original = 0;
+
+    // SAFETY:
+    //
+    // `original` is a valid pointer for the duration of the call. If `handle`
+    // is not a console handle, `GetConsoleMode` fails and returns zero, in
+    // which case stdin is not a console.
+    if unsafe { GetConsoleMode(handle, &mut original) } == 0 {
+      return Ok(f());
+    }
+
+    // read keystrokes as they are typed, without echoing them, and read
+    // ctrl-c and arrow keys as input, like a unix terminal in raw mode
+    let raw = (original & !(ENABLE_ECHO_INPUT | ENABLE_LINE_INPUT | ENABLE_PROCESSED_INPUT))
+      | ENABLE_VIRTUAL_TERMINAL_INPUT;
+
+    // SAFETY:
+    //
+    // `handle` is a console handle, since `GetConsoleMode` succeeded.
+    if unsafe { SetConsoleMode(handle, raw) } == 0 {
+      return Err(io::Error::last_os_error());
+    }
+
+    let _restore = Restore { handle, original };
+
+    Ok(f())
+  }
 }
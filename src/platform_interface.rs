@@ -21,4 +21,8 @@ pub(crate) trait PlatformInterface {
 
   /// extract signal from process exit status
   fn signal_from_exit_status(exit_status: ExitStatus) -> Option<i32>;
+
+  /// run `f` with the terminal attached to stdin, if any, in raw mode, so
+  /// that keystrokes can be read as they are typed
+  fn with_raw_terminal<T>(f: impl FnOnce() -> T) -> io::Result<T>;
 }
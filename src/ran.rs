@@ -1,18 +1,21 @@
 use super::*;
 
+/// Nodes that have been registered with the scheduler, keyed by recipe
+/// namepath and arguments, so that each recipe runs at most once per set of
+/// arguments.
 #[derive(Default)]
-pub(crate) struct Ran(Mutex<BTreeMap<String, BTreeMap<Vec<String>, Arc<Mutex<bool>>>>>);
+pub(crate) struct Ran(BTreeMap<String, BTreeMap<Arguments, usize>>);
 
 impl Ran {
-  pub(crate) fn mutex(&self, recipe: &Recipe, arguments: &[String]) -> Arc<Mutex<bool>> {
+  pub(crate) fn get(&self, recipe: &Recipe, arguments: &Arguments) -> Option<usize> {
+    self.0.get(recipe.namepath())?.get(arguments).copied()
+  }
+
+  pub(crate) fn insert(&mut self, recipe: &Recipe, arguments: &Arguments, node: usize) {
     self
       .0
-      .lock()
-      .unwrap()
       .entry(recipe.namepath().into())
       .or_default()
-      .entry(arguments.into())
-      .or_default()
-      .clone()
+      .insert(arguments.clone(), node);
   }
 }
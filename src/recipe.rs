@@ -1,4 +1,7 @@
-use super::*;
+use {
+  super::*,
+  std::{io::IsTerminal, time::Instant},
+};
 
 /// Return a `Error::Signal` if the process was terminated by a signal,
 /// otherwise return an `Error::UnknownFailure`
@@ -57,18 +60,18 @@ impl<'src, D> Recipe<'src, D> {
   }
 
   pub(crate) fn min_arguments(&self) -> usize {
-    self
-      .parameters
-      .iter()
-      .filter(|p| p.default.is_none() && p.kind != ParameterKind::Star)
-      .count()
+    self.parameters.iter().filter(|p| p.is_required()).count()
   }
 
   pub(crate) fn max_arguments(&self) -> usize {
     if self.parameters.iter().any(|p| p.kind.is_variadic()) {
       usize::MAX - 1
     } else {
-      self.parameters.len()
+      self
+        .parameters
+        .iter()
+        .filter(|p| !p.kind.is_option())
+        .count()
     }
   }
 
@@ -76,6 +79,11 @@ impl<'src, D> Recipe<'src, D> {
     self.name.lexeme()
   }
 
+  /// Name used in `--events` output, the namepath of resolved recipes
+  fn event_name(&self) -> &str {
+    self.namepath.as_deref().unwrap_or(self.name())
+  }
+
   pub(crate) fn line_number(&self) -> usize {
     self.name.line
   }
@@ -114,6 +122,13 @@ impl<'src, D> Recipe<'src, D> {
     self.attributes.contains(AttributeDiscriminant::Parallel)
   }
 
+  /// Whether to prompt for arguments which are not given on the command
+  /// line, which is only done if stdin is a terminal
+  pub(crate) fn prompts(&self, config: &Config) -> bool {
+    (config.interactive || self.attributes.contains(AttributeDiscriminant::Prompt))
+      && io::stdin().is_terminal()
+  }
+
   pub(crate) fn is_public(&self) -> bool {
     !self.private && !self.attributes.contains(AttributeDiscriminant::Private)
   }
@@ -161,7 +176,7 @@ impl<'src, D> Recipe<'src, D> {
     }
   }
 
-  fn working_directory<'a>(&'a self, context: &'a ExecutionContext) -> Option<PathBuf> {
+  pub(crate) fn working_directory<'a>(&'a self, context: &'a ExecutionContext) -> Option<PathBuf> {
     if !self.change_directory() {
       return None;
     }
@@ -177,6 +192,191 @@ impl<'src, D> Recipe<'src, D> {
     Some(working_directory)
   }
 
+  fn freshness(
+    &self,
+    context: &ExecutionContext,
+    key: Option<&str>,
+  ) -> RunResult<'src, Option<Freshness>> {
+    if let Some(key) = key {
+      let previous = Cache::get(context.search, self)?;
+      return Ok(Some(Freshness::cached(
+        context.config.force,
+        previous.as_deref(),
+        key,
+      )));
+    }
+
+    let outputs = self.outputs();
+
+    if outputs.is_empty() {
+      return Ok(None);
+    }
+
+    Freshness::new(
+      &self.input_directory(context),
+      context.config.force,
+      &self.inputs(),
+      &outputs,
+    )
+    .map(Some)
+  }
+
+  /// Environment variables set by `[env]` attributes, and their values
+  pub(crate) fn env(&self) -> Vec<(&str, &Expression<'src>)> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| {
+        if let Attribute::Env(key, value) = attribute {
+          Some((key.cooked.as_str(), value))
+        } else {
+          None
+        }
+      })
+      .collect()
+  }
+
+  /// Check that `arguments` are accepted by the types of the parameters they
+  /// are passed to, so that invalid arguments are reported before any recipe
+  /// runs. Defaults are checked when they are evaluated.
+  pub(crate) fn check_arguments(&self, arguments: &Arguments) -> RunResult<'src> {
+    let mut rest = arguments.positional.as_slice();
+
+    for parameter in &self.parameters {
+      let value = if parameter.kind.is_option() {
+        let Some(value) = arguments.options.get(parameter.name.lexeme()) else {
+          continue;
+        };
+        value.clone().into()
+      } else if rest.is_empty() {
+        continue;
+      } else if parameter.kind.is_variadic() {
+        let value = Value::List(rest.to_vec());
+        rest = &[];
+        value
+      } else {
+        let value = rest[0].clone().into();
+        rest = &rest[1..];
+        value
+      };
+
+      parameter.check(self.name(), &value)?;
+    }
+
+    Ok(())
+  }
+
+  /// `arguments`, as they would be passed on the command line, with options
+  /// following positional arguments, and secret arguments and the arguments
+  /// of secret parameters replaced with `***`
+  pub(crate) fn redact_arguments(&self, arguments: &Arguments) -> Vec<String> {
+    let mut positional = self
+      .parameters
+      .iter()
+      .filter(|parameter| !parameter.kind.is_option());
+
+    let mut parameter = None;
+
+    let mut redacted = arguments
+      .positional
+      .iter()
+      .enumerate()
+      .map(|(i, argument)| {
+        parameter = positional
+          .next()
+          .or(parameter.filter(|parameter: &&Parameter| parameter.kind.is_variadic()));
+
+        if arguments.secret.contains(&i)
+          || parameter.is_some_and(|parameter| self.is_secret(parameter.name.lexeme()))
+        {
+          secrets::REDACTED.into()
+        } else {
+          argument.clone()
+        }
+      })
+      .collect::<Vec<String>>();
+
+    for (name, value) in &arguments.options {
+      if self.is_secret(name) {
+        redacted.push(format!("--{name}={}", secrets::REDACTED));
+      } else {
+        redacted.push(format!("--{name}={value}"));
+      }
+    }
+
+    redacted
+  }
+
+  /// Whether `parameter` is listed in the recipe's `[secret]` attribute
+  pub(crate) fn is_secret(&self, parameter: &str) -> bool {
+    match self.attributes.get(AttributeDiscriminant::Secret) {
+      Some(Attribute::Secret(secrets)) => secrets.iter().any(|secret| secret.cooked == parameter),
+      _ => false,
+    }
+  }
+
+  /// Names of the recipes to run after this recipe's body: its
+  /// `[on-failure]` hooks, if it failed, followed by its `[finally]` hooks
+  pub(crate) fn hooks(&self, failed: bool) -> Vec<&str> {
+    let mut hooks = Vec::new();
+
+    for discriminant in [
+      AttributeDiscriminant::OnFailure,
+      AttributeDiscriminant::Finally,
+    ] {
+      if discriminant == AttributeDiscriminant::OnFailure && !failed {
+        continue;
+      }
+
+      if let Some(Attribute::Finally(names) | Attribute::OnFailure(names)) =
+        self.attributes.get(discriminant)
+      {
+        hooks.extend(names.iter().map(|name| name.cooked.as_str()));
+      }
+    }
+
+    hooks
+  }
+
+  /// Directory that `[inputs]` and `[outputs]` are relative to
+  pub(crate) fn input_directory(&self, context: &ExecutionContext) -> PathBuf {
+    self
+      .working_directory(context)
+      .unwrap_or_else(|| context.config.invocation_directory.clone())
+  }
+
+  pub(crate) fn inputs(&self) -> Vec<&str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| {
+        if let Attribute::Inputs(inputs) = attribute {
+          Some(inputs)
+        } else {
+          None
+        }
+      })
+      .flatten()
+      .map(|input| input.cooked.as_str())
+      .collect()
+  }
+
+  pub(crate) fn outputs(&self) -> Vec<&str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| {
+        if let Attribute::Outputs(outputs) = attribute {
+          Some(outputs)
+        } else {
+          None
+        }
+      })
+      .flatten()
+      .map(|output| output.cooked.as_str())
+      .collect()
+  }
+
   fn no_quiet(&self) -> bool {
     self.attributes.contains(AttributeDiscriminant::NoQuiet)
   }
@@ -202,13 +402,80 @@ impl<'src, D> Recipe<'src, D> {
       }
     }
 
-    let evaluator = Evaluator::new(context, is_dependency, scope);
-
-    if self.is_script() {
-      self.run_script(context, scope, positional, evaluator)
+    let key = if self.attributes.contains(AttributeDiscriminant::Cache) {
+      Some(Cache::key(context, self, scope, is_dependency)?)
     } else {
-      self.run_linewise(context, scope, positional, evaluator)
+      None
+    };
+
+    if let Some(freshness) = self.freshness(context, key.as_deref())? {
+      if context.config.explain || context.config.dry_run || context.config.verbosity.loquacious() {
+        eprintln!("{prefix}#### Recipe `{}` {freshness}{suffix}", self.name);
+      }
+
+      if freshness.is_fresh() {
+        return Ok(());
+      }
+    }
+
+    let retry = self.retry();
+
+    let mut attempts = 1;
+
+    loop {
+      let evaluator = Evaluator::new(context, is_dependency, scope);
+
+      let result = if self.is_script() {
+        self.run_script(context, scope, positional, evaluator)
+      } else {
+        self.run_linewise(context, scope, positional, evaluator)
+      };
+
+      let Err(error) = result else {
+        break;
+      };
+
+      let interrupted =
+        matches!(error, Error::Interrupted { .. }) || SignalHandler::instance().caught().is_some();
+
+      let Some(retry) = retry.filter(|retry| attempts <= retry.count && !interrupted) else {
+        return Err(if attempts > 1 {
+          Error::RetriesExhausted {
+            attempts,
+            error: Box::new(error),
+          }
+        } else {
+          error
+        });
+      };
+
+      let delay = retry.delay(attempts);
+
+      attempts += 1;
+
+      if context.config.verbosity.loud() {
+        eprintln!(
+          "{prefix}===> {}. Retrying{} (attempt {attempts} of {})...{suffix}",
+          error.message(),
+          delay
+            .map(|delay| format!(" in {}", Timeout(delay)))
+            .unwrap_or_default(),
+          retry.count + 1,
+        );
+      }
+
+      if let Some(delay) = delay {
+        thread::sleep(delay);
+      }
     }
+
+    if let Some(key) = key {
+      if !context.config.dry_run {
+        Cache::insert(context.search, self, key)?;
+      }
+    }
+
+    Ok(())
   }
 
   fn run_linewise<'run>(
@@ -220,6 +487,11 @@ impl<'src, D> Recipe<'src, D> {
   ) -> RunResult<'src, ()> {
     let config = &context.config;
 
+    let start = Instant::now();
+    let timeout = self.timeout(config);
+
+    let env = self.evaluate_env(&mut evaluator)?;
+
     let mut lines = self.body.iter().peekable();
     let mut line_number = self.line_number() + 1;
     loop {
@@ -227,6 +499,7 @@ impl<'src, D> Recipe<'src, D> {
         return Ok(());
       }
       let mut evaluated = String::new();
+      let mut redacted = String::new();
       let mut continued = false;
       let quiet_line = lines.peek().is_some_and(|line| line.is_quiet());
       let infallible_line = lines.peek().is_some_and(|line| line.is_infallible());
@@ -241,11 +514,14 @@ impl<'src, D> Recipe<'src, D> {
         let line = lines.next().unwrap();
         line_number += 1;
         if !comment_line {
-          evaluated += &evaluator.evaluate_line(line, continued)?;
+          let (line, shown) = evaluator.evaluate_line(line, continued)?;
+          evaluated += &line;
+          redacted += &shown;
         }
         if line.is_continuation() && !comment_line {
           continued = true;
           evaluated.pop();
+          redacted.pop();
         } else {
           break;
         }
@@ -261,10 +537,29 @@ impl<'src, D> Recipe<'src, D> {
 
       command = &command[sigils..];
 
+      let shown = &redacted[sigils..];
+
       if command.is_empty() {
         continue;
       }
 
+      // don't start lines after a fatal signal was caught while exiting was
+      // deferred, or after the timeout has elapsed
+      if !config.dry_run {
+        if let Some(signal) = SignalHandler::instance().caught() {
+          return Err(Error::Interrupted { signal });
+        }
+      }
+
+      if let Some(timeout) = timeout {
+        if !config.dry_run && start.elapsed() >= timeout.0 {
+          return Err(Error::Timeout {
+            elapsed: start.elapsed(),
+            recipe: self.name(),
+          });
+        }
+      }
+
       if config.dry_run
         || config.verbosity.loquacious()
         || !((quiet_line ^ self.quiet)
@@ -289,7 +584,7 @@ impl<'src, D> Recipe<'src, D> {
           );
         }
 
-        eprintln!("{}", color.paint(command));
+        eprintln!("{}", color.paint(shown));
       }
 
       if config.dry_run {
@@ -318,10 +613,31 @@ impl<'src, D> Recipe<'src, D> {
         &context.module.settings,
         context.dotenv,
         scope,
+        &env,
         &context.module.unexports,
       );
 
-      let (result, caught) = cmd.status_guard();
+      Events::emit(&Event::LineStarted {
+        command: shown,
+        line: Some(line_number),
+        recipe: self.event_name(),
+      });
+
+      let (result, caught) = match timeout {
+        Some(timeout) => cmd.status_guard_timeout(timeout.0.saturating_sub(start.elapsed())),
+        None => cmd.status_guard(),
+      };
+
+      Events::emit(&Event::LineFinished {
+        code: result.as_ref().ok().and_then(ExitStatus::code),
+        error: result.as_ref().err().map(ToString::to_string),
+        line: Some(line_number),
+        recipe: self.event_name(),
+        signal: result
+          .as_ref()
+          .ok()
+          .and_then(|exit_status| Platform::signal_from_exit_status(*exit_status)),
+      });
 
       match result {
         Ok(exit_status) => {
@@ -343,9 +659,15 @@ impl<'src, D> Recipe<'src, D> {
           }
         }
         Err(io_error) => {
-          return Err(Error::Io {
-            recipe: self.name(),
-            io_error,
+          return Err(match timeout {
+            Some(_) if io_error.kind() == io::ErrorKind::TimedOut => Error::Timeout {
+              elapsed: start.elapsed(),
+              recipe: self.name(),
+            },
+            _ => Error::Io {
+              recipe: self.name(),
+              io_error,
+            },
           });
         }
       }
@@ -367,13 +689,21 @@ impl<'src, D> Recipe<'src, D> {
   ) -> RunResult<'src, ()> {
     let config = &context.config;
 
+    let start = Instant::now();
+    let timeout = self.timeout(config);
+
+    let env = self.evaluate_env(&mut evaluator)?;
+
     let mut evaluated_lines = Vec::new();
+    let mut redacted_lines = Vec::new();
     for line in &self.body {
-      evaluated_lines.push(evaluator.evaluate_line(line, false)?);
+      let (line, redacted) = evaluator.evaluate_line(line, false)?;
+      evaluated_lines.push(line);
+      redacted_lines.push(redacted);
     }
 
     if config.verbosity.loud() && (config.dry_run || self.quiet) {
-      for line in &evaluated_lines {
+      for line in &redacted_lines {
         eprintln!(
           "{}",
           config
@@ -389,6 +719,10 @@ impl<'src, D> Recipe<'src, D> {
       return Ok(());
     }
 
+    if let Some(signal) = SignalHandler::instance().caught() {
+      return Err(Error::Interrupted { signal });
+    }
+
     let executor = if let Some(Attribute::Script(interpreter)) =
       self.attributes.get(AttributeDiscriminant::Script)
     {
@@ -449,11 +783,32 @@ impl<'src, D> Recipe<'src, D> {
       &context.module.settings,
       context.dotenv,
       scope,
+      &env,
       &context.module.unexports,
     );
 
+    Events::emit(&Event::LineStarted {
+      command: &path.to_string_lossy(),
+      line: None,
+      recipe: self.event_name(),
+    });
+
     // run it!
-    let (result, caught) = command.status_guard();
+    let (result, caught) = match timeout {
+      Some(timeout) => command.status_guard_timeout(timeout.0.saturating_sub(start.elapsed())),
+      None => command.status_guard(),
+    };
+
+    Events::emit(&Event::LineFinished {
+      code: result.as_ref().ok().and_then(ExitStatus::code),
+      error: result.as_ref().err().map(ToString::to_string),
+      line: None,
+      recipe: self.event_name(),
+      signal: result
+        .as_ref()
+        .ok()
+        .and_then(|exit_status| Platform::signal_from_exit_status(*exit_status)),
+    });
 
     match result {
       Ok(exit_status) => exit_status.code().map_or_else(
@@ -471,7 +826,15 @@ impl<'src, D> Recipe<'src, D> {
           }
         },
       )?,
-      Err(io_error) => return Err(executor.error(io_error, self.name())),
+      Err(io_error) => {
+        return Err(match timeout {
+          Some(_) if io_error.kind() == io::ErrorKind::TimedOut => Error::Timeout {
+            elapsed: start.elapsed(),
+            recipe: self.name(),
+          },
+          _ => executor.error(io_error, self.name()),
+        })
+      }
     }
 
     if let Some(signal) = caught {
@@ -481,6 +844,40 @@ impl<'src, D> Recipe<'src, D> {
     Ok(())
   }
 
+  /// Evaluate the values of the recipe's `[env]` attributes
+  pub(crate) fn evaluate_env<'run>(
+    &self,
+    evaluator: &mut Evaluator<'src, 'run>,
+  ) -> RunResult<'src, BTreeMap<String, String>> {
+    self
+      .env()
+      .into_iter()
+      .map(|(key, value)| Ok((key.into(), evaluator.evaluate_expression(value)?)))
+      .collect()
+  }
+
+  fn retry(&self) -> Option<Retry> {
+    self
+      .attributes
+      .iter()
+      .find_map(|attribute| match attribute {
+        Attribute::Retry(_, retry) => Some(*retry),
+        _ => None,
+      })
+  }
+
+  /// The recipe's `[timeout]`, or the `--timeout` default
+  fn timeout(&self, config: &Config) -> Option<Timeout> {
+    self
+      .attributes
+      .iter()
+      .find_map(|attribute| match attribute {
+        Attribute::Timeout(_, timeout) => Some(*timeout),
+        _ => None,
+      })
+      .or(config.timeout)
+  }
+
   pub(crate) fn groups(&self) -> BTreeSet<String> {
     self
       .attributes
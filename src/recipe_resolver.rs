@@ -45,6 +45,12 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
         }
       }
 
+      for (_key, value) in recipe.env() {
+        for variable in value.variables() {
+          resolver.resolve_variable(&variable, &recipe.parameters)?;
+        }
+      }
+
       for line in &recipe.body {
         if line.is_comment() && settings.ignore_comments {
           continue;
@@ -58,11 +64,55 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
           }
         }
       }
+
+      resolver.resolve_hooks(&mut vec![recipe.name()], recipe)?;
     }
 
     Ok(resolver.resolved_recipes)
   }
 
+  fn resolve_hooks(
+    &self,
+    stack: &mut Vec<&'src str>,
+    recipe: &Recipe<'src>,
+  ) -> CompileResult<'src> {
+    for name in recipe.hooks(true) {
+      let Some(hook) = self.resolved_recipes.get(name) else {
+        return Err(recipe.name.error(UnknownHook {
+          hook: name.into(),
+          recipe: recipe.name(),
+        }));
+      };
+
+      if hook.min_arguments() > 0 {
+        return Err(recipe.name.error(HookRequiresArguments {
+          hook: hook.name(),
+          recipe: recipe.name(),
+        }));
+      }
+
+      if stack.contains(&hook.name()) {
+        stack.push(hook.name());
+        return Err(
+          recipe.name.error(CircularRecipeHook {
+            recipe: hook.name(),
+            circle: stack
+              .iter()
+              .skip_while(|name| **name != hook.name())
+              .copied()
+              .collect(),
+          }),
+        );
+      }
+
+      stack.push(hook.name());
+      self.resolve_hooks(stack, hook)?;
+      stack.pop();
+    }
+
+    Ok(())
+  }
+
   fn resolve_variable(
     &self,
     variable: &Token<'src>,
@@ -0,0 +1,135 @@
+use super::*;
+
+/// A recipe's usage screen, printed by `just RECIPE --help`, and when a recipe
+/// is called with the wrong number of arguments
+#[derive(Debug)]
+pub(crate) struct RecipeUsage<'src> {
+  pub(crate) aliases: Vec<&'src str>,
+  pub(crate) doc: Option<String>,
+  pub(crate) groups: Vec<String>,
+  pub(crate) parameters: Vec<Parameter<'src>>,
+  pub(crate) path: String,
+}
+
+impl<'src> RecipeUsage<'src> {
+  pub(crate) fn new(module: &Justfile<'src>, recipe: &Recipe<'src>) -> Self {
+    Self {
+      aliases: module
+        .aliases
+        .values()
+        .filter(|alias| alias.is_public() && alias.target.namepath() == recipe.namepath())
+        .map(|alias| alias.name.lexeme())
+        .collect(),
+      doc: recipe.doc().map(Into::into),
+      groups: recipe.groups().into_iter().collect(),
+      parameters: recipe.parameters.clone(),
+      path: recipe.spaced_namepath(),
+    }
+  }
+
+  fn describe(parameter: &Parameter) -> String {
+    let mut description = vec![match parameter.kind {
+      ParameterKind::Flag => "flag".into(),
+      ParameterKind::Option => "option".into(),
+      ParameterKind::Plus => "one or more".into(),
+      ParameterKind::Singular if parameter.is_required() => "required".into(),
+      ParameterKind::Singular => "optional".into(),
+      ParameterKind::Star => "zero or more".into(),
+    }];
+
+    if let Some(ty) = &parameter.ty {
+      description.push(format!("type: {ty}"));
+    }
+
+    if let Some(default) = &parameter.default {
+      description.push(format!("default: {default}"));
+    }
+
+    if parameter.export {
+      description.push("exported".into());
+    }
+
+    description.join(", ")
+  }
+}
+
+impl ColorDisplay for RecipeUsage<'_> {
+  fn fmt(&self, f: &mut Formatter, color: Color) -> fmt::Result {
+    if let Some(doc) = &self.doc {
+      writeln!(f, "{}", color.doc().paint(doc))?;
+      writeln!(f)?;
+    }
+
+    write!(
+      f,
+      "{}:\n    just {}",
+      color.message().paint("usage"),
+      self.path
+    )?;
+    for parameter in &self.parameters {
+      write!(f, " {}", parameter.color_display(color))?;
+    }
+
+    if !self.parameters.is_empty() {
+      write!(f, "\n\n{}:", color.message().paint("parameters"))?;
+
+      let names = self
+        .parameters
+        .iter()
+        .map(|parameter| {
+          format!(
+            "{}{}{}",
+            parameter.kind.prefix().unwrap_or_default(),
+            if parameter.export { "$" } else { "" },
+            parameter.name.lexeme(),
+          )
+        })
+        .collect::<Vec<String>>();
+
+      let width = names
+        .iter()
+        .map(|name| UnicodeWidthStr::width(name.as_str()))
+        .max()
+        .unwrap_or_default();
+
+      for (parameter, name) in self.parameters.iter().zip(&names) {
+        write!(
+          f,
+          "\n    {}{:padding$}  {}",
+          color.parameter().paint(name),
+          "",
+          Self::describe(parameter),
+          padding = width - UnicodeWidthStr::width(name.as_str()),
+        )?;
+      }
+    }
+
+    if !self.groups.is_empty() {
+      write!(
+        f,
+        "\n\n{}: {}",
+        color.message().paint(if self.groups.len() == 1 {
+          "group"
+        } else {
+          "groups"
+        }),
+        self.groups.join(", "),
+      )?;
+    }
+
+    if !self.aliases.is_empty() {
+      write!(
+        f,
+        "\n\n{}: {}",
+        color.message().paint(if self.aliases.len() == 1 {
+          "alias"
+        } else {
+          "aliases"
+        }),
+        self.aliases.join(", "),
+      )?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,162 @@
+use {
+  super::*,
+  std::{fmt::Write as _, time::Duration},
+};
+
+/// A report of executed recipes, written by `--report FORMAT=PATH`
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Report {
+  pub(crate) format: ReportFormat,
+  pub(crate) path: PathBuf,
+}
+
+/// A single recipe execution, reported as a test case
+pub(crate) struct ReportCase {
+  pub(crate) classname: String,
+  pub(crate) duration: Duration,
+  pub(crate) failure: Option<ReportFailure>,
+  pub(crate) name: String,
+}
+
+pub(crate) struct ReportFailure {
+  pub(crate) code: Option<i32>,
+  pub(crate) message: String,
+  pub(crate) signal: Option<i32>,
+}
+
+impl Report {
+  fn escape(s: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in s.chars() {
+      match c {
+        '&' => escaped.push_str("&amp;"),
+        '<' => escaped.push_str("&lt;"),
+        '>' => escaped.push_str("&gt;"),
+        '"' => escaped.push_str("&quot;"),
+        '\'' => escaped.push_str("&apos;"),
+        '\n' => escaped.push_str("&#10;"),
+        _ => escaped.push(c),
+      }
+    }
+
+    escaped
+  }
+
+  fn junit(cases: &[ReportCase]) -> String {
+    let tests = cases.len();
+    let failures = cases.iter().filter(|case| case.failure.is_some()).count();
+    let time = cases
+      .iter()
+      .map(|case| case.duration)
+      .sum::<Duration>()
+      .as_secs_f64();
+
+    let mut xml = String::new();
+
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+      xml,
+      r#"<testsuites tests="{tests}" failures="{failures}" time="{time:.3}">"#
+    )
+    .unwrap();
+    writeln!(
+      xml,
+      r#"  <testsuite name="just" tests="{tests}" failures="{failures}" time="{time:.3}">"#
+    )
+    .unwrap();
+
+    for case in cases {
+      write!(
+        xml,
+        r#"    <testcase classname="{}" name="{}" time="{:.3}""#,
+        Self::escape(&case.classname),
+        Self::escape(&case.name),
+        case.duration.as_secs_f64(),
+      )
+      .unwrap();
+
+      let Some(failure) = &case.failure else {
+        writeln!(xml, "/>").unwrap();
+        continue;
+      };
+
+      writeln!(xml, ">").unwrap();
+
+      let message = Self::escape(&failure.message);
+
+      match (failure.code, failure.signal) {
+        (_, Some(signal)) => writeln!(
+          xml,
+          r#"      <failure message="{message}" type="signal">signal {signal}</failure>"#
+        ),
+        (Some(code), None) => writeln!(
+          xml,
+          r#"      <failure message="{message}" type="exit-code">exit code {code}</failure>"#
+        ),
+        (None, None) => writeln!(xml, r#"      <failure message="{message}" type="error"/>"#),
+      }
+      .unwrap();
+
+      writeln!(xml, "    </testcase>").unwrap();
+    }
+
+    writeln!(xml, "  </testsuite>").unwrap();
+    writeln!(xml, "</testsuites>").unwrap();
+
+    xml
+  }
+
+  pub(crate) fn write(&self, cases: &[ReportCase]) -> RunResult<'static> {
+    let report = match self.format {
+      ReportFormat::Junit => Self::junit(cases),
+    };
+
+    fs::write(&self.path, report).map_err(|io_error| Error::ReportWrite {
+      path: self.path.clone(),
+      io_error,
+    })
+  }
+}
+
+impl FromStr for Report {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let Some((format, path)) = s.split_once('=') else {
+      return Err("expected `FORMAT=PATH`".into());
+    };
+
+    if path.is_empty() {
+      return Err("report path may not be empty".into());
+    }
+
+    Ok(Self {
+      format: <ReportFormat as ValueEnum>::from_str(format, false)
+        .map_err(|_| format!("unknown report format `{format}`"))?,
+      path: path.into(),
+    })
+  }
+}
+
+impl ReportFailure {
+  pub(crate) fn new(error: &Error) -> Self {
+    let cause = match error {
+      Error::RetriesExhausted { error, .. } => error,
+      _ => error,
+    };
+
+    let (code, signal) = match cause {
+      Error::Code { code, .. } => (Some(*code), None),
+      Error::Signal { signal, .. } => (None, Some(*signal)),
+      Error::Interrupted { signal } => (None, Some(signal.number())),
+      _ => (None, None),
+    };
+
+    Self {
+      code,
+      message: error.message(),
+      signal,
+    }
+  }
+}
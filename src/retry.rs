@@ -0,0 +1,85 @@
+use {super::*, std::time::Duration};
+
+/// How many times to re-run a failed recipe body, and how long to wait
+/// between attempts, from a `[retry(COUNT, DELAY, BACKOFF)]` attribute
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct Retry {
+  pub(crate) count: u32,
+  pub(crate) delay: Option<Duration>,
+  pub(crate) exponential: bool,
+}
+
+impl Retry {
+  /// Delay before `retry`, where the first retry is retry 1
+  pub(crate) fn delay(self, retry: u32) -> Option<Duration> {
+    let delay = self.delay?;
+
+    if !self.exponential {
+      return Some(delay);
+    }
+
+    Some(delay.saturating_mul(2u32.saturating_pow(retry.saturating_sub(1))))
+  }
+
+  pub(crate) fn parse(arguments: &[StringLiteral]) -> Result<Self, (String, String)> {
+    let invalid =
+      |argument: &StringLiteral, message: &str| Err((argument.cooked.clone(), message.to_owned()));
+
+    let mut arguments = arguments.iter();
+
+    let count = arguments.next().unwrap();
+
+    let count = match count.cooked.parse::<u32>() {
+      Ok(count) if count > 0 => count,
+      _ => return invalid(count, "expected a positive number of retries"),
+    };
+
+    let delay = match arguments.next() {
+      Some(delay) => match delay.cooked.parse::<Timeout>() {
+        Ok(Timeout(delay)) => Some(delay),
+        Err(message) => return invalid(delay, &message),
+      },
+      None => None,
+    };
+
+    let exponential = match arguments.next() {
+      Some(backoff) => match backoff.cooked.as_str() {
+        "exponential" => true,
+        "fixed" => false,
+        _ => return invalid(backoff, "expected `fixed` or `exponential` backoff"),
+      },
+      None => false,
+    };
+
+    Ok(Self {
+      count,
+      delay,
+      exponential,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn delay() {
+    let retry = Retry {
+      count: 3,
+      delay: Some(Duration::from_secs(1)),
+      exponential: false,
+    };
+
+    assert_eq!(retry.delay(3), Some(Duration::from_secs(1)));
+
+    let retry = Retry {
+      exponential: true,
+      ..retry
+    };
+
+    assert_eq!(retry.delay(1), Some(Duration::from_secs(1)));
+    assert_eq!(retry.delay(2), Some(Duration::from_secs(2)));
+    assert_eq!(retry.delay(3), Some(Duration::from_secs(4)));
+  }
+}
@@ -25,6 +25,7 @@ pub fn run(args: impl Iterator<Item = impl Into<OsString> + Clone>) -> Result<()
   config
     .and_then(|config| {
       SignalHandler::install(config.verbosity)?;
+      Events::install(&config)?;
       config.subcommand.execute(&config, &loader)
     })
     .map_err(|error| {
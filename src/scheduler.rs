@@ -0,0 +1,747 @@
+use {
+  super::*,
+  std::time::{Duration, Instant},
+};
+
+/// The scheduler runs command-line invocations, their priors, and their
+/// subsequents as a graph of nodes, each of which is a recipe invoked with a
+/// particular set of arguments.
+///
+/// Nodes are deduplicated with `Ran`, so a recipe invoked with the same
+/// arguments runs only once, no matter how many nodes depend on it.
+/// Subsequents are registered in a fresh `Ran`, so they always run after
+/// their parent's body, even if they already ran elsewhere.
+///
+/// The children of a node are started one after another, unless the node is
+/// a `[parallel]` recipe or `--jobs` is greater than one, in which case they
+/// are all started at once. Ready nodes are run by a pool of worker threads,
+/// of which there are at most `--jobs`, or as many as are needed if `--jobs`
+/// was not passed.
+///
+/// Once a node's body has run, whether or not it succeeded, its `[on-failure]`
+/// and `[finally]` hooks are registered in a fresh `Ran` and run before the
+/// result of the body is acted on, so that errors, including interruptions,
+/// propagate only after cleanup has finished. If the body failed, its error
+/// stops the run immediately, so that it is not replaced by errors from its
+/// hooks, but hooks, and their dependencies, are run before the run stops.
+///
+/// A fatal signal caught while a recipe with hooks is running does not cause
+/// `just` to exit immediately, but instead stops the run, so that the hooks
+/// are run first. Hooks ignore signals caught before they were queued, but
+/// are interrupted by signals caught afterwards.
+///
+/// With `--keep-going`, a node which fails is marked as failed instead of
+/// stopping the run, and its dependents are marked as failed and skipped
+/// once their other children have completed.
+pub(crate) struct Scheduler<'src: 'run, 'run> {
+  cases: Mutex<Vec<ReportCase>>,
+  condvar: Condvar,
+  config: &'run Config,
+  dotenv: &'run BTreeMap<String, String>,
+  justfile: &'run Justfile<'src>,
+  prompt: Mutex<()>,
+  scopes: &'run BTreeMap<String, (&'run Justfile<'src>, &'run Scope<'src, 'run>)>,
+  search: &'run Search,
+  state: Mutex<State<'src, 'run>>,
+}
+
+struct State<'src: 'run, 'run> {
+  completed: Vec<usize>,
+  error: Option<Error<'src>>,
+  failures: usize,
+  idle: usize,
+  nodes: Vec<Node<'src, 'run>>,
+  ran: Vec<Ran>,
+  ready: VecDeque<Task>,
+  workers: usize,
+}
+
+struct Node<'src: 'run, 'run> {
+  arguments: Arguments,
+  deferred: bool,
+  dependents: Vec<usize>,
+  done: bool,
+  failed: bool,
+  hook: bool,
+  ignored: usize,
+  is_dependency: bool,
+  outcome: Option<Outcome>,
+  pending: usize,
+  phase: Phase,
+  queue: VecDeque<(&'run Recipe<'src>, Arguments)>,
+  ran: usize,
+  recipe: Option<&'run Recipe<'src>>,
+  recipe_failed: Option<bool>,
+  result: Option<RunResult<'src, Vec<(&'run Recipe<'src>, Arguments)>>>,
+  scope: Option<Arc<(Scope<'src, 'run>, Vec<String>)>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Outcome {
+  Failed,
+  Passed,
+  Skipped,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Phase {
+  Hooks,
+  Priors,
+  Subsequents,
+}
+
+#[derive(Clone, Copy)]
+enum Task {
+  Run(usize),
+  Start(usize),
+}
+
+impl<'src: 'run, 'run> Scheduler<'src, 'run> {
+  pub(crate) fn run(
+    config: &'run Config,
+    dotenv: &'run BTreeMap<String, String>,
+    invocations: Vec<(&'run Recipe<'src>, Arguments)>,
+    justfile: &'run Justfile<'src>,
+    scopes: &'run BTreeMap<String, (&'run Justfile<'src>, &'run Scope<'src, 'run>)>,
+    search: &'run Search,
+  ) -> RunResult<'src> {
+    let root = Node {
+      arguments: Arguments::default(),
+      deferred: false,
+      dependents: Vec::new(),
+      done: false,
+      failed: false,
+      hook: false,
+      ignored: 0,
+      is_dependency: false,
+      outcome: None,
+      pending: 0,
+      phase: Phase::Subsequents,
+      queue: invocations.into(),
+      ran: 0,
+      recipe: None,
+      recipe_failed: None,
+      result: None,
+      scope: None,
+    };
+
+    let scheduler = Self {
+      cases: Mutex::new(Vec::new()),
+      condvar: Condvar::new(),
+      config,
+      dotenv,
+      justfile,
+      prompt: Mutex::new(()),
+      scopes,
+      search,
+      state: Mutex::new(State {
+        completed: Vec::new(),
+        error: None,
+        failures: 0,
+        idle: 0,
+        nodes: vec![root],
+        ran: vec![Ran::default()],
+        ready: VecDeque::new(),
+        workers: 1,
+      }),
+    };
+
+    scheduler.schedule(&mut scheduler.state.lock().unwrap(), 0);
+
+    thread::scope(|thread_scope| scheduler.work(thread_scope));
+
+    let report = match &config.report {
+      Some(report) => report.write(&scheduler.cases.into_inner().unwrap()),
+      None => Ok(()),
+    };
+
+    let mut state = scheduler.state.into_inner().unwrap();
+
+    for node in 0..state.nodes.len() {
+      Self::undefer_exit(&mut state, node);
+    }
+
+    if state.error.is_none() {
+      if let Some(signal) = SignalHandler::instance().caught() {
+        state.error = Some(Error::Interrupted { signal });
+      }
+    }
+
+    if config.keep_going && config.verbosity.loud() {
+      Self::summary(config, &state);
+    }
+
+    match state.error {
+      Some(error) => Err(error),
+      None if state.failures > 0 => Err(Error::RecipeFailures {
+        count: state.failures,
+      }),
+      None => report,
+    }
+  }
+
+  fn work<'scope>(&'scope self, thread_scope: &'scope thread::Scope<'scope, '_>) {
+    let mut state = self.state.lock().unwrap();
+
+    loop {
+      // stop the run if a fatal signal was caught while `just` deferred
+      // exiting, for example while a recipe with hooks was running
+      if state.error.is_none() {
+        if let Some(signal) = SignalHandler::instance().caught() {
+          state.error = Some(Error::Interrupted { signal });
+        }
+      }
+
+      if state.error.is_some() {
+        // hooks must run to completion, even once the run has failed
+        let State { nodes, ready, .. } = &mut *state;
+        ready.retain(|task| nodes[task.node()].hook);
+
+        if ready.is_empty() {
+          break;
+        }
+      }
+
+      if let Some(task) = state.ready.pop_front() {
+        let running = state.workers - state.idle;
+
+        if state.ready.len() > state.idle && running < self.max_workers() {
+          state.workers += 1;
+          thread_scope.spawn(move || self.work(thread_scope));
+        }
+
+        // defer exiting on fatal signals while a recipe with hooks is
+        // running, so that its hooks are run before exiting
+        if let Task::Run(node) = task {
+          if !state.nodes[node].recipe.unwrap().hooks(true).is_empty() {
+            state.nodes[node].deferred = true;
+            SignalHandler::instance().defer_exit();
+          }
+        }
+
+        let ignored = state.nodes[task.node()].ignored;
+
+        drop(state);
+
+        let result = SignalHandler::ignoring(ignored, || self.execute(task));
+
+        state = self.state.lock().unwrap();
+
+        let hooks = match task {
+          Task::Run(node) => self.hooks(state.nodes[node].recipe.unwrap(), result.is_err()),
+          Task::Start(_) => Vec::new(),
+        };
+
+        if hooks.is_empty() {
+          Self::undefer_exit(&mut state, task.node());
+          match result {
+            Ok(children) => self.finish(&mut state, task, children),
+            Err(error) => self.fail(&mut state, task.node(), error),
+          }
+        } else {
+          self.hook(&mut state, task.node(), hooks, result);
+        }
+
+        self.condvar.notify_all();
+      } else if state.idle + 1 == state.workers {
+        break;
+      } else {
+        state.idle += 1;
+        state = self.condvar.wait(state).unwrap();
+        state.idle -= 1;
+      }
+    }
+
+    state.workers -= 1;
+
+    self.condvar.notify_all();
+  }
+
+  fn max_workers(&self) -> usize {
+    self.config.jobs.map_or(usize::MAX, NonZeroUsize::get)
+  }
+
+  fn concurrent(&self, recipe: Option<&Recipe>) -> bool {
+    self.config.jobs.is_some_and(|jobs| jobs.get() > 1) || recipe.is_some_and(Recipe::is_parallel)
+  }
+
+  fn execute(&self, task: Task) -> RunResult<'src, Vec<(&'run Recipe<'src>, Arguments)>> {
+    let (recipe, arguments, is_dependency, recipe_failed, scope) = {
+      let state = self.state.lock().unwrap();
+      let node = &state.nodes[task.node()];
+      (
+        node.recipe.unwrap(),
+        node.arguments.clone(),
+        node.is_dependency,
+        node.recipe_failed,
+        node.scope.clone(),
+      )
+    };
+
+    let (module, module_scope) = self
+      .scopes
+      .get(recipe.module_path())
+      .expect("failed to retrieve scope for module");
+
+    let context = ExecutionContext {
+      config: self.config,
+      dotenv: self.dotenv,
+      module,
+      recipe_failed,
+      root: self.justfile,
+      search: self.search,
+    };
+
+    match task {
+      Task::Start(node) => {
+        if !self.config.yes {
+          let _prompt = self.prompt.lock().unwrap();
+
+          let confirm = recipe.attributes.contains(AttributeDiscriminant::Confirm);
+
+          if confirm {
+            Events::emit(&Event::ConfirmationRequested {
+              recipe: recipe.namepath(),
+            });
+          }
+
+          let confirmed = recipe.confirm()?;
+
+          if confirm {
+            Events::emit(&Event::ConfirmationAnswered {
+              confirmed,
+              recipe: recipe.namepath(),
+            });
+          }
+
+          if !confirmed {
+            return Err(Error::NotConfirmed {
+              recipe: recipe.name(),
+            });
+          }
+        }
+
+        let (outer, positional) = {
+          let _prompt = recipe
+            .prompts(self.config)
+            .then(|| self.prompt.lock().unwrap());
+
+          Evaluator::evaluate_parameters(&context, is_dependency, &arguments, recipe, module_scope)?
+        };
+
+        let priors = self.evaluate_dependencies(&context, recipe.priors(), &outer)?;
+
+        self.state.lock().unwrap().nodes[node].scope = Some(Arc::new((outer, positional)));
+
+        Ok(priors)
+      }
+      Task::Run(_) => {
+        let scope = scope.unwrap();
+        let (outer, positional) = scope.as_ref();
+
+        Events::emit(&Event::RecipeStarted {
+          arguments: &recipe.redact_arguments(&arguments),
+          recipe: recipe.namepath(),
+        });
+
+        let start = Instant::now();
+
+        let result = recipe.run(&context, &outer.child(), positional, is_dependency);
+
+        let duration = start.elapsed();
+
+        Events::emit(&Event::RecipeFinished {
+          arguments: &recipe.redact_arguments(&arguments),
+          duration: duration.as_secs_f64(),
+          error: result.as_ref().err().map(Error::message),
+          recipe: recipe.namepath(),
+        });
+
+        self.report(recipe, &arguments, duration, result.as_ref().err());
+
+        result?;
+
+        self.evaluate_dependencies(&context, recipe.subsequents(), outer)
+      }
+    }
+  }
+
+  fn evaluate_dependencies(
+    &self,
+    context: &ExecutionContext<'src, 'run>,
+    dependencies: &'run [Dependency<'src>],
+    outer: &Scope<'src, 'run>,
+  ) -> RunResult<'src, Vec<(&'run Recipe<'src>, Arguments)>> {
+    if self.config.no_dependencies {
+      return Ok(Vec::new());
+    }
+
+    let scope = outer.child();
+
+    let mut evaluator = Evaluator::new(context, true, &scope);
+
+    let mut evaluated = Vec::new();
+    for Dependency { recipe, arguments } in dependencies {
+      let mut evaluated_arguments = Arguments::default();
+      for (i, argument) in arguments.iter().enumerate() {
+        if evaluator.is_secret(argument) {
+          evaluated_arguments.secret.insert(i);
+        }
+        evaluated_arguments
+          .positional
+          .push(evaluator.evaluate_expression(argument)?);
+      }
+      evaluated.push((recipe.as_ref(), evaluated_arguments));
+    }
+
+    Ok(evaluated)
+  }
+
+  /// Stop the run with `error`, or with `--keep-going`, print `error` and mark
+  /// `node` as failed
+  fn fail(&self, state: &mut State<'src, 'run>, node: usize, error: Error<'src>) {
+    // nodes without a scope failed before running, for example because their
+    // arguments were invalid or they were not confirmed, and so have not been
+    // reported
+    if let (Some(recipe), None) = (state.nodes[node].recipe, &state.nodes[node].scope) {
+      self.report(
+        recipe,
+        &state.nodes[node].arguments,
+        Duration::ZERO,
+        Some(&error),
+      );
+    }
+
+    if self.config.keep_going {
+      if self.config.verbosity.loud() && error.print_message() {
+        eprintln!("{}", error.color_display(self.config.color.stderr()));
+      }
+
+      let current = &mut state.nodes[node];
+      current.failed = true;
+      current.outcome = Some(Outcome::Failed);
+      state.failures += 1;
+
+      self.schedule(state, node);
+    } else if state.error.is_none() {
+      state.error = Some(error);
+    }
+  }
+
+  fn finish(
+    &self,
+    state: &mut State<'src, 'run>,
+    task: Task,
+    children: Vec<(&'run Recipe<'src>, Arguments)>,
+  ) {
+    match task {
+      Task::Start(node) => {
+        state.nodes[node].queue = children.into();
+        self.schedule(state, node);
+      }
+      Task::Run(node) => {
+        state.ran.push(Ran::default());
+        let ran = state.ran.len() - 1;
+        let current = &mut state.nodes[node];
+        current.outcome = Some(Outcome::Passed);
+        current.phase = Phase::Subsequents;
+        current.queue = children.into();
+        current.ran = ran;
+        self.schedule(state, node);
+      }
+    }
+  }
+
+  /// Queue `node`'s hooks, deferring `result` until they have completed
+  fn hook(
+    &self,
+    state: &mut State<'src, 'run>,
+    node: usize,
+    hooks: Vec<(&'run Recipe<'src>, Arguments)>,
+    result: RunResult<'src, Vec<(&'run Recipe<'src>, Arguments)>>,
+  ) {
+    let outcome = if result.is_ok() {
+      Outcome::Passed
+    } else {
+      Outcome::Failed
+    };
+
+    // without `--keep-going`, stop the run now, so that the error is not
+    // replaced by any errors from the hooks
+    let result = match result {
+      Err(error) if !self.config.keep_going => {
+        if state.error.is_none() {
+          state.error = Some(error);
+        }
+        None
+      }
+      result => Some(result),
+    };
+
+    state.ran.push(Ran::default());
+    let ran = state.ran.len() - 1;
+    let current = &mut state.nodes[node];
+    // hooks must run to completion, even if the recipe was interrupted, so
+    // signals caught before they were queued are ignored while they run
+    current.ignored = SignalHandler::instance().caught_count();
+    current.outcome = Some(outcome);
+    current.phase = Phase::Hooks;
+    current.queue = hooks.into();
+    current.ran = ran;
+    current.result = result;
+    self.schedule(state, node);
+  }
+
+  /// Stop deferring exiting on fatal signals for `node`, once its hooks, if
+  /// any, have run
+  fn undefer_exit(state: &mut State<'src, 'run>, node: usize) {
+    if mem::take(&mut state.nodes[node].deferred) {
+      SignalHandler::instance().undefer_exit();
+    }
+  }
+
+  fn hooks(
+    &self,
+    recipe: &'run Recipe<'src>,
+    failed: bool,
+  ) -> Vec<(&'run Recipe<'src>, Arguments)> {
+    let (module, _scope) = self
+      .scopes
+      .get(recipe.module_path())
+      .expect("failed to retrieve scope for module");
+
+    recipe
+      .hooks(failed)
+      .into_iter()
+      .map(|name| {
+        (
+          module.recipes.get(name).unwrap().as_ref(),
+          Arguments::default(),
+        )
+      })
+      .collect()
+  }
+
+  /// Record a report case for `recipe`, if `--report` was passed
+  fn report(
+    &self,
+    recipe: &Recipe<'src>,
+    arguments: &Arguments,
+    duration: Duration,
+    error: Option<&Error<'src>>,
+  ) {
+    if self.config.report.is_none() {
+      return;
+    }
+
+    let mut name = recipe.name().to_owned();
+    for argument in recipe.redact_arguments(arguments) {
+      name.push(' ');
+      name.push_str(&argument);
+    }
+
+    let classname = match recipe.module_path() {
+      "" => "justfile",
+      module_path => module_path,
+    };
+
+    self.cases.lock().unwrap().push(ReportCase {
+      classname: classname.into(),
+      duration,
+      failure: error.map(ReportFailure::new),
+      name,
+    });
+  }
+
+  /// Register queued children of `node`, and advance `node` to its next phase
+  /// once all of its children have completed.
+  fn schedule(&self, state: &mut State<'src, 'run>, node: usize) {
+    let concurrent = self.concurrent(state.nodes[node].recipe);
+
+    loop {
+      if state.nodes[node].failed {
+        state.nodes[node].queue.clear();
+        break;
+      }
+
+      if state.nodes[node].pending > 0 && !concurrent {
+        break;
+      }
+
+      let Some((recipe, arguments)) = state.nodes[node].queue.pop_front() else {
+        break;
+      };
+
+      let ran = state.nodes[node].ran;
+
+      let recipe_failed = (state.nodes[node].phase == Phase::Hooks)
+        .then(|| state.nodes[node].outcome == Some(Outcome::Failed));
+
+      let child = state.ran[ran].get(recipe, &arguments);
+
+      if child.is_some() {
+        Events::emit(&Event::RecipeSkipped {
+          arguments: &recipe.redact_arguments(&arguments),
+          recipe: recipe.namepath(),
+        });
+      }
+
+      match child {
+        Some(child) if state.nodes[child].done => {
+          if state.nodes[child].failed && node != 0 {
+            state.nodes[node].failed = true;
+          }
+        }
+        Some(child) => {
+          state.nodes[child].dependents.push(node);
+          state.nodes[node].pending += 1;
+        }
+        None => {
+          Events::emit(&Event::RecipeQueued {
+            arguments: &recipe.redact_arguments(&arguments),
+            dependency: node != 0,
+            recipe: recipe.namepath(),
+          });
+
+          let child = state.nodes.len();
+          state.ran[ran].insert(recipe, &arguments, child);
+          state.nodes.push(Node {
+            arguments,
+            deferred: false,
+            dependents: vec![node],
+            done: false,
+            failed: false,
+            hook: state.nodes[node].hook || state.nodes[node].phase == Phase::Hooks,
+            ignored: state.nodes[node].ignored,
+            is_dependency: node != 0,
+            outcome: None,
+            pending: 0,
+            phase: Phase::Priors,
+            queue: VecDeque::new(),
+            ran,
+            recipe: Some(recipe),
+            recipe_failed,
+            result: None,
+            scope: None,
+          });
+          state.nodes[node].pending += 1;
+          state.ready.push_back(Task::Start(child));
+        }
+      }
+    }
+
+    let current = &mut state.nodes[node];
+
+    if current.pending > 0 || !current.queue.is_empty() {
+      return;
+    }
+
+    if current.phase == Phase::Priors && !current.failed {
+      state.ready.push_back(Task::Run(node));
+      return;
+    }
+
+    if current.phase == Phase::Hooks {
+      Self::undefer_exit(state, node);
+    }
+
+    let current = &mut state.nodes[node];
+
+    let failed = current.failed;
+
+    match current.result.take() {
+      Some(Ok(children)) if !failed => {
+        self.finish(state, Task::Run(node), children);
+        return;
+      }
+      Some(Ok(_)) => current.outcome = Some(Outcome::Passed),
+      Some(Err(error)) => {
+        self.fail(state, node, error);
+        return;
+      }
+      None => {}
+    }
+
+    let current = &mut state.nodes[node];
+
+    current.done = true;
+    current.outcome.get_or_insert(Outcome::Skipped);
+
+    if node != 0 {
+      state.completed.push(node);
+    }
+
+    for dependent in mem::take(&mut current.dependents) {
+      state.nodes[dependent].pending -= 1;
+      if failed && dependent != 0 {
+        state.nodes[dependent].failed = true;
+      }
+      self.schedule(state, dependent);
+    }
+  }
+
+  /// Print the outcome of each recipe in the order they completed, for
+  /// `--keep-going`
+  fn summary(config: &Config, state: &State) {
+    let color = config.color.stderr();
+
+    let recipes = state
+      .completed
+      .iter()
+      .map(|&node| {
+        let node = &state.nodes[node];
+        let recipe = node.recipe.unwrap();
+        let mut name = recipe.namepath().to_owned();
+        for argument in recipe.redact_arguments(&node.arguments) {
+          name.push(' ');
+          name.push_str(&argument);
+        }
+        (name, node.outcome.unwrap_or(Outcome::Skipped))
+      })
+      .collect::<Vec<(String, Outcome)>>();
+
+    let count = |outcome| {
+      recipes
+        .iter()
+        .filter(|(_, other)| *other == outcome)
+        .count()
+    };
+
+    let banner = color.banner();
+
+    eprintln!(
+      "{}===> {} passed, {} failed, {} skipped{}",
+      banner.prefix(),
+      count(Outcome::Passed),
+      count(Outcome::Failed),
+      count(Outcome::Skipped),
+      banner.suffix(),
+    );
+
+    let width = recipes
+      .iter()
+      .map(|(name, _)| UnicodeWidthStr::width(name.as_str()))
+      .max()
+      .unwrap_or_default();
+
+    for (name, outcome) in &recipes {
+      let padding = " ".repeat(width - UnicodeWidthStr::width(name.as_str()));
+
+      let outcome = match outcome {
+        Outcome::Failed => color.error().paint("failed"),
+        Outcome::Passed => color.paint("passed"),
+        Outcome::Skipped => color.warning().paint("skipped"),
+      };
+
+      eprintln!("{name}{padding}  {outcome}");
+    }
+  }
+}
+
+impl Task {
+  fn node(self) -> usize {
+    match self {
+      Self::Run(node) | Self::Start(node) => node,
+    }
+  }
+}
@@ -2,7 +2,7 @@ use super::*;
 
 #[derive(Debug)]
 pub(crate) struct Scope<'src: 'run, 'run> {
-  bindings: Table<'src, Binding<'src, String>>,
+  bindings: Table<'src, Binding<'src>>,
   parent: Option<&'run Self>,
 }
 
@@ -37,6 +37,7 @@ impl<'src, 'run> Scope<'src, 'run> {
           },
         },
         private: false,
+        secret: false,
         value: (*value).into(),
       });
     }
@@ -52,15 +53,19 @@ impl<'src, 'run> Scope<'src, 'run> {
     self.bindings.contains_key(name)
   }
 
-  pub(crate) fn value(&self, name: &str) -> Option<&str> {
+  pub(crate) fn binding(&self, name: &str) -> Option<&Binding<'src>> {
     if let Some(binding) = self.bindings.get(name) {
-      Some(binding.value.as_ref())
+      Some(binding)
     } else {
-      self.parent?.value(name)
+      self.parent?.binding(name)
     }
   }
 
-  pub(crate) fn bindings(&self) -> impl Iterator<Item = &Binding<String>> {
+  pub(crate) fn value(&self, name: &str) -> Option<&Value> {
+    self.binding(name).map(|binding| &binding.value)
+  }
+
+  pub(crate) fn bindings(&self) -> impl Iterator<Item = &Binding<'src>> {
     self.bindings.values()
   }
 
@@ -0,0 +1,2 @@
+/// Replacement for the values of `secret` assignments and parameters in output
+pub(crate) const REDACTED: &str = "***";
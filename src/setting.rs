@@ -5,6 +5,7 @@ pub(crate) enum Setting<'src> {
   AllowDuplicateRecipes(bool),
   AllowDuplicateVariables(bool),
   DotenvFilename(StringLiteral<'src>),
+  DotenvFiles(Vec<StringLiteral<'src>>),
   DotenvLoad(bool),
   DotenvOverride(bool),
   DotenvPath(StringLiteral<'src>),
@@ -19,6 +20,7 @@ pub(crate) enum Setting<'src> {
   Shell(Interpreter<'src>),
   Tempdir(StringLiteral<'src>),
   Unstable(bool),
+  VariadicLists(bool),
   WindowsPowerShell(bool),
   WindowsShell(Interpreter<'src>),
   WorkingDirectory(StringLiteral<'src>),
@@ -39,7 +41,18 @@ impl Display for Setting<'_> {
       | Self::PositionalArguments(value)
       | Self::Quiet(value)
       | Self::Unstable(value)
+      | Self::VariadicLists(value)
       | Self::WindowsPowerShell(value) => write!(f, "{value}"),
+      Self::DotenvFiles(files) => {
+        write!(f, "[")?;
+        for (i, file) in files.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{file}")?;
+        }
+        write!(f, "]")
+      }
       Self::ScriptInterpreter(shell) | Self::Shell(shell) | Self::WindowsShell(shell) => {
         write!(f, "[{shell}]")
       }
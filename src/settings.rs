@@ -10,6 +10,7 @@ pub(crate) struct Settings<'src> {
   pub(crate) allow_duplicate_recipes: bool,
   pub(crate) allow_duplicate_variables: bool,
   pub(crate) dotenv_filename: Option<String>,
+  pub(crate) dotenv_files: Option<Vec<String>>,
   pub(crate) dotenv_load: bool,
   pub(crate) dotenv_override: bool,
   pub(crate) dotenv_path: Option<PathBuf>,
@@ -25,6 +26,7 @@ pub(crate) struct Settings<'src> {
   pub(crate) shell: Option<Interpreter<'src>>,
   pub(crate) tempdir: Option<String>,
   pub(crate) unstable: bool,
+  pub(crate) variadic_lists: bool,
   pub(crate) windows_powershell: bool,
   pub(crate) windows_shell: Option<Interpreter<'src>>,
   pub(crate) working_directory: Option<PathBuf>,
@@ -45,6 +47,9 @@ impl<'src> Settings<'src> {
         Setting::DotenvFilename(filename) => {
           settings.dotenv_filename = Some(filename.cooked);
         }
+        Setting::DotenvFiles(files) => {
+          settings.dotenv_files = Some(files.into_iter().map(|file| file.cooked).collect());
+        }
         Setting::DotenvLoad(dotenv_load) => {
           settings.dotenv_load = dotenv_load;
         }
@@ -84,6 +89,9 @@ impl<'src> Settings<'src> {
         Setting::Unstable(unstable) => {
           settings.unstable = unstable;
         }
+        Setting::VariadicLists(variadic_lists) => {
+          settings.variadic_lists = variadic_lists;
+        }
         Setting::WindowsPowerShell(windows_powershell) => {
           settings.windows_powershell = windows_powershell;
         }
@@ -113,39 +121,56 @@ impl<'src> Settings<'src> {
   }
 
   pub(crate) fn shell<'a>(&'a self, config: &'a Config) -> (&'a str, Vec<&'a str>) {
-    match (&config.shell, &config.shell_args) {
-      (Some(shell), Some(shell_args)) => (shell, shell_args.iter().map(String::as_ref).collect()),
-      (Some(shell), None) => (shell, DEFAULT_SHELL_ARGS.to_vec()),
-      (None, Some(shell_args)) => (
-        DEFAULT_SHELL,
-        shell_args.iter().map(String::as_ref).collect(),
-      ),
-      (None, None) => {
-        if let (true, Some(shell)) = (cfg!(windows), &self.windows_shell) {
-          (
-            shell.command.cooked.as_ref(),
-            shell
-              .arguments
-              .iter()
-              .map(|argument| argument.cooked.as_ref())
-              .collect(),
-          )
-        } else if cfg!(windows) && self.windows_powershell {
-          (WINDOWS_POWERSHELL_SHELL, WINDOWS_POWERSHELL_ARGS.to_vec())
-        } else if let Some(shell) = &self.shell {
-          (
-            shell.command.cooked.as_ref(),
-            shell
-              .arguments
-              .iter()
-              .map(|argument| argument.cooked.as_ref())
-              .collect(),
-          )
-        } else {
-          (DEFAULT_SHELL, DEFAULT_SHELL_ARGS.to_vec())
-        }
-      }
+    if let Some(shell) = Self::configured_shell(config.shell.as_ref(), config.shell_args.as_ref()) {
+      return shell;
     }
+
+    if let (true, Some(shell)) = (cfg!(windows), &self.windows_shell) {
+      (
+        shell.command.cooked.as_ref(),
+        shell
+          .arguments
+          .iter()
+          .map(|argument| argument.cooked.as_ref())
+          .collect(),
+      )
+    } else if cfg!(windows) && self.windows_powershell {
+      (WINDOWS_POWERSHELL_SHELL, WINDOWS_POWERSHELL_ARGS.to_vec())
+    } else if let Some(shell) = &self.shell {
+      (
+        shell.command.cooked.as_ref(),
+        shell
+          .arguments
+          .iter()
+          .map(|argument| argument.cooked.as_ref())
+          .collect(),
+      )
+    } else {
+      Self::configured_shell(
+        config.default_shell.as_ref(),
+        config.default_shell_args.as_ref(),
+      )
+      .unwrap_or_else(|| (DEFAULT_SHELL, DEFAULT_SHELL_ARGS.to_vec()))
+    }
+  }
+
+  /// The shell given by `shell` and `shell_args`, if either is set, using the
+  /// default for the other
+  fn configured_shell<'a>(
+    shell: Option<&'a String>,
+    shell_args: Option<&'a Vec<String>>,
+  ) -> Option<(&'a str, Vec<&'a str>)> {
+    if shell.is_none() && shell_args.is_none() {
+      return None;
+    }
+
+    Some((
+      shell.map_or(DEFAULT_SHELL, String::as_str),
+      shell_args.map_or_else(
+        || DEFAULT_SHELL_ARGS.to_vec(),
+        |shell_args| shell_args.iter().map(String::as_ref).collect(),
+      ),
+    ))
   }
 }
 
@@ -1,18 +1,63 @@
-use super::*;
+use {super::*, std::cell::Cell};
+
+thread_local! {
+  /// The number of fatal signals caught before the hooks running on this
+  /// thread were queued, which are ignored by `SignalHandler::caught`
+  static IGNORED: Cell<usize> = const { Cell::new(0) };
+}
 
 pub(crate) struct SignalHandler {
-  caught: Option<Signal>,
+  caught: Vec<Signal>,
   children: BTreeMap<i32, Command>,
+  deferred: usize,
   initialized: bool,
+  terminated: BTreeSet<i32>,
   verbosity: Verbosity,
 }
 
 impl SignalHandler {
+  /// The first fatal signal caught, ignoring those caught before the hooks
+  /// running on this thread were queued
+  pub(crate) fn caught(&self) -> Option<Signal> {
+    self.caught.get(IGNORED.get()).copied()
+  }
+
+  /// The number of fatal signals caught so far
+  pub(crate) fn caught_count(&self) -> usize {
+    self.caught.len()
+  }
+
+  /// Defer exiting on receipt of a fatal signal while no child process is
+  /// running, until `SignalHandler::undefer_exit` is called, so that hooks
+  /// can be run before exiting
+  pub(crate) fn defer_exit(&mut self) {
+    self.deferred += 1;
+  }
+
+  pub(crate) fn undefer_exit(&mut self) {
+    self.deferred -= 1;
+  }
+
+  /// Call `f`, ignoring the first `ignored` fatal signals caught, so that
+  /// hooks run to completion, even if they were queued because a recipe was
+  /// interrupted
+  pub(crate) fn ignoring<T>(ignored: usize, f: impl FnOnce() -> T) -> T {
+    let previous = IGNORED.replace(ignored);
+    let result = f();
+    IGNORED.set(previous);
+    result
+  }
+
   pub(crate) fn install(verbosity: Verbosity) -> RunResult<'static> {
     let mut instance = Self::instance();
     instance.verbosity = verbosity;
     if !instance.initialized {
-      Platform::install_signal_handler(|signal| Self::instance().handle(signal))?;
+      Platform::install_signal_handler(|signal| {
+        Events::emit(&Event::SignalCaught {
+          signal: signal.to_string(),
+        });
+        Self::instance().handle(signal);
+      })?;
       instance.initialized = true;
     }
     Ok(())
@@ -38,30 +83,34 @@ impl SignalHandler {
 
   const fn new() -> Self {
     Self {
-      caught: None,
+      caught: Vec::new(),
       children: BTreeMap::new(),
+      deferred: 0,
       initialized: false,
+      terminated: BTreeSet::new(),
       verbosity: Verbosity::default(),
     }
   }
 
   fn handle(&mut self, signal: Signal) {
     if signal.is_fatal() {
-      if self.children.is_empty() {
+      if self.children.is_empty() && self.deferred == 0 {
         process::exit(signal.code());
       }
 
-      if self.caught.is_none() {
-        self.caught = Some(signal);
-      }
+      self.caught.push(signal);
     }
 
     match signal {
       // SIGHUP, SIGINT, and SIGQUIT are normally sent on terminal close,
       // ctrl-c, and ctrl-\, respectively, and are sent to all processes in the
-      // foreground process group. this includes child processes, so we ignore
-      // the signal and wait for them to exit
-      Signal::Hangup | Signal::Interrupt | Signal::Quit => {}
+      // foreground process group. child processes which have been handed the
+      // terminal receive them directly, and `just` does not
+      //
+      // when child processes are in their own process groups but have not
+      // been handed the terminal, they are not in the foreground process group,
+      // so we forward the signal to them
+      Signal::Hangup | Signal::Interrupt | Signal::Quit => self.forward(signal),
       #[cfg(any(
         target_os = "dragonfly",
         target_os = "freebsd",
@@ -92,29 +141,99 @@ impl SignalHandler {
       }
       // SIGTERM is the default signal sent by kill. forward it to child
       // processes and wait for them to exit
-      Signal::Terminate =>
-      {
-        #[cfg(not(windows))]
-        for &child in self.children.keys() {
-          if self.verbosity.loquacious() {
-            eprintln!("just: sending SIGTERM to child process {child}");
-          }
-          nix::sys::signal::kill(
-            nix::unistd::Pid::from_raw(child),
-            Some(Signal::Terminate.into()),
-          )
-          .ok();
-        }
+      Signal::Terminate => self.forward(signal),
+    }
+  }
+
+  /// Forward `signal` to the process groups of child processes
+  #[cfg_attr(windows, allow(unused_variables))]
+  fn forward(&self, signal: Signal) {
+    #[cfg(not(windows))]
+    for &child in self.children.keys() {
+      if self.verbosity.loquacious() {
+        eprintln!("just: sending {signal} to child process {child}");
       }
+
+      nix::sys::signal::killpg(nix::unistd::Pid::from_raw(child), Some(signal.into())).ok();
+    }
+  }
+
+  /// Send SIGKILL to the process groups of all running child processes, for
+  /// example if they are still running after a grace period following
+  /// `SignalHandler::terminate`
+  pub(crate) fn kill(&self) {
+    #[cfg(not(windows))]
+    for &child in self.children.keys() {
+      nix::sys::signal::killpg(
+        nix::unistd::Pid::from_raw(child),
+        nix::sys::signal::Signal::SIGKILL,
+      )
+      .ok();
+    }
+  }
+
+  /// Send SIGTERM to the process groups of running child processes which have
+  /// not already been sent SIGTERM, for example before restarting them
+  pub(crate) fn terminate(&mut self) {
+    #[cfg(not(windows))]
+    for &child in self.children.keys() {
+      if !self.terminated.insert(child) {
+        continue;
+      }
+
+      if self.verbosity.loquacious() {
+        eprintln!(
+          "just: sending {} to child process {child}",
+          Signal::Terminate
+        );
+      }
+
+      nix::sys::signal::killpg(
+        nix::unistd::Pid::from_raw(child),
+        nix::sys::signal::Signal::from(Signal::Terminate),
+      )
+      .ok();
     }
   }
 
+  /// Spawn `command` in its own process group, so that signals sent to it
+  /// reach its descendants as well, and wait for it with `f`
+  ///
+  /// Processes outside of the terminal's foreground process group are stopped
+  /// if they read from the terminal, so if `just` is in the foreground process
+  /// group, the terminal is handed over to the child's process group until it
+  /// exits.
   pub(crate) fn spawn<T>(
     mut command: Command,
     f: impl Fn(process::Child) -> io::Result<T>,
   ) -> (io::Result<T>, Option<Signal>) {
     let mut instance = Self::instance();
 
+    #[cfg(unix)]
+    let foreground = Self::foreground();
+
+    #[cfg(unix)]
+    {
+      use std::os::unix::process::CommandExt;
+
+      command.process_group(0);
+
+      if foreground {
+        // SAFETY:
+        //
+        // The closure only calls async-signal-safe functions. The terminal is
+        // handed over before the child is executed, so that it cannot read
+        // from the terminal before it is in the foreground process group.
+        unsafe {
+          command.pre_exec(|| {
+            use std::os::fd::BorrowedFd;
+            Self::set_foreground(BorrowedFd::borrow_raw(0), nix::unistd::getpid()).ok();
+            Ok(())
+          });
+        }
+      }
+    }
+
     let child = match command.spawn() {
       Err(err) => return (Err(err), None),
       Ok(child) => child,
@@ -130,6 +249,15 @@ impl SignalHandler {
       Ok(pid) => pid,
     };
 
+    // the child's stdin may not be the terminal, so hand the terminal over
+    // here as well, in case it opens it directly
+    #[cfg(unix)]
+    if foreground {
+      Self::set_foreground(io::stdin(), nix::unistd::Pid::from_raw(pid)).ok();
+      let id = child.id();
+      thread::spawn(move || Self::monitor(id));
+    }
+
     instance.children.insert(pid, command);
 
     drop(instance);
@@ -138,8 +266,103 @@ impl SignalHandler {
 
     let mut instance = Self::instance();
 
+    #[cfg(unix)]
+    if foreground {
+      Self::set_foreground(io::stdin(), nix::unistd::getpgrp()).ok();
+    }
+
     instance.children.remove(&pid);
+    instance.terminated.remove(&pid);
+
+    (result, instance.caught())
+  }
+
+  /// Wait for child `id`, which has been handed the terminal, to be stopped,
+  /// for example by ctrl-z
+  ///
+  /// `just` is not in the foreground process group, so it doesn't receive
+  /// SIGTSTP itself. Instead, when the child is stopped, `just` takes the
+  /// terminal back and stops itself, so that the shell regains control, and
+  /// when `just` is continued, it hands the terminal back to the child and
+  /// continues it.
+  #[cfg(unix)]
+  fn monitor(id: u32) {
+    use nix::{
+      sys::signal::{self, Signal},
+      unistd::{self, Pid},
+    };
+
+    let stopped = |flags| loop {
+      // SAFETY:
+      //
+      // `info` is valid for the duration of the call, and an all-zero
+      // `siginfo_t` is valid.
+      let (result, info) = unsafe {
+        let mut info = mem::zeroed::<libc::siginfo_t>();
+        let result = libc::waitid(libc::P_PID, id, &mut info, flags);
+        (result, info)
+      };
+
+      if result == 0 {
+        return info.si_code == libc::CLD_STOPPED;
+      }
+
+      if nix::errno::Errno::last() != nix::errno::Errno::EINTR {
+        return false;
+      }
+    };
+
+    let Ok(pid) = i32::try_from(id).map(Pid::from_raw) else {
+      return;
+    };
+
+    // wait without reaping the child, which is left to `SignalHandler::spawn`
+    while stopped(libc::WEXITED | libc::WSTOPPED | libc::WNOWAIT) {
+      // consume the stop, so that it is not reported again
+      stopped(libc::WSTOPPED);
+
+      Self::set_foreground(io::stdin(), unistd::getpgrp()).ok();
+
+      signal::raise(Signal::SIGTSTP).ok();
+
+      if Self::foreground() {
+        Self::set_foreground(io::stdin(), pid).ok();
+      }
+
+      signal::killpg(pid, Signal::SIGCONT).ok();
+    }
+  }
+
+  /// Whether stdin is a terminal and `just` is in its foreground process group
+  #[cfg(unix)]
+  fn foreground() -> bool {
+    use std::io::IsTerminal;
+
+    let stdin = io::stdin();
+
+    stdin.is_terminal()
+      && nix::unistd::tcgetpgrp(stdin).is_ok_and(|pgrp| pgrp == nix::unistd::getpgrp())
+  }
+
+  /// Make `pgrp` the foreground process group of `terminal`
+  ///
+  /// Processes outside of the foreground process group are sent SIGTTOU if
+  /// they call `tcsetpgrp`, so it is blocked for the duration of the call.
+  #[cfg(unix)]
+  fn set_foreground(terminal: impl std::os::fd::AsFd, pgrp: nix::unistd::Pid) -> nix::Result<()> {
+    use nix::sys::signal::{pthread_sigmask, SigSet, SigmaskHow};
+
+    let mut ttou = SigSet::empty();
+    ttou.add(nix::sys::signal::Signal::SIGTTOU);
+
+    let mut original = SigSet::empty();
+
+    pthread_sigmask(SigmaskHow::SIG_BLOCK, Some(&ttou), Some(&mut original))?;
+
+    let result = nix::unistd::tcsetpgrp(terminal, pgrp);
+
+    pthread_sigmask(SigmaskHow::SIG_SETMASK, Some(&original), None)?;
 
-    (result, instance.caught)
+    result
   }
 }
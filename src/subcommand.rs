@@ -1,4 +1,12 @@
-use {super::*, clap_mangen::Man};
+use {
+  super::*,
+  clap_mangen::Man,
+  std::{
+    io::BufRead,
+    sync::atomic::{self, AtomicBool},
+    time::{Duration, Instant},
+  },
+};
 
 pub const INIT_JUSTFILE: &str = "\
 # https://just.systems
@@ -14,6 +22,7 @@ fn backtick_re() -> &'static Regex {
 
 #[derive(PartialEq, Clone, Debug)]
 pub(crate) enum Subcommand {
+  CacheClean,
   Changelog,
   Choose {
     overrides: BTreeMap<String, String>,
@@ -24,6 +33,9 @@ pub(crate) enum Subcommand {
     binary: OsString,
     overrides: BTreeMap<String, String>,
   },
+  Complete {
+    words: Vec<String>,
+  },
   Completions {
     shell: completions::Shell,
   },
@@ -34,11 +46,17 @@ pub(crate) enum Subcommand {
     variable: Option<String>,
   },
   Format,
+  Graph {
+    overrides: BTreeMap<String, String>,
+    recipes: Vec<String>,
+  },
   Groups,
   Init,
+  Lint,
   List {
     path: ModulePath,
   },
+  Lsp,
   Man,
   Request {
     request: Request,
@@ -50,11 +68,22 @@ pub(crate) enum Subcommand {
   Show {
     path: ModulePath,
   },
+  ShowConfig {
+    values: Vec<(String, ConfigValue, ConfigSource)>,
+  },
   Summary,
   Variables,
+  Watch {
+    arguments: Vec<String>,
+    overrides: BTreeMap<String, String>,
+  },
 }
 
 impl Subcommand {
+  /// How long `--watch` waits for recipes to exit after sending SIGTERM,
+  /// before sending SIGKILL
+  const WATCH_GRACE: Duration = Duration::from_secs(2);
+
   pub(crate) fn execute<'src>(&self, config: &Config, loader: &'src Loader) -> RunResult<'src> {
     use Subcommand::*;
 
@@ -68,8 +97,13 @@ impl Subcommand {
         return Ok(());
       }
       Init => return Self::init(config),
+      Lsp => return super::Lsp::run(),
       Man => return Self::man(),
       Request { request } => return Self::request(request),
+      ShowConfig { values } => {
+        Self::show_config(values);
+        return Ok(());
+      }
       _ => {}
     }
 
@@ -79,8 +113,14 @@ impl Subcommand {
       &config.search_config,
     )?;
 
-    if let Edit = self {
-      return Self::edit(&search);
+    match self {
+      CacheClean => return Cache::clean(&search),
+      Edit => return Self::edit(&search),
+      Watch {
+        arguments,
+        overrides,
+      } => return Self::watch(config, loader, &search, arguments, overrides),
+      _ => {}
     }
 
     let compilation = Self::compile(config, loader, &search)?;
@@ -90,12 +130,14 @@ impl Subcommand {
       Choose { overrides, chooser } => {
         Self::choose(config, justfile, &search, overrides, chooser.as_deref())?;
       }
-      Command { overrides, .. } | Evaluate { overrides, .. } => {
+      Command { overrides, .. } | Evaluate { overrides, .. } | Graph { overrides, .. } => {
         justfile.run(config, &search, overrides, &[])?;
       }
+      Complete { words } => Self::complete(config, justfile, words),
       Dump => Self::dump(config, compilation)?,
       Format => Self::format(config, &search, compilation)?,
       Groups => Self::groups(config, justfile),
+      Lint => Self::lint(config, justfile)?,
       List { path } => Self::list(config, justfile, path)?,
       Run {
         arguments,
@@ -104,7 +146,16 @@ impl Subcommand {
       Show { path } => Self::show(config, justfile, path)?,
       Summary => Self::summary(config, justfile),
       Variables => Self::variables(justfile),
-      Changelog | Completions { .. } | Edit | Init | Man | Request { .. } => unreachable!(),
+      CacheClean
+      | Changelog
+      | Completions { .. }
+      | Edit
+      | Init
+      | Lsp
+      | Man
+      | Request { .. }
+      | ShowConfig { .. }
+      | Watch { .. } => unreachable!(),
     }
 
     Ok(())
@@ -176,6 +227,164 @@ impl Subcommand {
     }
   }
 
+  fn watch<'src>(
+    config: &Config,
+    loader: &'src Loader,
+    search: &Search,
+    arguments: &[String],
+    overrides: &BTreeMap<String, String>,
+  ) -> RunResult<'src> {
+    let print = |error: Error| {
+      if !config.verbosity.quiet() && error.print_message() {
+        eprintln!("{}", error.color_display(config.color.stderr()));
+      }
+    };
+
+    let root = search.justfile.parent().unwrap();
+
+    let mut paths = vec![search.justfile.clone()];
+
+    loop {
+      let mut patterns = Vec::new();
+
+      let compilation = Self::compile(config, loader, search)
+        .and_then(|compilation| {
+          paths = compilation
+            .justfile
+            .loaded
+            .iter()
+            .map(|path| root.join(path))
+            .collect();
+          patterns = Self::watch_patterns(config, &compilation.justfile, arguments)?;
+          Ok(compilation)
+        })
+        .map_err(print)
+        .ok();
+
+      let watcher = Watcher::new(paths.clone(), patterns);
+
+      let snapshot = watcher.snapshot();
+
+      let interrupted = Mutex::new(None);
+
+      let restarting = AtomicBool::new(false);
+
+      thread::scope(|scope| {
+        let handle = compilation.as_ref().map(|compilation| {
+          scope.spawn(|| {
+            let result = compilation
+              .justfile
+              .run(config, search, overrides, arguments);
+
+            let caught = SignalHandler::instance().caught();
+
+            if let Some(signal) = caught {
+              *interrupted.lock().unwrap() =
+                Some(result.err().unwrap_or(Error::Interrupted { signal }));
+            } else if let Err(error) = result {
+              if !restarting.load(atomic::Ordering::Relaxed) {
+                print(error);
+              }
+            }
+          })
+        });
+
+        if !watcher.wait(&snapshot, || interrupted.lock().unwrap().is_some()) {
+          return;
+        }
+
+        restarting.store(true, atomic::Ordering::Relaxed);
+
+        // send SIGTERM to running recipes, and to any which start before the
+        // run is stopped, followed by SIGKILL after a grace period
+        if let Some(handle) = handle {
+          let start = Instant::now();
+          while !handle.is_finished() {
+            if start.elapsed() < Self::WATCH_GRACE {
+              SignalHandler::instance().terminate();
+            } else {
+              SignalHandler::instance().kill();
+            }
+            thread::sleep(Duration::from_millis(10));
+          }
+        }
+      });
+
+      if let Some(error) = interrupted.into_inner().unwrap() {
+        return Err(error);
+      }
+
+      if config.verbosity.loquacious() {
+        eprintln!("===> Change detected, restarting...");
+      }
+    }
+  }
+
+  /// Glob patterns from the `[watch]` attributes of invoked recipes and their
+  /// dependencies, relative to the directory of the module they appear in
+  fn watch_patterns<'src>(
+    config: &Config,
+    justfile: &Justfile<'src>,
+    arguments: &[String],
+  ) -> RunResult<'src, Vec<String>> {
+    let arguments = arguments.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    let mut stack = Vec::new();
+
+    for group in ArgumentParser::parse_arguments(config, justfile, &arguments)? {
+      let (name, path) = group.path.split_last().unwrap();
+
+      let mut module = justfile;
+      for name in path {
+        module = module.modules.get(name).unwrap();
+      }
+
+      stack.push(module.get_recipe(name).unwrap());
+    }
+
+    let mut patterns = Vec::new();
+    let mut seen = HashSet::new();
+
+    while let Some(recipe) = stack.pop() {
+      if !seen.insert(recipe.namepath()) {
+        continue;
+      }
+
+      let mut module = justfile;
+      for name in recipe
+        .module_path()
+        .split("::")
+        .filter(|name| !name.is_empty())
+      {
+        module = module.modules.get(name).unwrap();
+      }
+
+      let directory = glob::Pattern::escape(&module.working_directory.to_string_lossy());
+
+      for attribute in &recipe.attributes {
+        if let Attribute::Watch(watch) = attribute {
+          for pattern in watch {
+            patterns.push(
+              Path::new(&directory)
+                .join(&pattern.cooked)
+                .to_string_lossy()
+                .into_owned(),
+            );
+          }
+        }
+      }
+
+      stack.extend(
+        recipe
+          .dependencies
+          .iter()
+          .map(|dependency| dependency.recipe.as_ref()),
+      );
+    }
+
+    Ok(patterns)
+  }
+
   fn compile<'src>(
     config: &Config,
     loader: &'src Loader,
@@ -185,12 +394,6 @@ impl Subcommand {
 
     compilation.justfile.check_unstable(config)?;
 
-    if config.verbosity.loud() {
-      for warning in &compilation.justfile.warnings {
-        eprintln!("{}", warning.color_display(config.color.stderr()));
-      }
-    }
-
     Ok(compilation)
   }
 
@@ -205,6 +408,12 @@ impl Subcommand {
     overrides: &BTreeMap<String, String>,
     chooser: Option<&str>,
   ) -> RunResult<'src> {
+    let builtin = chooser.is_none()
+      && which(&search.working_directory, "fzf")
+        .ok()
+        .flatten()
+        .is_none();
+
     let mut recipes = Vec::<&Recipe>::new();
     let mut stack = vec![justfile];
     while let Some(module) = stack.pop() {
@@ -212,7 +421,7 @@ impl Subcommand {
         module
           .public_recipes(config)
           .iter()
-          .filter(|recipe| recipe.min_arguments() == 0),
+          .filter(|recipe| builtin || recipe.min_arguments() == 0),
       );
       stack.extend(module.modules.values());
     }
@@ -221,6 +430,10 @@ impl Subcommand {
       return Err(Error::NoChoosableRecipes);
     }
 
+    if builtin {
+      return Self::choose_builtin(config, justfile, search, overrides, &recipes);
+    }
+
     let chooser = if let Some(chooser) = chooser {
       OsString::from(chooser)
     } else {
@@ -286,6 +499,161 @@ impl Subcommand {
     justfile.run(config, search, overrides, &recipes)
   }
 
+  /// Choose recipes with the built-in picker, and prompt for the arguments
+  /// of their required parameters
+  fn choose_builtin<'src>(
+    config: &Config,
+    justfile: &Justfile<'src>,
+    search: &Search,
+    overrides: &BTreeMap<String, String>,
+    recipes: &[&Recipe],
+  ) -> RunResult<'src> {
+    let mut stdin = io::BufReader::new(io::stdin());
+
+    let chosen = Platform::with_raw_terminal(|| Picker::new(config, recipes).pick(&mut stdin))
+      .and_then(|result| result)
+      .map_err(|io_error| Error::ChooserIo { io_error })?
+      .ok_or(Error::ChooserCancelled)?;
+
+    let mut groups = Vec::new();
+
+    for recipe in chosen {
+      let mut arguments = Vec::new();
+
+      for parameter in recipe
+        .parameters
+        .iter()
+        .filter(|parameter| parameter.is_required())
+      {
+        eprint!(
+          "{} {}: ",
+          recipe.spaced_namepath(),
+          parameter.color_display(config.color.stderr()),
+        );
+
+        let mut line = String::new();
+        if stdin
+          .read_line(&mut line)
+          .map_err(|io_error| Error::ChooserIo { io_error })?
+          == 0
+        {
+          return Err(Error::ChooserCancelled);
+        }
+
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        if parameter.kind.is_variadic() {
+          arguments.extend(line.split_whitespace().map(str::to_owned));
+        } else {
+          arguments.push(line.to_owned());
+        }
+      }
+
+      groups.push(ArgumentGroup {
+        arguments,
+        help: false,
+        options: BTreeMap::new(),
+        path: recipe.namepath().split("::").map(str::to_owned).collect(),
+      });
+    }
+
+    justfile.run_groups(config, search, overrides, groups)
+  }
+
+  /// Print completions for the word following `words`, which are the
+  /// arguments already on the command line. If the next word is an argument
+  /// to a recipe parameter with a type, print the values it accepts, otherwise
+  /// print the names of all recipes. If the last recipe has options, print
+  /// them as well.
+  fn complete(config: &Config, justfile: &Justfile, words: &[String]) {
+    let words = words
+      .iter()
+      .map(String::as_str)
+      .skip_while(|word| word.starts_with('-') || word.contains('='))
+      .collect::<Vec<&str>>();
+
+    let next = if words.is_empty() {
+      None
+    } else {
+      match ArgumentParser::parse_arguments(config, justfile, &words) {
+        Ok(groups) => groups.last().and_then(|group| {
+          let (name, path) = group.path.split_last()?;
+
+          let mut module = justfile;
+          for name in path {
+            module = module.modules.get(name)?;
+          }
+
+          let recipe = module.get_recipe(name)?;
+
+          let options = recipe
+            .parameters
+            .iter()
+            .any(|parameter| parameter.kind.is_option());
+
+          let found = group
+            .arguments
+            .iter()
+            .filter(|argument| !(options && argument.starts_with("--")))
+            .count();
+
+          Some((recipe.parameters.clone(), found))
+        }),
+        Err(Error::ArgumentCountMismatch { usage, found, .. }) => Some((usage.parameters, found)),
+        Err(_) => None,
+      }
+    };
+
+    let Some((parameters, found)) = next else {
+      Self::summary(config, justfile);
+      return;
+    };
+
+    let options = parameters
+      .iter()
+      .filter(|parameter| parameter.kind.is_option())
+      .map(|parameter| format!("--{}", parameter.name.lexeme()))
+      .collect::<Vec<String>>();
+
+    if let Some(ty) = Self::next_parameter_type(&parameters, found) {
+      let mut completions = ty
+        .completions()
+        .into_iter()
+        .map(str::to_owned)
+        .collect::<Vec<String>>();
+      completions.extend(options);
+      println!("{}", completions.join(" "));
+    } else {
+      if !options.is_empty() {
+        print!("{} ", options.join(" "));
+      }
+      Self::summary(config, justfile);
+    }
+  }
+
+  /// The type of the positional parameter that will be bound to the next
+  /// argument, given that `found` positional arguments have already been
+  /// passed
+  fn next_parameter_type<'a, 'src>(
+    parameters: &'a [Parameter<'src>],
+    found: usize,
+  ) -> Option<&'a ParameterType<'src>> {
+    let positional = parameters
+      .iter()
+      .filter(|parameter| !parameter.kind.is_option())
+      .collect::<Vec<&Parameter>>();
+
+    positional
+      .get(found)
+      .or_else(|| {
+        positional
+          .last()
+          .filter(|parameter| parameter.kind.is_variadic())
+      })?
+      .ty
+      .as_ref()
+  }
+
   fn completions(shell: completions::Shell) {
     print!("{}", shell.script());
   }
@@ -293,11 +661,23 @@ impl Subcommand {
   fn dump(config: &Config, compilation: Compilation) -> RunResult<'static> {
     match config.dump_format {
       DumpFormat::Json => {
-        serde_json::to_writer(io::stdout(), &compilation.justfile)
+        let json = serde_json::to_string(&compilation.justfile)
           .map_err(|source| Error::DumpJson { source })?;
-        println!();
+        writeln!(io::stdout(), "{json}").map_err(|io_error| Error::DumpJson {
+          source: serde_json::Error::io(io_error),
+        })?;
+      }
+      DumpFormat::Just => {
+        let mut ast = compilation.root_ast().clone();
+
+        for item in &mut ast.items {
+          if let Item::Assignment { assignment, .. } = item {
+            *assignment = assignment.redacted();
+          }
+        }
+
+        print!("{ast}");
       }
-      DumpFormat::Just => print!("{}", compilation.root_ast()),
     }
     Ok(())
   }
@@ -400,6 +780,28 @@ impl Subcommand {
     Ok(())
   }
 
+  fn lint(config: &Config, justfile: &Justfile) -> RunResult<'static> {
+    let mut count = 0;
+    let mut modules = vec![justfile];
+
+    while let Some(module) = modules.pop() {
+      for warning in &module.warnings {
+        if config.verbosity.loud() {
+          eprintln!("{}\n", warning.color_display(config.color.stderr()));
+        }
+        count += 1;
+      }
+
+      modules.extend(module.modules.values().rev());
+    }
+
+    if count == 0 {
+      Ok(())
+    } else {
+      Err(Error::Lint { count })
+    }
+  }
+
   fn man() -> RunResult<'static> {
     let mut buffer = Vec::<u8>::new();
 
@@ -710,6 +1112,29 @@ impl Subcommand {
     }
   }
 
+  fn show_config(values: &[(String, ConfigValue, ConfigSource)]) {
+    let mut lines = values
+      .iter()
+      .map(|(key, value, source)| (format!("{key} = {value}"), source))
+      .collect::<Vec<(String, &ConfigSource)>>();
+
+    lines.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let width = lines
+      .iter()
+      .map(|(line, _source)| line.chars().count())
+      .max()
+      .unwrap_or_default();
+
+    let mut stdout = io::stdout().lock();
+
+    for (line, source) in lines {
+      if writeln!(stdout, "{line:width$}  # {source}").is_err() {
+        return;
+      }
+    }
+  }
+
   fn show<'src>(
     config: &Config,
     mut module: &Justfile<'src>,
@@ -119,6 +119,8 @@ impl Parameter {
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum ParameterKind {
+  Flag,
+  Option,
   Plus,
   Singular,
   Star,
@@ -127,6 +129,8 @@ pub enum ParameterKind {
 impl ParameterKind {
   fn new(parameter_kind: full::ParameterKind) -> Self {
     match parameter_kind {
+      full::ParameterKind::Flag => Self::Flag,
+      full::ParameterKind::Option => Self::Option,
       full::ParameterKind::Singular => Self::Singular,
       full::ParameterKind::Plus => Self::Plus,
       full::ParameterKind::Star => Self::Star,
@@ -213,6 +217,9 @@ pub enum Expression {
     lhs: Option<Box<Expression>>,
     rhs: Box<Expression>,
   },
+  List {
+    elements: Vec<Expression>,
+  },
   Or {
     lhs: Box<Expression>,
     rhs: Box<Expression>,
@@ -252,10 +259,12 @@ impl Expression {
           name: name.lexeme().to_owned(),
           arguments: Vec::new(),
         },
-        full::Thunk::Unary { name, arg, .. } => Self::Call {
-          name: name.lexeme().to_owned(),
-          arguments: vec![Self::new(arg)],
-        },
+        full::Thunk::Unary { name, arg, .. } | full::Thunk::UnaryValue { name, arg, .. } => {
+          Self::Call {
+            name: name.lexeme().to_owned(),
+            arguments: vec![Self::new(arg)],
+          }
+        }
         full::Thunk::UnaryOpt {
           name,
           args: (a, opt_b),
@@ -287,6 +296,9 @@ impl Expression {
         }
         full::Thunk::Binary {
           name, args: [a, b], ..
+        }
+        | full::Thunk::BinaryValue {
+          name, args: [a, b], ..
         } => Self::Call {
           name: name.lexeme().to_owned(),
           arguments: vec![Self::new(a), Self::new(b)],
@@ -313,6 +325,28 @@ impl Expression {
           name: name.lexeme().to_owned(),
           arguments: vec![Self::new(a), Self::new(b), Self::new(c)],
         },
+        full::Thunk::Filter {
+          name,
+          function,
+          list,
+        }
+        | full::Thunk::Map {
+          name,
+          function,
+          list,
+        } => Self::Call {
+          name: name.lexeme().to_owned(),
+          arguments: vec![
+            Self::new(list),
+            Self::Variable {
+              name: function.lexeme().to_owned(),
+            },
+          ],
+        },
+        full::Thunk::UserDefined { args, .. } => Self::Call {
+          name: thunk.qualified_name(),
+          arguments: args.iter().map(Self::new).collect(),
+        },
       },
       Concatenation { lhs, rhs } => Self::Concatenation {
         lhs: Self::new(lhs).into(),
@@ -334,6 +368,9 @@ impl Expression {
         lhs: lhs.as_ref().map(|lhs| Self::new(lhs).into()),
         rhs: Self::new(rhs).into(),
       },
+      List { elements } => Self::List {
+        elements: elements.iter().map(Self::new).collect(),
+      },
       Or { lhs, rhs } => Self::Or {
         lhs: Self::new(lhs).into(),
         rhs: Self::new(rhs).into(),
@@ -45,6 +45,33 @@ pub(crate) enum Thunk<'src> {
     function: fn(function::Context, &str, &str, &str) -> FunctionResult,
     args: [Box<Expression<'src>>; 3],
   },
+  UnaryValue {
+    name: Name<'src>,
+    #[derive_where(skip(Debug, EqHashOrd))]
+    function: fn(function::Context, Value) -> ValueResult,
+    arg: Box<Expression<'src>>,
+  },
+  BinaryValue {
+    name: Name<'src>,
+    #[derive_where(skip(Debug, EqHashOrd))]
+    function: fn(function::Context, Value, Value) -> ValueResult,
+    args: [Box<Expression<'src>>; 2],
+  },
+  Filter {
+    name: Name<'src>,
+    function: Name<'src>,
+    list: Box<Expression<'src>>,
+  },
+  Map {
+    name: Name<'src>,
+    function: Name<'src>,
+    list: Box<Expression<'src>>,
+  },
+  UserDefined {
+    name: Name<'src>,
+    args: Vec<Expression<'src>>,
+    path: Vec<Name<'src>>,
+  },
 }
 
 impl<'src> Thunk<'src> {
@@ -56,82 +83,142 @@ impl<'src> Thunk<'src> {
       | Self::UnaryPlus { name, .. }
       | Self::Binary { name, .. }
       | Self::BinaryPlus { name, .. }
-      | Self::Ternary { name, .. } => *name,
+      | Self::Ternary { name, .. }
+      | Self::UnaryValue { name, .. }
+      | Self::BinaryValue { name, .. }
+      | Self::Filter { name, .. }
+      | Self::Map { name, .. }
+      | Self::UserDefined { name, .. } => *name,
+    }
+  }
+
+  /// The name of the called function, including the path of the module it
+  /// is called from, e.g., `foo::bar`
+  pub(crate) fn qualified_name(&self) -> String {
+    let mut qualified = String::new();
+
+    if let Self::UserDefined { path, .. } = self {
+      for module in path {
+        qualified.push_str(module.lexeme());
+        qualified.push_str("::");
+      }
     }
+
+    qualified.push_str(self.name().lexeme());
+
+    qualified
   }
 
   pub(crate) fn resolve(
     name: Name<'src>,
     mut arguments: Vec<Expression<'src>>,
   ) -> CompileResult<'src, Thunk<'src>> {
-    function::get(name.lexeme()).map_or(
-      Err(name.error(CompileErrorKind::UnknownFunction {
-        function: name.lexeme(),
-      })),
-      |function| match (function, arguments.len()) {
-        (Function::Nullary(function), 0) => Ok(Thunk::Nullary { function, name }),
-        (Function::Unary(function), 1) => Ok(Thunk::Unary {
+    let Some(function) = function::get(name.lexeme()) else {
+      return Ok(Thunk::UserDefined {
+        name,
+        args: arguments,
+        path: Vec::new(),
+      });
+    };
+
+    match (function, arguments.len()) {
+      (Function::Nullary(function), 0) => Ok(Thunk::Nullary { function, name }),
+      (Function::Unary(function), 1) => Ok(Thunk::Unary {
+        function,
+        arg: arguments.pop().unwrap().into(),
+        name,
+      }),
+      (Function::UnaryOpt(function), 1..=2) => {
+        let a = arguments.remove(0).into();
+        let b = match arguments.pop() {
+          Some(value) => Some(value).into(),
+          None => None.into(),
+        };
+        Ok(Thunk::UnaryOpt {
           function,
-          arg: arguments.pop().unwrap().into(),
+          args: (a, b),
           name,
-        }),
-        (Function::UnaryOpt(function), 1..=2) => {
-          let a = arguments.remove(0).into();
-          let b = match arguments.pop() {
-            Some(value) => Some(value).into(),
-            None => None.into(),
-          };
-          Ok(Thunk::UnaryOpt {
-            function,
-            args: (a, b),
-            name,
-          })
-        }
-        (Function::UnaryPlus(function), 1..=usize::MAX) => {
-          let rest = arguments.drain(1..).collect();
-          let a = Box::new(arguments.pop().unwrap());
-          Ok(Thunk::UnaryPlus {
-            function,
-            args: (a, rest),
-            name,
-          })
-        }
-        (Function::Binary(function), 2) => {
-          let b = arguments.pop().unwrap().into();
-          let a = arguments.pop().unwrap().into();
-          Ok(Thunk::Binary {
-            function,
-            args: [a, b],
-            name,
-          })
-        }
-        (Function::BinaryPlus(function), 2..=usize::MAX) => {
-          let rest = arguments.drain(2..).collect();
-          let b = arguments.pop().unwrap().into();
-          let a = arguments.pop().unwrap().into();
-          Ok(Thunk::BinaryPlus {
+        })
+      }
+      (Function::UnaryPlus(function), 1..=usize::MAX) => {
+        let rest = arguments.drain(1..).collect();
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::UnaryPlus {
+          function,
+          args: (a, rest),
+          name,
+        })
+      }
+      (Function::Binary(function), 2) => {
+        let b = arguments.pop().unwrap().into();
+        let a = arguments.pop().unwrap().into();
+        Ok(Thunk::Binary {
+          function,
+          args: [a, b],
+          name,
+        })
+      }
+      (Function::BinaryPlus(function), 2..=usize::MAX) => {
+        let rest = arguments.drain(2..).collect();
+        let b = arguments.pop().unwrap().into();
+        let a = arguments.pop().unwrap().into();
+        Ok(Thunk::BinaryPlus {
+          function,
+          args: ([a, b], rest),
+          name,
+        })
+      }
+      (Function::Ternary(function), 3) => {
+        let c = arguments.pop().unwrap().into();
+        let b = arguments.pop().unwrap().into();
+        let a = arguments.pop().unwrap().into();
+        Ok(Thunk::Ternary {
+          function,
+          args: [a, b, c],
+          name,
+        })
+      }
+      (Function::UnaryValue(function), 1) => Ok(Thunk::UnaryValue {
+        function,
+        arg: arguments.pop().unwrap().into(),
+        name,
+      }),
+      (Function::BinaryValue(function), 2) => {
+        let b = arguments.pop().unwrap().into();
+        let a = arguments.pop().unwrap().into();
+        Ok(Thunk::BinaryValue {
+          function,
+          args: [a, b],
+          name,
+        })
+      }
+      (Function::Filter | Function::Map, 2) => {
+        let Some(Expression::Variable { name: function }) = arguments.pop() else {
+          return Err(name.error(CompileErrorKind::ExpectedFunctionName {
+            function: name.lexeme(),
+          }));
+        };
+        let list = arguments.pop().unwrap().into();
+        if name.lexeme() == "filter" {
+          Ok(Thunk::Filter {
             function,
-            args: ([a, b], rest),
+            list,
             name,
           })
-        }
-        (Function::Ternary(function), 3) => {
-          let c = arguments.pop().unwrap().into();
-          let b = arguments.pop().unwrap().into();
-          let a = arguments.pop().unwrap().into();
-          Ok(Thunk::Ternary {
+        } else {
+          Ok(Thunk::Map {
             function,
-            args: [a, b, c],
+            list,
             name,
           })
         }
-        (function, _) => Err(name.error(CompileErrorKind::FunctionArgumentCountMismatch {
-          function: name.lexeme(),
-          found: arguments.len(),
-          expected: function.argc(),
-        })),
-      },
-    )
+      }
+      (function, _) => Err(name.error(CompileErrorKind::FunctionArgumentCountMismatch {
+        function: name.lexeme(),
+        found: arguments.len(),
+        expected: function.argc(),
+      })),
+    }
   }
 }
 
@@ -140,7 +227,9 @@ impl Display for Thunk<'_> {
     use Thunk::*;
     match self {
       Nullary { name, .. } => write!(f, "{}()", name.lexeme()),
-      Unary { name, arg, .. } => write!(f, "{}({arg})", name.lexeme()),
+      Unary { name, arg, .. } | UnaryValue { name, arg, .. } => {
+        write!(f, "{}({arg})", name.lexeme())
+      }
       UnaryOpt {
         name, args: (a, b), ..
       } => {
@@ -163,6 +252,9 @@ impl Display for Thunk<'_> {
       }
       Binary {
         name, args: [a, b], ..
+      }
+      | BinaryValue {
+        name, args: [a, b], ..
       } => write!(f, "{}({a}, {b})", name.lexeme()),
       BinaryPlus {
         name,
@@ -180,6 +272,29 @@ impl Display for Thunk<'_> {
         args: [a, b, c],
         ..
       } => write!(f, "{}({a}, {b}, {c})", name.lexeme()),
+      Filter {
+        name,
+        function,
+        list,
+      }
+      | Map {
+        name,
+        function,
+        list,
+      } => write!(f, "{}({list}, {function})", name.lexeme()),
+      UserDefined { name, args, path } => {
+        for module in path {
+          write!(f, "{module}::")?;
+        }
+        write!(f, "{}(", name.lexeme())?;
+        for (i, arg) in args.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{arg}")?;
+        }
+        write!(f, ")")
+      }
     }
   }
 }
@@ -191,10 +306,15 @@ impl Serialize for Thunk<'_> {
   {
     let mut seq = serializer.serialize_seq(None)?;
     seq.serialize_element("call")?;
-    seq.serialize_element(&self.name())?;
+    match self {
+      Self::UserDefined { path, .. } if !path.is_empty() => {
+        seq.serialize_element(&self.qualified_name())?;
+      }
+      _ => seq.serialize_element(&self.name())?,
+    }
     match self {
       Self::Nullary { .. } => {}
-      Self::Unary { arg, .. } => seq.serialize_element(&arg)?,
+      Self::Unary { arg, .. } | Self::UnaryValue { arg, .. } => seq.serialize_element(&arg)?,
       Self::UnaryOpt {
         args: (a, opt_b), ..
       } => {
@@ -211,7 +331,7 @@ impl Serialize for Thunk<'_> {
           seq.serialize_element(arg)?;
         }
       }
-      Self::Binary { args, .. } => {
+      Self::Binary { args, .. } | Self::BinaryValue { args, .. } => {
         for arg in args {
           seq.serialize_element(arg)?;
         }
@@ -226,6 +346,15 @@ impl Serialize for Thunk<'_> {
           seq.serialize_element(arg)?;
         }
       }
+      Self::Filter { function, list, .. } | Self::Map { function, list, .. } => {
+        seq.serialize_element(list)?;
+        seq.serialize_element(function)?;
+      }
+      Self::UserDefined { args, .. } => {
+        for arg in args {
+          seq.serialize_element(arg)?;
+        }
+      }
     }
     seq.end()
   }
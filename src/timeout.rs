@@ -0,0 +1,118 @@
+use {super::*, std::time::Duration};
+
+/// A recipe timeout, parsed from a duration like `500ms`, `30s`, or `1h30m`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct Timeout(pub(crate) Duration);
+
+impl FromStr for Timeout {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || "expected a duration like `30s`, `5m`, or `1h30m`".to_owned();
+
+    let mut rest = s;
+    let mut duration = Duration::ZERO;
+
+    while !rest.is_empty() {
+      let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+
+      if digits == 0 {
+        return Err(invalid());
+      }
+
+      let value = rest[..digits].parse::<u64>().map_err(|_| invalid())?;
+
+      rest = &rest[digits..];
+
+      let units = rest
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(rest.len());
+
+      let unit = match &rest[..units] {
+        "ms" => Duration::from_millis(value),
+        "s" => Duration::from_secs(value),
+        "m" => Duration::from_secs(value.saturating_mul(60)),
+        "h" => Duration::from_secs(value.saturating_mul(60 * 60)),
+        _ => return Err(invalid()),
+      };
+
+      rest = &rest[units..];
+
+      duration = duration.saturating_add(unit);
+    }
+
+    if duration.is_zero() {
+      return Err(invalid());
+    }
+
+    Ok(Self(duration))
+  }
+}
+
+impl Display for Timeout {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let secs = self.0.as_secs();
+    let millis = self.0.subsec_millis();
+
+    let hours = secs / 3600;
+    let minutes = secs / 60 % 60;
+    let seconds = secs % 60;
+
+    if hours > 0 {
+      write!(f, "{hours}h")?;
+    }
+
+    if minutes > 0 {
+      write!(f, "{minutes}m")?;
+    }
+
+    if seconds > 0 {
+      write!(f, "{seconds}s")?;
+    }
+
+    if millis > 0 {
+      write!(f, "{millis}ms")?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    #[track_caller]
+    fn case(s: &str, millis: u64) {
+      assert_eq!(
+        s.parse::<Timeout>().unwrap(),
+        Timeout(Duration::from_millis(millis)),
+      );
+    }
+
+    case("500ms", 500);
+    case("30s", 30_000);
+    case("5m", 300_000);
+    case("1h", 3_600_000);
+    case("1h30m", 5_400_000);
+    case("1m30s500ms", 90_500);
+  }
+
+  #[test]
+  fn parse_invalid() {
+    for s in ["", "30", "s", "30x", "-1s", "1.5s", "0s", "30 s"] {
+      assert!(s.parse::<Timeout>().is_err(), "{s}");
+    }
+  }
+
+  #[test]
+  fn display() {
+    for s in ["500ms", "30s", "5m", "1h", "1h30m", "1m30s500ms"] {
+      assert_eq!(s.parse::<Timeout>().unwrap().to_string(), s);
+    }
+  }
+}
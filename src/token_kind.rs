@@ -8,6 +8,7 @@ pub(crate) enum TokenKind {
   Backtick,
   BangEquals,
   BangTilde,
+  Bar,
   BarBar,
   BraceL,
   BraceR,
@@ -30,6 +31,7 @@ pub(crate) enum TokenKind {
   Indent,
   InterpolationEnd,
   InterpolationStart,
+  MinusMinus,
   ParenL,
   ParenR,
   Plus,
@@ -54,6 +56,7 @@ impl Display for TokenKind {
         Backtick => "backtick",
         BangEquals => "'!='",
         BangTilde => "'!~'",
+        Bar => "'|'",
         BarBar => "'||'",
         BraceL => "'{'",
         BraceR => "'}'",
@@ -76,6 +79,7 @@ impl Display for TokenKind {
         Indent => "indent",
         InterpolationEnd => "'}}'",
         InterpolationStart => "'{{'",
+        MinusMinus => "'--'",
         ParenL => "'('",
         ParenR => "')'",
         Plus => "'+'",
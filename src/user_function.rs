@@ -0,0 +1,50 @@
+use super::*;
+
+/// A user-defined function, e.g. `fn slug(s) := lowercase(s)`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct UserFunction<'src> {
+  #[serde(skip)]
+  pub(crate) allowed: BTreeSet<Lint>,
+  pub(crate) body: Expression<'src>,
+  pub(crate) name: Name<'src>,
+  pub(crate) parameters: Vec<Name<'src>>,
+}
+
+impl<'src> UserFunction<'src> {
+  /// User-defined functions called by `expression`, including those passed
+  /// to `map` and `filter`, and the module paths they are called with
+  pub(crate) fn calls<'expression>(
+    expression: &'expression Expression<'src>,
+  ) -> impl Iterator<Item = (&'expression [Name<'src>], Name<'src>)> + 'expression {
+    expression.walk().filter_map(|expression| match expression {
+      Expression::Call {
+        thunk: Thunk::UserDefined { name, path, .. },
+      } => Some((path.as_slice(), *name)),
+      Expression::Call {
+        thunk: Thunk::Filter { function, .. } | Thunk::Map { function, .. },
+      } if function::get(function.lexeme()).is_none() => Some((&[][..], *function)),
+      _ => None,
+    })
+  }
+}
+
+impl<'src> Keyed<'src> for UserFunction<'src> {
+  fn key(&self) -> &'src str {
+    self.name.lexeme()
+  }
+}
+
+impl Display for UserFunction<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "fn {}(", self.name)?;
+
+    for (i, parameter) in self.parameters.iter().enumerate() {
+      if i > 0 {
+        write!(f, ", ")?;
+      }
+      write!(f, "{parameter}")?;
+    }
+
+    write!(f, ") := {}", self.body)
+  }
+}
@@ -0,0 +1,57 @@
+use super::*;
+
+/// The result of evaluating an expression
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub(crate) enum Value {
+  List(Vec<String>),
+  String(String),
+}
+
+impl Value {
+  /// Lists are empty if they have no elements, strings if they have no
+  /// characters
+  pub(crate) fn is_empty(&self) -> bool {
+    match self {
+      Self::List(list) => list.is_empty(),
+      Self::String(string) => string.is_empty(),
+    }
+  }
+
+  /// Convert to a list, treating a string as a single-element list
+  pub(crate) fn into_list(self) -> Vec<String> {
+    match self {
+      Self::List(list) => list,
+      Self::String(string) => vec![string],
+    }
+  }
+
+  /// Convert to a string, joining list elements with spaces
+  pub(crate) fn into_string(self) -> String {
+    match self {
+      Self::List(list) => list.join(" "),
+      Self::String(string) => string,
+    }
+  }
+}
+
+impl From<String> for Value {
+  fn from(string: String) -> Self {
+    Self::String(string)
+  }
+}
+
+impl From<&str> for Value {
+  fn from(string: &str) -> Self {
+    Self::String(string.into())
+  }
+}
+
+impl Display for Value {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::List(list) => write!(f, "{}", list.join(" ")),
+      Self::String(string) => write!(f, "{string}"),
+    }
+  }
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+/// Pre-order iterator over an expression and all of its subexpressions
+pub(crate) struct Walk<'expression, 'src> {
+  stack: Vec<&'expression Expression<'src>>,
+}
+
+impl<'expression, 'src> Walk<'expression, 'src> {
+  pub(crate) fn new(root: &'expression Expression<'src>) -> Self {
+    Self { stack: vec![root] }
+  }
+}
+
+impl<'expression, 'src> Iterator for Walk<'expression, 'src> {
+  type Item = &'expression Expression<'src>;
+
+  fn next(&mut self) -> Option<&'expression Expression<'src>> {
+    let expression = self.stack.pop()?;
+
+    match expression {
+      Expression::And { lhs, rhs } | Expression::Or { lhs, rhs } => {
+        self.stack.push(lhs);
+        self.stack.push(rhs);
+      }
+      Expression::Assert {
+        condition: Condition {
+          lhs,
+          rhs,
+          operator: _,
+        },
+        error,
+      } => {
+        self.stack.push(error);
+        self.stack.push(rhs);
+        self.stack.push(lhs);
+      }
+      Expression::Backtick { .. }
+      | Expression::StringLiteral { .. }
+      | Expression::Variable { .. } => {}
+      Expression::Call { thunk } => match thunk {
+        Thunk::Nullary { .. } => {}
+        Thunk::Unary { arg, .. } | Thunk::UnaryValue { arg, .. } => self.stack.push(arg),
+        Thunk::UnaryOpt {
+          args: (a, opt_b), ..
+        } => {
+          self.stack.push(a);
+          if let Some(b) = opt_b.as_ref() {
+            self.stack.push(b);
+          }
+        }
+        Thunk::UnaryPlus {
+          args: (a, rest), ..
+        } => {
+          let first: &[&Expression] = &[a];
+          for arg in first.iter().copied().chain(rest).rev() {
+            self.stack.push(arg);
+          }
+        }
+        Thunk::Binary { args, .. } | Thunk::BinaryValue { args, .. } => {
+          for arg in args.iter().rev() {
+            self.stack.push(arg);
+          }
+        }
+        Thunk::BinaryPlus {
+          args: ([a, b], rest),
+          ..
+        } => {
+          let first: &[&Expression] = &[a, b];
+          for arg in first.iter().copied().chain(rest).rev() {
+            self.stack.push(arg);
+          }
+        }
+        Thunk::Ternary { args, .. } => {
+          for arg in args.iter().rev() {
+            self.stack.push(arg);
+          }
+        }
+        Thunk::Filter { list, .. } | Thunk::Map { list, .. } => self.stack.push(list),
+        Thunk::UserDefined { args, .. } => {
+          for arg in args.iter().rev() {
+            self.stack.push(arg);
+          }
+        }
+      },
+      Expression::Concatenation { lhs, rhs } => {
+        self.stack.push(rhs);
+        self.stack.push(lhs);
+      }
+      Expression::Conditional {
+        condition: Condition {
+          lhs,
+          rhs,
+          operator: _,
+        },
+        then,
+        otherwise,
+      } => {
+        self.stack.push(otherwise);
+        self.stack.push(then);
+        self.stack.push(rhs);
+        self.stack.push(lhs);
+      }
+      Expression::Group { contents } => {
+        self.stack.push(contents);
+      }
+      Expression::List { elements } => {
+        for element in elements.iter().rev() {
+          self.stack.push(element);
+        }
+      }
+      Expression::Join { lhs, rhs } => {
+        self.stack.push(rhs);
+        if let Some(lhs) = lhs {
+          self.stack.push(lhs);
+        }
+      }
+    }
+
+    Some(expression)
+  }
+}
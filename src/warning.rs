@@ -1,41 +1,135 @@
 use super::*;
 
 #[derive(Clone, Debug, PartialEq)]
-pub(crate) enum Warning {}
+pub(crate) enum Warning<'src> {
+  DeprecatedFunction {
+    function: Name<'src>,
+    replacement: &'static str,
+  },
+  ParameterShadowsVariable {
+    parameter: Name<'src>,
+    recipe: &'src str,
+  },
+  RecipeShadowsAlias {
+    alias: Name<'src>,
+    recipe: Name<'src>,
+  },
+  SettingOverriddenByImport {
+    setting: Name<'src>,
+  },
+  UnreachableRecipe {
+    recipe: Name<'src>,
+    shadowed_by: Name<'src>,
+  },
+  UnusedParameter {
+    parameter: Name<'src>,
+    recipe: &'src str,
+  },
+  UnusedVariable {
+    variable: Name<'src>,
+  },
+}
+
+impl<'src> Warning<'src> {
+  pub(crate) fn lint(&self) -> Lint {
+    match self {
+      Self::DeprecatedFunction { .. } => Lint::DeprecatedFunction,
+      Self::ParameterShadowsVariable { .. } => Lint::ParameterShadowsVariable,
+      Self::RecipeShadowsAlias { .. } => Lint::RecipeShadowsAlias,
+      Self::SettingOverriddenByImport { .. } => Lint::SettingOverriddenByImport,
+      Self::UnreachableRecipe { .. } => Lint::UnreachableRecipe,
+      Self::UnusedParameter { .. } => Lint::UnusedParameter,
+      Self::UnusedVariable { .. } => Lint::UnusedVariable,
+    }
+  }
+
+  pub(crate) fn token(&self) -> Token<'src> {
+    match self {
+      Self::DeprecatedFunction { function: name, .. }
+      | Self::ParameterShadowsVariable {
+        parameter: name, ..
+      }
+      | Self::RecipeShadowsAlias { recipe: name, .. }
+      | Self::SettingOverriddenByImport { setting: name }
+      | Self::UnreachableRecipe { recipe: name, .. }
+      | Self::UnusedParameter {
+        parameter: name, ..
+      }
+      | Self::UnusedVariable { variable: name } => name.token,
+    }
+  }
+}
 
-impl Warning {
-  #[allow(clippy::unused_self)]
-  fn context(&self) -> Option<&Token> {
-    None
+impl Display for Warning<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::DeprecatedFunction {
+        function,
+        replacement,
+      } => write!(
+        f,
+        "Function `{function}` is deprecated, use `{replacement}` instead"
+      ),
+      Self::ParameterShadowsVariable { parameter, recipe } => {
+        write!(
+          f,
+          "Recipe `{recipe}` parameter `{parameter}` shadows variable `{parameter}`"
+        )
+      }
+      Self::RecipeShadowsAlias { alias, recipe } => write!(
+        f,
+        "Recipe `{recipe}` conflicts with alias `{alias}` on line {} on platforms where it is \
+        enabled",
+        alias.line.ordinal(),
+      ),
+      Self::SettingOverriddenByImport { setting } => write!(
+        f,
+        "Setting `{setting}` is set in an imported file, overriding it for the importing justfile",
+      ),
+      Self::UnreachableRecipe {
+        recipe,
+        shadowed_by,
+      } => write!(
+        f,
+        "Recipe `{recipe}` is unreachable, since recipe `{shadowed_by}` on line {} is enabled on \
+        all of its platforms",
+        shadowed_by.line.ordinal(),
+      ),
+      Self::UnusedParameter { parameter, recipe } => {
+        write!(f, "Recipe `{recipe}` parameter `{parameter}` is never used")
+      }
+      Self::UnusedVariable { variable } => write!(f, "Variable `{variable}` is never used"),
+    }
   }
 }
 
-impl ColorDisplay for Warning {
+impl ColorDisplay for Warning<'_> {
   fn fmt(&self, f: &mut Formatter, color: Color) -> fmt::Result {
     let warning = color.warning();
     let message = color.message();
 
-    write!(f, "{} {}", warning.paint("warning:"), message.prefix())?;
-
-    write!(f, "{}", message.suffix())?;
-
-    if let Some(token) = self.context() {
-      writeln!(f)?;
-      write!(f, "{}", token.color_display(color))?;
-    }
+    write!(
+      f,
+      "{} {}{self}{}",
+      warning.paint(&format!("warning[{}]:", self.lint())),
+      message.prefix(),
+      message.suffix(),
+    )?;
 
-    Ok(())
+    writeln!(f)?;
+    write!(f, "{}", self.token().color_display(color))
   }
 }
 
-impl Serialize for Warning {
+impl Serialize for Warning<'_> {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
     S: Serializer,
   {
     let mut map = serializer.serialize_map(None)?;
 
-    map.serialize_entry("message", &self.color_display(Color::never()).to_string())?;
+    map.serialize_entry("lint", &self.lint())?;
+    map.serialize_entry("message", &self.to_string())?;
 
     map.end()
   }
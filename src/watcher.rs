@@ -0,0 +1,151 @@
+use {
+  super::*,
+  std::time::{Duration, SystemTime},
+};
+
+/// Waits for changes to files and glob patterns by polling
+///
+/// Watched files are compared against a snapshot of their modification times
+/// every 250ms. On Linux, inotify is used to wake up early when a watched
+/// directory changes, but the full snapshot is still rescanned to determine
+/// whether a change occurred.
+pub(crate) struct Watcher {
+  paths: Vec<PathBuf>,
+  patterns: Vec<String>,
+}
+
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+impl Watcher {
+  const DEBOUNCE: Duration = Duration::from_millis(50);
+  const INTERVAL: Duration = Duration::from_millis(250);
+
+  pub(crate) fn new(paths: Vec<PathBuf>, patterns: Vec<String>) -> Self {
+    Self { paths, patterns }
+  }
+
+  pub(crate) fn snapshot(&self) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+
+    for path in &self.paths {
+      snapshot.insert(path.clone(), Self::modified(path));
+    }
+
+    for pattern in &self.patterns {
+      for path in glob::glob(pattern).into_iter().flatten().flatten() {
+        let modified = Self::modified(&path);
+        snapshot.insert(path, modified);
+      }
+    }
+
+    snapshot
+  }
+
+  /// Block until the watched files differ from `snapshot`, or `stop` returns
+  /// true. Returns whether a change was detected.
+  pub(crate) fn wait(&self, snapshot: &Snapshot, stop: impl Fn() -> bool) -> bool {
+    #[cfg(target_os = "linux")]
+    let inotify = Inotify::new(&self.directories(snapshot));
+
+    loop {
+      #[cfg(target_os = "linux")]
+      match &inotify {
+        Some(inotify) => inotify.wait(Self::INTERVAL),
+        None => thread::sleep(Self::INTERVAL),
+      }
+
+      #[cfg(not(target_os = "linux"))]
+      thread::sleep(Self::INTERVAL);
+
+      if stop() {
+        return false;
+      }
+
+      if self.snapshot() != *snapshot {
+        thread::sleep(Self::DEBOUNCE);
+        return true;
+      }
+    }
+  }
+
+  /// Directories containing watched files, plus the non-glob prefix of each
+  /// pattern, so that newly created matches are noticed
+  #[cfg(target_os = "linux")]
+  fn directories(&self, snapshot: &Snapshot) -> BTreeSet<PathBuf> {
+    let mut directories = snapshot
+      .keys()
+      .filter_map(|path| path.parent())
+      .map(Path::to_path_buf)
+      .collect::<BTreeSet<PathBuf>>();
+
+    for pattern in &self.patterns {
+      let prefix = Path::new(pattern)
+        .components()
+        .take_while(|component| {
+          !component
+            .as_os_str()
+            .to_string_lossy()
+            .contains(['*', '?', '[', ']'])
+        })
+        .collect::<PathBuf>();
+
+      directories.insert(prefix);
+    }
+
+    directories
+  }
+
+  fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+      .and_then(|metadata| metadata.modified())
+      .ok()
+  }
+}
+
+#[cfg(target_os = "linux")]
+struct Inotify(nix::sys::inotify::Inotify);
+
+#[cfg(target_os = "linux")]
+impl Inotify {
+  fn new(directories: &BTreeSet<PathBuf>) -> Option<Self> {
+    use nix::sys::inotify::{AddWatchFlags, InitFlags};
+
+    let inotify =
+      nix::sys::inotify::Inotify::init(InitFlags::IN_CLOEXEC | InitFlags::IN_NONBLOCK).ok()?;
+
+    for directory in directories {
+      inotify
+        .add_watch(
+          directory,
+          AddWatchFlags::IN_ATTRIB
+            | AddWatchFlags::IN_CLOSE_WRITE
+            | AddWatchFlags::IN_CREATE
+            | AddWatchFlags::IN_DELETE
+            | AddWatchFlags::IN_MODIFY
+            | AddWatchFlags::IN_MOVED_FROM
+            | AddWatchFlags::IN_MOVED_TO,
+        )
+        .ok();
+    }
+
+    Some(Self(inotify))
+  }
+
+  /// Wait for events or `timeout`, then drain pending events
+  fn wait(&self, timeout: Duration) {
+    use {
+      nix::poll::{PollFd, PollFlags, PollTimeout},
+      std::os::fd::AsFd,
+    };
+
+    let timeout = PollTimeout::try_from(timeout).unwrap_or(PollTimeout::MAX);
+
+    nix::poll::poll(
+      &mut [PollFd::new(self.0.as_fd(), PollFlags::POLLIN)],
+      timeout,
+    )
+    .ok();
+
+    while self.0.read_events().is_ok_and(|events| !events.is_empty()) {}
+  }
+}
@@ -1,6 +1,6 @@
 use super::*;
 
-pub(crate) fn which(context: function::Context, name: &str) -> Result<Option<String>, String> {
+pub(crate) fn which(working_directory: &Path, name: &str) -> Result<Option<String>, String> {
   let name = Path::new(name);
 
   let candidates = match name.components().count() {
@@ -22,11 +22,7 @@ pub(crate) fn which(context: function::Context, name: &str) -> Result<Option<Str
       // This candidate is a relative path, either because the user invoked `which("rel/path")`,
       // or because there was a relative path in `PATH`. Resolve it to an absolute path,
       // relative to the working directory of the just invocation.
-      candidate = context
-        .evaluator
-        .context
-        .working_directory()
-        .join(candidate);
+      candidate = working_directory.join(candidate);
     }
 
     candidate = candidate.lexiclean();
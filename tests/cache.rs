@@ -0,0 +1,302 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  [cache]
+  [inputs('*.c')]
+  build target='debug':
+    @echo building {{ target }}
+";
+
+#[test]
+fn unchanged_recipe_is_skipped() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .write("a.c", "foo")
+    .stdout("building debug\n")
+    .run();
+
+  Test::with_tempdir(tempdir).justfile(JUSTFILE).run();
+}
+
+#[test]
+fn changed_input_reruns_recipe() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .write("a.c", "foo")
+    .stdout("building debug\n")
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .write("a.c", "bar")
+    .stdout("building debug\n")
+    .run();
+}
+
+#[test]
+fn new_input_reruns_recipe() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .write("a.c", "foo")
+    .stdout("building debug\n")
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .write("b.c", "foo")
+    .stdout("building debug\n")
+    .run();
+}
+
+#[test]
+fn changed_parameter_reruns_recipe() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .stdout("building debug\n")
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .args(["build", "release"])
+    .stdout("building release\n")
+    .run();
+}
+
+#[test]
+fn changed_body_reruns_recipe() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .stdout("building debug\n")
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(
+      "
+        [cache]
+        [inputs('*.c')]
+        build target='debug':
+          @echo compiling {{ target }}
+      ",
+    )
+    .stdout("compiling debug\n")
+    .run();
+}
+
+#[test]
+fn changed_exported_variable_reruns_recipe() {
+  let justfile = |value: &str| {
+    format!(
+      "
+        export FOO := '{value}'
+
+        [cache]
+        build:
+          @echo $FOO
+      "
+    )
+  };
+
+  let Output { tempdir, .. } = Test::new().justfile(justfile("a")).stdout("a\n").run();
+
+  let Output { tempdir, .. } = Test::with_tempdir(tempdir).justfile(justfile("a")).run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(justfile("b"))
+    .stdout("b\n")
+    .run();
+}
+
+#[test]
+fn failed_run_is_not_cached() {
+  let justfile = "
+    [cache]
+    build:
+      @echo building
+      @exit 1
+  ";
+
+  let Output { tempdir, .. } = Test::new()
+    .justfile(justfile)
+    .stdout("building\n")
+    .stderr("error: Recipe `build` failed on line 4 with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(justfile)
+    .stdout("building\n")
+    .stderr("error: Recipe `build` failed on line 4 with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dry_run_is_not_cached() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .arg("--dry-run")
+    .stderr(
+      "
+        #### Recipe `build` is out of date: no successful run is cached
+        echo building debug
+      ",
+    )
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .stdout("building debug\n")
+    .run();
+}
+
+#[test]
+fn force_reruns_cached_recipe() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .stdout("building debug\n")
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .arg("--force")
+    .stdout("building debug\n")
+    .run();
+}
+
+#[test]
+fn explain() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .arg("--explain")
+    .stdout("building debug\n")
+    .stderr("#### Recipe `build` is out of date: no successful run is cached\n")
+    .run();
+
+  let Output { tempdir, .. } = Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .arg("--explain")
+    .stderr("#### Recipe `build` is up to date: content hash matches last successful run\n")
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .write("a.c", "foo")
+    .arg("--explain")
+    .stdout("building debug\n")
+    .stderr("#### Recipe `build` is out of date: content hash changed since last successful run\n")
+    .run();
+}
+
+#[test]
+fn cache_is_stored_next_to_justfile() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .create_dir("sub")
+    .current_dir("sub")
+    .stdout("building debug\n")
+    .run();
+
+  assert!(tempdir.path().join(".just/cache").is_file());
+}
+
+#[test]
+fn cache_clean() {
+  let Output { tempdir, .. } = Test::new()
+    .justfile(JUSTFILE)
+    .stdout("building debug\n")
+    .run();
+
+  let Output { tempdir, .. } = Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .arg("--cache-clean")
+    .run();
+
+  assert!(!tempdir.path().join(".just/cache").exists());
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .stdout("building debug\n")
+    .run();
+}
+
+#[test]
+fn cache_clean_without_cache() {
+  Test::new().arg("--cache-clean").run();
+}
+
+#[test]
+fn changed_function_output_reruns_recipe() {
+  let justfile = "
+    [cache]
+    build:
+      @echo {{ env('V', 'none') }}
+  ";
+
+  let Output { tempdir, .. } = Test::new()
+    .justfile(justfile)
+    .env("V", "a")
+    .stdout("a\n")
+    .run();
+
+  let Output { tempdir, .. } = Test::with_tempdir(tempdir)
+    .justfile(justfile)
+    .env("V", "a")
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(justfile)
+    .env("V", "b")
+    .stdout("b\n")
+    .run();
+}
+
+#[test]
+fn changed_env_attribute_reruns_recipe() {
+  let justfile = "
+    [cache]
+    [env('FOO', env('V', 'none'))]
+    build:
+      @echo $FOO
+  ";
+
+  let Output { tempdir, .. } = Test::new()
+    .justfile(justfile)
+    .env("V", "a")
+    .stdout("a\n")
+    .run();
+
+  let Output { tempdir, .. } = Test::with_tempdir(tempdir)
+    .justfile(justfile)
+    .env("V", "a")
+    .run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(justfile)
+    .env("V", "b")
+    .stdout("b\n")
+    .run();
+}
+
+#[test]
+fn changed_variable_reruns_recipe() {
+  let justfile = |value: &str| {
+    format!(
+      "
+        foo := '{value}'
+
+        [cache]
+        build:
+          @echo {{{{ foo }}}}
+      "
+    )
+  };
+
+  let Output { tempdir, .. } = Test::new().justfile(justfile("a")).stdout("a\n").run();
+
+  let Output { tempdir, .. } = Test::with_tempdir(tempdir).justfile(justfile("a")).run();
+
+  Test::with_tempdir(tempdir)
+    .justfile(justfile("b"))
+    .stdout("b\n")
+    .run();
+}
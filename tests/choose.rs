@@ -119,6 +119,7 @@ fn skip_recipes_that_require_arguments() {
 fn no_choosable_recipes() {
   Test::new()
     .arg("--choose")
+    .env("JUST_CHOOSER", "head -n1")
     .justfile(
       "
         _foo:
@@ -155,7 +156,21 @@ fn multiple_recipes() {
 
 #[test]
 fn invoke_error_function() {
-  Test::new()
+  let tempdir = tempdir();
+
+  let cat = which("cat").unwrap();
+  let fzf = tempdir.path().join(format!("fzf{EXE_SUFFIX}"));
+
+  #[cfg(unix)]
+  std::os::unix::fs::symlink(cat, fzf).unwrap();
+
+  #[cfg(windows)]
+  std::os::windows::fs::symlink_file(cat, fzf).unwrap();
+
+  let path = tempdir.path().to_owned();
+
+  Test::with_tempdir(tempdir)
+    .env("PATH", path.to_str().unwrap())
     .justfile(
       "
         foo:
@@ -243,3 +258,196 @@ fn default() {
 
   assert_stdout(&output, "foo\n");
 }
+
+fn builtin() -> Test {
+  Test::new()
+    .arg("--choose")
+    .env("PATH", "")
+    .shell(false)
+    .args(["--shell", "/bin/sh", "--shell-arg", "-cu"])
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_is_used_if_fzf_is_not_installed() {
+  builtin()
+    .justfile(
+      "
+        foo:
+          echo foo
+
+        bar:
+          echo bar
+      ",
+    )
+    .stdin("fo\n")
+    .stderr("echo foo\n")
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_selects_first_recipe_by_default() {
+  builtin()
+    .justfile(
+      "
+        foo:
+          echo foo
+
+        bar:
+          echo bar
+      ",
+    )
+    .stdin("\n")
+    .stderr("echo bar\n")
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_arrow_keys() {
+  builtin()
+    .justfile(
+      "
+        a:
+          echo a
+
+        b:
+          echo b
+
+        c:
+          echo c
+      ",
+    )
+    .stdin("\x1b[B\x1b[B\x1b[A\n")
+    .stderr("echo b\n")
+    .stdout("b\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_multiple_recipes() {
+  builtin()
+    .justfile(
+      "
+        a:
+          echo a
+
+        b:
+          echo b
+
+        c:
+          echo c
+      ",
+    )
+    .stdin("\t\x1b[B\t\n")
+    .stderr("echo a\necho c\n")
+    .stdout("a\nc\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_lists_recipes_under_group_headers() {
+  builtin()
+    .justfile(
+      "
+        [group('x')]
+        a:
+          echo a
+
+        b:
+          echo b
+      ",
+    )
+    .stdin("\x1b[B\n")
+    .stderr("echo a\n")
+    .stdout("a\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_prompts_for_required_parameters() {
+  builtin()
+    .justfile(
+      "
+        foo a +b:
+          echo {{a}} {{b}}
+      ",
+    )
+    .stdin("\nA\nB C\n")
+    .stderr("foo a: foo +b: echo A B C\n")
+    .stdout("A B C\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_accepts_carriage_return_line_endings() {
+  builtin()
+    .justfile(
+      "
+        foo a:
+          echo {{a}}
+      ",
+    )
+    .stdin("\r\nA\r\n")
+    .stderr("foo a: echo A\n")
+    .stdout("A\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_arguments_are_not_passed_to_later_recipes() {
+  builtin()
+    .justfile(
+      "
+        foo a b='B':
+          echo {{a}} {{b}}
+
+        bar:
+          echo bar
+      ",
+    )
+    .stdin("\t\t\nA\n")
+    .stderr("foo a: echo bar\necho A B\n")
+    .stdout("bar\nA B\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_cancelled() {
+  builtin()
+    .justfile(
+      "
+        foo:
+          echo foo
+      ",
+    )
+    .stdin("\x03")
+    .stderr("error: Chooser was cancelled\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn builtin_chooser_no_matches() {
+  builtin()
+    .justfile(
+      "
+        foo:
+          echo foo
+      ",
+    )
+    .stdin("bar\n")
+    .stderr("error: Chooser was cancelled\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
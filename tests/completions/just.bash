@@ -53,6 +53,48 @@ test_complete_recipes_from_subdirs() {
 }
 test_complete_recipes_from_subdirs
 
+test_complete_parameter_choices() {
+  COMP_WORDS=(just deploy '')
+  COMP_CWORD=2 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="dev" [1]="prod" [2]="--dry" [3]="--region")'
+}
+test_complete_parameter_choices
+
+test_complete_parameter_choices_starting_with_p() {
+  COMP_WORDS=(just deploy p)
+  COMP_CWORD=2 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="prod")'
+}
+test_complete_parameter_choices_starting_with_p
+
+test_complete_recipes_after_arguments() {
+  COMP_WORDS=(just deploy dev '')
+  COMP_CWORD=3 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="--dry" [1]="--region" [2]="deploy" [3]="install" [4]="publish" [5]="push" [6]="test")'
+}
+test_complete_recipes_after_arguments
+
+test_complete_recipe_options() {
+  COMP_WORDS=(just deploy --)
+  COMP_CWORD=2 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="--dry" [1]="--region")'
+}
+test_complete_recipe_options
+
+test_complete_just_options() {
+  COMP_WORDS=(just --sh)
+  COMP_CWORD=1 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="--shell" [1]="--shell-arg" [2]="--shell-command" [3]="--show" [4]="--show-config")'
+}
+test_complete_just_options
+
+test_complete_parameter_choices_after_option() {
+  COMP_WORDS=(just deploy --dry '')
+  COMP_CWORD=3 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="dev" [1]="prod" [2]="--dry" [3]="--region")'
+}
+test_complete_parameter_choices_after_option
+
 # --- Conclusion ---
 if [ $exit_code = 0 ]; then
   echo "All tests passed."
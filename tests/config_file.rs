@@ -0,0 +1,319 @@
+use super::*;
+
+fn test() -> (Test, String) {
+  let tempdir = tempdir();
+  let path = tempdir.path().to_str().unwrap().to_owned();
+  (
+    Test::with_tempdir(tempdir)
+      .env("JUST_NO_CONFIG", "false")
+      .env("XDG_CONFIG_HOME", &path)
+      .shell(false),
+    path,
+  )
+}
+
+#[test]
+fn user_config() {
+  let (test, _path) = test();
+  test
+    .write(
+      "just/config.toml",
+      "unsorted = true\nlist-heading = \"Recipes:\\n\"\n",
+    )
+    .justfile(
+      "
+        b:
+        a:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Recipes:
+            b
+            a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn project_config() {
+  let (test, _path) = test();
+  test
+    .write(".justrc", "list-prefix = '- '\n")
+    .justfile(
+      "
+        a:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+        - a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn project_config_is_found_in_ancestors() {
+  let (test, _path) = test();
+  test
+    .write(".justrc", "list-prefix = '- '\n")
+    .write("sub/justfile", "a:")
+    .current_dir("sub")
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+        - a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn shell() {
+  let (test, _path) = test();
+  test
+    .write(".justrc", "shell = 'bash'\nshell-arg = ['-c']\n")
+    .justfile(
+      "
+        foo:
+          @echo $0
+      ",
+    )
+    .stdout("bash\n")
+    .run();
+}
+
+#[test]
+fn justfile_shell_overrides_config_shell() {
+  let (test, _path) = test();
+  test
+    .write(".justrc", "shell = 'bash'\nshell-arg = ['-c']\n")
+    .justfile(
+      "
+        set shell := ['sh', '-c']
+
+        foo:
+          @echo $0
+      ",
+    )
+    .stdout("sh\n")
+    .run();
+}
+
+#[test]
+fn no_unsorted_overrides_config() {
+  let (test, _path) = test();
+  test
+    .write(".justrc", "unsorted = true\n")
+    .justfile(
+      "
+        b:
+        a:
+      ",
+    )
+    .args(["--list", "--no-unsorted"])
+    .stdout(
+      "
+        Available recipes:
+            a
+            b
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn project_config_overrides_user_config() {
+  let (test, _path) = test();
+  test
+    .write("just/config.toml", "list-prefix = 'user '\n")
+    .write(".justrc", "list-prefix = 'project '\n")
+    .justfile("a:")
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+        project a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn environment_variable_overrides_project_config() {
+  let (test, _path) = test();
+  test
+    .write(".justrc", "list-prefix = 'project '\n")
+    .env("JUST_LIST_PREFIX", "env ")
+    .justfile("a:")
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+        env a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn command_line_overrides_environment_variable() {
+  let (test, _path) = test();
+  test
+    .env("JUST_LIST_PREFIX", "env ")
+    .justfile("a:")
+    .args(["--list-prefix", "cli ", "--list"])
+    .stdout(
+      "
+        Available recipes:
+        cli a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn clear_shell_args_overrides_config() {
+  let (test, _path) = test();
+  test
+    .write(".justrc", "shell-arg = ['-x']\n")
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .args(["--shell", "bash", "--clear-shell-args", "--shell-arg", "-c"])
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn show_config() {
+  let (test, path) = test();
+  test
+    .write("just/config.toml", "unsorted = true\nchooser = 'sk'\n")
+    .write(
+      ".justrc",
+      "shell = \"bash\"\nshell-arg = ['-euc']\ncolor = 'never'\n",
+    )
+    .env("JUST_TIMESTAMP", "true")
+    .args(["--show-config", "--list-heading", "Recipes:\n"])
+    .stdout(
+      unindent(
+        r#"
+          chooser = "sk"                 # user config `DIR/just/config.toml`
+          color = "never"                # project config `DIR/.justrc`
+          list-heading = "Recipes:\n"    # command line
+          list-prefix = "    "           # default
+          shell = "bash"                 # project config `DIR/.justrc`
+          shell-arg = ["-euc"]           # project config `DIR/.justrc`
+          timestamp = true               # environment variable `JUST_TIMESTAMP`
+          timestamp-format = "%H:%M:%S"  # default
+          unsorted = true                # user config `DIR/just/config.toml`
+        "#,
+      )
+      .replace("DIR", &path),
+    )
+    .run();
+}
+
+#[test]
+fn show_config_omits_unset_options() {
+  let (test, _path) = test();
+  test
+    .arg("--show-config")
+    .stdout(
+      r#"
+        color = "auto"                         # default
+        list-heading = "Available recipes:\n"  # default
+        list-prefix = "    "                   # default
+        timestamp = false                      # default
+        timestamp-format = "%H:%M:%S"          # default
+        unsorted = false                       # default
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn parse_error() {
+  let (test, path) = test();
+  test
+    .write("just/config.toml", "# comment\nunsorted = 'yes'\n")
+    .justfile("a:")
+    .stderr(format!(
+      "error: Failed to parse config file `{path}/just/config.toml` on line 2: \
+       `unsorted` must be a boolean\n"
+    ))
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn unknown_key() {
+  let (test, path) = test();
+  test
+    .write(".justrc", "dry-run = true\n")
+    .justfile("a:")
+    .stderr(format!(
+      "error: Failed to parse config file `{path}/.justrc` on line 1: unknown key `dry-run`\n"
+    ))
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn no_config() {
+  let (test, _path) = test();
+  test
+    .write(".justrc", "list-prefix = '- '\n")
+    .write("just/config.toml", "list-heading = 'Recipes:\\n'\n")
+    .justfile(
+      "
+        a:
+      ",
+    )
+    .args(["--no-config", "--list"])
+    .stdout(
+      "
+        Available recipes:
+            a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn valid_toml_is_accepted() {
+  let (test, _path) = test();
+  test
+    .write(
+      ".justrc",
+      "
+list-heading = \"\"\"
+Recipes:
+\"\"\"
+list-prefix = '''- '''
+",
+    )
+    .justfile(
+      "
+        a:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Recipes:
+        - a
+      ",
+    )
+    .run();
+}
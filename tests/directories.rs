@@ -13,6 +13,10 @@ fn cache_directory() {
 fn config_directory() {
   Test::new()
     .justfile("x := config_directory()")
+    .env(
+      "XDG_CONFIG_HOME",
+      dirs::config_dir().unwrap_or_default().to_str().unwrap(),
+    )
     .args(["--evaluate", "x"])
     .stdout(dirs::config_dir().unwrap_or_default().to_string_lossy())
     .run();
@@ -22,6 +26,10 @@ fn config_directory() {
 fn config_local_directory() {
   Test::new()
     .justfile("x := config_local_directory()")
+    .env(
+      "XDG_CONFIG_HOME",
+      dirs::config_dir().unwrap_or_default().to_str().unwrap(),
+    )
     .args(["--evaluate", "x"])
     .stdout(
       dirs::config_local_dir()
@@ -442,3 +442,202 @@ fn dotenv_path_does_not_override_dotenv_file() {
     .stdout("ROOT\n")
     .run();
 }
+
+#[test]
+fn dotenv_files_are_loaded_in_order() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.local']
+
+        foo:
+          @echo $A $B
+      ",
+    )
+    .write(".env", "A=a\nB=b")
+    .write(".env.local", "B=local")
+    .stdout("a local\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_which_do_not_exist_are_skipped() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.local']
+
+        foo:
+          @echo $A
+      ",
+    )
+    .write(".env.local", "A=local")
+    .stdout("local\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_required() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.local']
+        set dotenv-required
+
+        foo:
+      ",
+    )
+    .write(".env.other", "A=a")
+    .stderr("error: Dotenv file not found\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dotenv_files_expand_variables_from_earlier_files() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.local']
+
+        foo:
+          @echo $B
+      ",
+    )
+    .write(".env", "A='a \"$ \\\\'")
+    .write(".env.local", "B=${A}b")
+    .stdout("a \"$ \\\\b\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_profile() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.{{profile}}']
+
+        foo:
+          @echo $A
+      ",
+    )
+    .write(".env", "A=a")
+    .write(".env.prod", "A=prod")
+    .args(["--dotenv-profile", "prod"])
+    .stdout("prod\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_profile_from_environment() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.{{profile}}']
+
+        foo:
+          @echo $A
+      ",
+    )
+    .write(".env", "A=a")
+    .write(".env.prod", "A=prod")
+    .env("JUST_DOTENV_PROFILE", "prod")
+    .stdout("prod\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_without_profile() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.{{profile}}']
+
+        foo:
+          @echo $A
+      ",
+    )
+    .write(".env", "A=a")
+    .write(".env.{{profile}}", "A=profile")
+    .stdout("a\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_do_not_override_environment() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.local']
+
+        foo:
+          @echo $A
+      ",
+    )
+    .write(".env", "A=a")
+    .write(".env.local", "A=local")
+    .env("A", "environment")
+    .stdout("environment\n")
+    .run();
+}
+
+#[test]
+fn dotenv_path_flag_overrides_dotenv_files() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env']
+
+        foo:
+          @echo $A
+      ",
+    )
+    .write(".env", "A=a")
+    .write(".env.other", "A=other")
+    .args(["--dotenv-path", ".env.other"])
+    .stdout("other\n")
+    .run();
+}
+
+#[test]
+fn evaluate_shows_dotenv_sources() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-files := ['.env', '.env.local']
+
+        x := 'y'
+      ",
+    )
+    .write(".env", "A=a\nB=b")
+    .write(".env.local", "B=local\nC=c")
+    .arg("--evaluate")
+    .stdout(
+      "
+        x := \"y\"
+
+        # .env
+        A=\"a\"
+
+        # .env.local
+        B=\"local\"
+        C=\"c\"
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn evaluate_shows_dotenv_source_without_variables() {
+  Test::new()
+    .justfile("set dotenv-load")
+    .write(".env", "A=a")
+    .arg("--evaluate")
+    .stdout(
+      "
+        # .env
+        A=\"a\"
+      ",
+    )
+    .run();
+}
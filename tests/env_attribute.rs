@@ -0,0 +1,218 @@
+use super::*;
+
+#[test]
+fn linewise() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'bar')]
+        foo:
+          @echo $FOO
+      ",
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn script() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'bar')]
+        foo:
+          #!/usr/bin/env sh
+          echo $FOO
+      ",
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn value_is_an_expression() {
+  Test::new()
+    .justfile(
+      "
+        level := 'debug'
+
+        [env('RUST_LOG', name + '=' + level)]
+        foo name:
+          @echo $RUST_LOG
+      ",
+    )
+    .args(["foo", "bar"])
+    .stdout("bar=debug\n")
+    .run();
+}
+
+#[test]
+fn multiple() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'a')]
+        [env('BAR', 'b')]
+        foo:
+          @echo $FOO $BAR
+      ",
+    )
+    .stdout("a b\n")
+    .run();
+}
+
+#[test]
+fn overrides_exports() {
+  Test::new()
+    .justfile(
+      "
+        export FOO := 'a'
+
+        [env('FOO', 'b')]
+        foo:
+          @echo $FOO
+      ",
+    )
+    .stdout("b\n")
+    .run();
+}
+
+#[test]
+fn is_not_exported_to_dependencies() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'a')]
+        foo: bar
+
+        bar:
+          @echo ${FOO:-unset}
+      ",
+    )
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn unexport_is_respected() {
+  Test::new()
+    .justfile(
+      "
+        unexport FOO
+
+        [env('FOO', 'a')]
+        foo:
+          @echo ${FOO:-unset}
+      ",
+    )
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn duplicate_key() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'a')]
+        [env('FOO', 'b')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe attribute `env` first used on line 1 is duplicated on line 2
+         ——▶ justfile:2:2
+          │
+        2 │ [env('FOO', 'b')]
+          │  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_key() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO=BAR', 'a')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Invalid environment variable name `FOO=BAR`
+         ——▶ justfile:1:2
+          │
+        1 │ [env('FOO=BAR', 'a')]
+          │  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn missing_value() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Attribute `env` got 1 argument but takes 2 arguments
+         ——▶ justfile:1:2
+          │
+        1 │ [env('FOO')]
+          │  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn undefined_variable() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', bar)]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Variable `bar` not defined
+         ——▶ justfile:1:13
+          │
+        1 │ [env('FOO', bar)]
+          │             ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'a' + `echo b`)]
+        foo:
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [env('FOO', 'a' + `echo b`)]
+        foo:
+      ",
+    )
+    .run();
+}
@@ -61,6 +61,10 @@ fn argument_count_mismatch() {
       error: Recipe `foo` got 0 arguments but takes 2
       usage:
           just foo a b
+
+      parameters:
+          a  required
+          b  required
     ",
     )
     .status(EXIT_FAILURE)
@@ -74,7 +78,7 @@ fn file_path_is_indented_if_justfile_is_long() {
     .status(EXIT_FAILURE)
     .stderr(
       "
-error: Expected '*', ':', '$', identifier, or '+', but found end of file
+error: Expected '*', ':', '$', identifier, '--', or '+', but found end of file
   ——▶ justfile:20:4
    │
 20 │ foo
@@ -92,7 +96,7 @@ fn file_paths_are_relative() {
     .status(EXIT_FAILURE)
     .stderr(format!(
       "
-error: Expected '*', ':', '$', identifier, or '+', but found end of file
+error: Expected '*', ':', '$', identifier, '--', or '+', but found end of file
  ——▶ foo{MAIN_SEPARATOR}bar.just:1:4
   │
 1 │ baz
@@ -112,7 +116,7 @@ fn file_paths_not_in_subdir_are_absolute() {
     .args(["--justfile", "foo/justfile"])
     .status(EXIT_FAILURE)
     .stderr_regex(
-      r"error: Expected '\*', ':', '\$', identifier, or '\+', but found end of file
+      r"error: Expected '\*', ':', '\$', identifier, '--', or '\+', but found end of file
  ——▶ /.*/bar.just:1:4
   │
 1 │ baz
@@ -0,0 +1,268 @@
+use super::*;
+
+/// Read events from `events.jsonl`, removing the nondeterministic `time` and
+/// `duration` fields
+fn events(output: &Output) -> Vec<Value> {
+  fs::read_to_string(output.tempdir.path().join("events.jsonl"))
+    .unwrap()
+    .lines()
+    .map(|line| {
+      let mut event = serde_json::from_str::<Value>(line).unwrap();
+      let object = event.as_object_mut().unwrap();
+      assert!(object.remove("time").unwrap().is_string());
+      if let Some(duration) = object.remove("duration") {
+        assert!(duration.is_f64());
+      }
+      event
+    })
+    .collect()
+}
+
+#[test]
+fn recipe_lifecycle() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo: (bar 'a')
+          @echo foo
+
+        bar x:
+          @echo {{ x }}
+      ",
+    )
+    .args(["--events", "json", "--events-file", "events.jsonl"])
+    .stdout("a\nfoo\n")
+    .run();
+
+  assert_eq!(
+    events(&output),
+    [
+      json!({"event": "recipe_queued", "arguments": [], "dependency": false, "recipe": "foo"}),
+      json!({"event": "recipe_queued", "arguments": ["a"], "dependency": true, "recipe": "bar"}),
+      json!({"event": "recipe_started", "arguments": ["a"], "recipe": "bar"}),
+      json!({"event": "line_started", "command": "echo a", "line": 5, "recipe": "bar"}),
+      json!({
+        "event": "line_finished",
+        "code": 0,
+        "error": null,
+        "line": 5,
+        "recipe": "bar",
+        "signal": null,
+      }),
+      json!({"event": "recipe_finished", "arguments": ["a"], "error": null, "recipe": "bar"}),
+      json!({"event": "recipe_started", "arguments": [], "recipe": "foo"}),
+      json!({"event": "line_started", "command": "echo foo", "line": 2, "recipe": "foo"}),
+      json!({
+        "event": "line_finished",
+        "code": 0,
+        "error": null,
+        "line": 2,
+        "recipe": "foo",
+        "signal": null,
+      }),
+      json!({"event": "recipe_finished", "arguments": [], "error": null, "recipe": "foo"}),
+    ],
+  );
+}
+
+#[test]
+fn recipes_which_already_ran_are_skipped() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo: bar bar
+
+        bar:
+      ",
+    )
+    .args(["--events", "json", "--events-file", "events.jsonl"])
+    .run();
+
+  assert_eq!(
+    events(&output),
+    [
+      json!({"event": "recipe_queued", "arguments": [], "dependency": false, "recipe": "foo"}),
+      json!({"event": "recipe_queued", "arguments": [], "dependency": true, "recipe": "bar"}),
+      json!({"event": "recipe_started", "arguments": [], "recipe": "bar"}),
+      json!({"event": "recipe_finished", "arguments": [], "error": null, "recipe": "bar"}),
+      json!({"event": "recipe_skipped", "arguments": [], "recipe": "bar"}),
+      json!({"event": "recipe_started", "arguments": [], "recipe": "foo"}),
+      json!({"event": "recipe_finished", "arguments": [], "error": null, "recipe": "foo"}),
+    ],
+  );
+}
+
+#[test]
+fn failed_lines_and_recipes() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @exit 3
+      ",
+    )
+    .args(["--events", "json", "--events-file", "events.jsonl"])
+    .stderr("error: Recipe `foo` failed on line 2 with exit code 3\n")
+    .status(3)
+    .run();
+
+  assert_eq!(
+    events(&output),
+    [
+      json!({"event": "recipe_queued", "arguments": [], "dependency": false, "recipe": "foo"}),
+      json!({"event": "recipe_started", "arguments": [], "recipe": "foo"}),
+      json!({"event": "line_started", "command": "exit 3", "line": 2, "recipe": "foo"}),
+      json!({
+        "event": "line_finished",
+        "code": 3,
+        "error": null,
+        "line": 2,
+        "recipe": "foo",
+        "signal": null,
+      }),
+      json!({
+        "event": "recipe_finished",
+        "arguments": [],
+        "error": "Recipe `foo` failed on line 2 with exit code 3",
+        "recipe": "foo",
+      }),
+    ],
+  );
+}
+
+#[test]
+fn lines_which_cannot_be_run_finish_with_error() {
+  let output = Test::new()
+    .justfile(
+      "
+        set shell := ['nonexistent-shell']
+
+        foo:
+          @echo foo
+      ",
+    )
+    .shell(false)
+    .args(["--events", "json", "--events-file", "events.jsonl"])
+    .stderr_regex(
+      "error: Recipe `foo` could not be run because just could not find the shell: .*\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+
+  let events = events(&output);
+
+  assert_eq!(events[3]["event"], "line_finished");
+  assert_eq!(events[3]["code"], Value::Null);
+  assert_eq!(events[3]["line"], 4);
+  assert!(events[3]["error"].is_string());
+}
+
+#[test]
+#[cfg(unix)]
+fn script_recipes() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          #!/bin/sh
+          echo foo
+      ",
+    )
+    .args(["--events", "json", "--events-file", "events.jsonl"])
+    .stdout("foo\n")
+    .run();
+
+  let events = events(&output);
+
+  assert_eq!(events[2]["event"], "line_started");
+  assert_eq!(events[2]["line"], Value::Null);
+  assert!(events[2]["command"].as_str().unwrap().ends_with("foo"));
+  assert_eq!(
+    events[3],
+    json!({
+      "event": "line_finished",
+      "code": 0,
+      "error": null,
+      "line": null,
+      "recipe": "foo",
+      "signal": null,
+    }),
+  );
+}
+
+#[test]
+fn confirmation() {
+  let output = Test::new()
+    .justfile(
+      "
+        [confirm]
+        foo:
+      ",
+    )
+    .args(["--events", "json", "--events-file", "events.jsonl"])
+    .stdin("n")
+    .stderr("Run recipe `foo`? error: Recipe `foo` was not confirmed\n")
+    .status(EXIT_FAILURE)
+    .run();
+
+  assert_eq!(
+    events(&output),
+    [
+      json!({"event": "recipe_queued", "arguments": [], "dependency": false, "recipe": "foo"}),
+      json!({"event": "confirmation_requested", "recipe": "foo"}),
+      json!({"event": "confirmation_answered", "confirmed": false, "recipe": "foo"}),
+    ],
+  );
+}
+
+#[test]
+fn submodule_recipes_are_identified_by_path() {
+  let output = Test::new()
+    .write("foo.just", "bar:\n")
+    .justfile("mod foo")
+    .args([
+      "--events",
+      "json",
+      "--events-file",
+      "events.jsonl",
+      "foo::bar",
+    ])
+    .run();
+
+  assert_eq!(
+    events(&output)[0],
+    json!({"event": "recipe_queued", "arguments": [], "dependency": false, "recipe": "foo::bar"}),
+  );
+}
+
+#[test]
+fn events_are_written_to_stderr_by_default() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--events", "json"])
+    .stderr_regex(
+      r#"\{"time":"[^"]+","event":"recipe_queued","arguments":\[\],"dependency":false,"recipe":"foo"\}
+\{"time":"[^"]+","event":"recipe_started","arguments":\[\],"recipe":"foo"\}
+\{"time":"[^"]+","event":"recipe_finished","arguments":\[\],"duration":[0-9.e-]+,"error":null,"recipe":"foo"\}
+"#,
+    )
+    .run();
+}
+
+#[test]
+fn no_events_without_flag() {
+  Test::new()
+    .justfile("foo:\n @echo foo")
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn events_file_cannot_be_created() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--events", "json", "--events-file", "missing/events.jsonl"])
+    .stderr_regex("error: Failed to create events file `missing/events.jsonl`: .*\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,295 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  mode := 'release'
+
+  build: (compile mode) && notify
+
+  compile profile:
+
+  notify:
+
+  test: build
+";
+
+#[test]
+fn dot() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .arg("--graph")
+    .stdout(
+      r#"
+        digraph justfile {
+          "build"
+          "compile"
+          "notify"
+          "test"
+          "build" -> "compile" [label = "mode"]
+          "build" -> "notify" [style = dashed]
+          "test" -> "build"
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn mermaid() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--graph", "--graph-format", "mermaid"])
+    .stdout(
+      r#"
+        flowchart LR
+          n0["build"]
+          n1["compile"]
+          n2["notify"]
+          n3["test"]
+          n0 -->|"mode"| n1
+          n0 -.-> n2
+          n3 --> n0
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn recipes_limit_graph_to_reachable_recipes() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--graph", "build"])
+    .stdout(
+      r#"
+        digraph justfile {
+          "build"
+          "compile"
+          "notify"
+          "build" -> "compile" [label = "mode"]
+          "build" -> "notify" [style = dashed]
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn arguments_are_evaluated_with_graph_eval() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--graph", "--graph-eval", "build"])
+    .stdout(
+      r#"
+        digraph justfile {
+          "build"
+          "compile"
+          "notify"
+          "build" -> "compile" [label = "'release'"]
+          "build" -> "notify" [style = dashed]
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn backticks_are_not_run() {
+  Test::new()
+    .justfile(
+      "
+        x := `touch bar.txt; echo x`
+
+        foo: (bar x)
+
+        bar y:
+      ",
+    )
+    .arg("--graph")
+    .stdout(
+      r#"
+        digraph justfile {
+          "bar"
+          "foo"
+          "foo" -> "bar" [label = "x"]
+        }
+      "#,
+    )
+    .run()
+    .tempdir
+    .path()
+    .join("bar.txt")
+    .try_exists()
+    .map(|exists| assert!(!exists))
+    .unwrap();
+}
+
+#[test]
+fn overrides_are_used_to_evaluate_arguments() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--graph", "--graph-eval", "mode=debug", "build"])
+    .stdout(
+      r#"
+        digraph justfile {
+          "build"
+          "compile"
+          "notify"
+          "build" -> "compile" [label = "'debug'"]
+          "build" -> "notify" [style = dashed]
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn arguments_which_depend_on_parameters_are_not_evaluated() {
+  Test::new()
+    .justfile(
+      "
+        build target: (compile target + '-x' 'y')
+
+        compile a b:
+      ",
+    )
+    .args(["--graph", "--graph-eval"])
+    .stdout(
+      r#"
+        digraph justfile {
+          "build"
+          "compile"
+          "build" -> "compile" [label = "target + '-x' 'y'"]
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn labels_are_escaped() {
+  Test::new()
+    .justfile(
+      r#"
+        foo: (bar "it's \"quoted\"")
+
+        bar x:
+      "#,
+    )
+    .arg("--graph")
+    .stdout(
+      r#"
+        digraph justfile {
+          "bar"
+          "foo"
+          "foo" -> "bar" [label = "\"it's \\\"quoted\\\"\""]
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn modules() {
+  Test::new()
+    .write("foo.just", "bar:\n")
+    .justfile(
+      "
+        mod foo
+
+        baz: foo::bar
+      ",
+    )
+    .arg("--graph")
+    .stdout(
+      r#"
+        digraph justfile {
+          "baz"
+          subgraph "cluster_foo" {
+            label = "foo"
+            "foo::bar"
+          }
+          "baz" -> "foo::bar"
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn modules_mermaid() {
+  Test::new()
+    .write("foo.just", "bar:\n")
+    .justfile(
+      "
+        mod foo
+
+        baz: foo::bar
+      ",
+    )
+    .args(["--graph", "--graph-format", "mermaid", "baz"])
+    .stdout(
+      r#"
+        flowchart LR
+          n0["baz"]
+          subgraph m1 ["foo"]
+            n1["foo::bar"]
+          end
+          n0 --> n1
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn submodule_recipe() {
+  Test::new()
+    .write("foo.just", "bar: baz\nbaz:\n")
+    .justfile("mod foo")
+    .args(["--graph", "foo::bar"])
+    .stdout(
+      r#"
+        digraph justfile {
+          subgraph "cluster_foo" {
+            label = "foo"
+            "foo::bar"
+            "foo::baz"
+          }
+          "foo::bar" -> "foo::baz"
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn unknown_recipe() {
+  Test::new()
+    .justfile("build:")
+    .args(["--graph", "buidl"])
+    .stderr(
+      "
+        error: Justfile does not contain recipe `buidl`
+        Did you mean `build`?
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn graph_eval_requires_graph() {
+  Test::new()
+    .arg("--graph-eval")
+    .stderr_regex("error: the following required arguments were not provided:\n  --graph\n(.|\n)*")
+    .status(2)
+    .run();
+}
+
+#[test]
+fn unknown_submodule() {
+  Test::new()
+    .justfile("build:")
+    .args(["--graph", "foo::build"])
+    .stderr("error: Justfile does not contain submodule `foo`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
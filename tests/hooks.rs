@@ -0,0 +1,453 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  [finally('stop')]
+  [on-failure('notify')]
+  test: start
+    @echo test
+    @exit {{ env('CODE', '0') }}
+
+  start:
+    @echo start
+
+  stop:
+    @echo stop {{ recipe_failed() }}
+
+  notify:
+    @echo notify {{ recipe_failed() }}
+";
+
+#[test]
+fn finally_runs_after_success() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .stdout("start\ntest\nstop false\n")
+    .run();
+}
+
+#[test]
+fn hooks_run_after_failure() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .env("CODE", "3")
+    .stdout("start\ntest\nnotify true\nstop true\n")
+    .stderr("error: Recipe `test` failed on line 5 with exit code 3\n")
+    .status(3)
+    .run();
+}
+
+#[test]
+fn hooks_run_before_subsequents() {
+  Test::new()
+    .justfile(
+      "
+        [finally('stop')]
+        foo: && bar
+          @echo foo
+
+        bar:
+          @echo bar
+
+        stop:
+          @echo stop
+      ",
+    )
+    .stdout("foo\nstop\nbar\n")
+    .run();
+}
+
+#[test]
+fn hooks_run_in_order() {
+  Test::new()
+    .justfile(
+      "
+        [finally('b', 'a')]
+        foo:
+
+        a:
+          @echo a
+
+        b:
+          @echo b
+      ",
+    )
+    .stdout("b\na\n")
+    .run();
+}
+
+#[test]
+fn hooks_run_even_if_they_already_ran() {
+  Test::new()
+    .justfile(
+      "
+        [finally('stop')]
+        foo: stop
+          @echo foo
+
+        stop:
+          @echo stop
+      ",
+    )
+    .stdout("stop\nfoo\nstop\n")
+    .run();
+}
+
+#[test]
+fn hook_dependencies_run() {
+  Test::new()
+    .justfile(
+      "
+        [finally('stop')]
+        foo:
+          @echo foo
+
+        stop: flush
+          @echo stop
+
+        flush:
+          @echo flush
+      ",
+    )
+    .stdout("foo\nflush\nstop\n")
+    .run();
+}
+
+#[test]
+fn hooks_do_not_run_if_priors_fail() {
+  Test::new()
+    .justfile(
+      "
+        [finally('stop')]
+        foo: bar
+          @echo foo
+
+        bar:
+          @exit 1
+
+        stop:
+          @echo stop
+      ",
+    )
+    .stderr("error: Recipe `bar` failed on line 6 with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn hook_failure() {
+  Test::new()
+    .justfile(
+      "
+        [finally('stop')]
+        foo:
+          @echo foo
+
+        stop:
+          @exit 2
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("error: Recipe `stop` failed on line 6 with exit code 2\n")
+    .status(2)
+    .run();
+}
+
+#[test]
+fn hook_failure_does_not_replace_recipe_failure() {
+  Test::new()
+    .justfile(
+      "
+        [on-failure('notify')]
+        foo:
+          @exit 3
+
+        notify:
+          @echo notify
+          @exit 4
+      ",
+    )
+    .stdout("notify\n")
+    .stderr("error: Recipe `foo` failed on line 3 with exit code 3\n")
+    .status(3)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn hooks_run_after_parallel_failure() {
+  Test::new()
+    .justfile(
+      "
+        [parallel]
+        all: foo bar
+
+        foo:
+          @exit 3
+
+        [finally('stop')]
+        bar:
+          @sleep 0.5
+          @echo bar
+
+        stop: start
+          @echo stop
+
+        start:
+          @echo start
+      ",
+    )
+    .stdout("bar\nstart\nstop\n")
+    .stderr("error: Recipe `foo` failed on line 5 with exit code 3\n")
+    .status(3)
+    .run();
+}
+
+#[test]
+fn hooks_with_keep_going() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .env("CODE", "3")
+    .args(["--keep-going", "test"])
+    .stdout("start\ntest\nnotify true\nstop true\n")
+    .stderr(
+      "
+        error: Recipe `test` failed on line 5 with exit code 3
+        ===> 3 passed, 1 failed, 0 skipped
+        start   passed
+        notify  passed
+        stop    passed
+        test    failed
+        error: 1 recipe failed.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recipe_failed_outside_of_hook() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo {{ recipe_failed() }}
+      ",
+    )
+    .stderr(
+      "
+        error: Call to function `recipe_failed` failed: `recipe_failed()` may only be called from a `[finally]` or `[on-failure]` hook
+         ——▶ justfile:2:12
+          │
+        2 │   @echo {{ recipe_failed() }}
+          │            ^^^^^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn unknown_hook() {
+  Test::new()
+    .justfile(
+      "
+        [finally('bar')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` has unknown hook `bar`
+         ——▶ justfile:2:1
+          │
+        2 │ foo:
+          │ ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn hook_which_requires_arguments() {
+  Test::new()
+    .justfile(
+      "
+        [on-failure('bar')]
+        foo:
+
+        bar x:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` has hook `bar` which requires arguments
+         ——▶ justfile:2:1
+          │
+        2 │ foo:
+          │ ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn circular_hooks() {
+  Test::new()
+    .justfile(
+      "
+        [finally('bar')]
+        foo:
+
+        [finally('foo')]
+        bar:
+      ",
+    )
+    .stderr_regex("error: Recipe `(foo|bar)` has circular hook `(foo -> bar -> foo|bar -> foo -> bar)`\n(.|\n)*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recipe_is_its_own_hook() {
+  Test::new()
+    .justfile(
+      "
+        [finally('foo')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` is its own hook
+         ——▶ justfile:2:1
+          │
+        2 │ foo:
+          │ ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+/// Run `justfile` with `arguments`, sending SIGINT to `just` after each of
+/// `delays`
+#[cfg(unix)]
+fn interrupt(justfile: &str, arguments: &[&str], delays: &[u64]) -> std::process::Output {
+  use nix::{sys::signal::Signal, unistd::Pid};
+
+  let tempdir = tempdir();
+
+  fs::write(tempdir.path().join("justfile"), unindent(justfile)).unwrap();
+
+  let child = Command::new(executable_path("just"))
+    .args(arguments)
+    .current_dir(&tempdir)
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+
+  for &delay in delays {
+    thread::sleep(Duration::from_millis(delay));
+
+    nix::sys::signal::kill(
+      Pid::from_raw(child.id().try_into().unwrap()),
+      Signal::SIGINT,
+    )
+    .unwrap();
+  }
+
+  child.wait_with_output().unwrap()
+}
+
+#[test]
+#[cfg(unix)]
+fn hooks_run_after_interrupt() {
+  let output = interrupt(
+    "
+      [finally('stop')]
+      foo:
+        @sleep 1
+        @echo unreachable
+
+      stop:
+        @echo stop {{ recipe_failed() }}
+    ",
+    &[],
+    &[500],
+  );
+
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "stop true\n");
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "error: Recipe `foo` was terminated on line 3 by signal 2\n",
+  );
+  assert_eq!(output.status.code(), Some(130));
+}
+
+#[test]
+#[cfg(unix)]
+fn hooks_run_after_interrupt_while_no_child_is_running() {
+  let output = interrupt(
+    "
+      [finally('stop')]
+      [retry('1', '1s')]
+      foo:
+        @exit 1
+
+      stop:
+        @echo stop {{ recipe_failed() }}
+    ",
+    &[],
+    &[500],
+  );
+
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "stop true\n");
+  assert_eq!(output.status.code(), Some(130));
+}
+
+#[test]
+#[cfg(unix)]
+fn interrupt_stops_run_with_keep_going() {
+  let output = interrupt(
+    "
+      [finally('stop')]
+      foo:
+        @sleep 1
+
+      bar:
+        @echo bar
+
+      stop:
+        @echo stop
+    ",
+    &["--keep-going", "foo", "bar"],
+    &[500],
+  );
+
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "stop\n");
+  assert_eq!(output.status.code(), Some(130));
+}
+
+#[test]
+#[cfg(unix)]
+fn interrupt_during_hooks_is_not_ignored() {
+  let output = interrupt(
+    "
+      [finally('stop')]
+      foo:
+        @sleep 1
+
+      stop:
+        @trap '' INT && sleep 1
+        @echo unreachable
+    ",
+    &[],
+    &[500, 500],
+  );
+
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "");
+  assert_eq!(output.status.code(), Some(130));
+}
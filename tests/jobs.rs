@@ -0,0 +1,173 @@
+use super::*;
+
+#[test]
+fn invocations_run_concurrently() {
+  Test::new()
+    .justfile(
+      "
+        a:
+          @for i in `seq 100`; do test -f b && exit 0; sleep 0.1; done; exit 1
+
+        b:
+          @touch b
+      ",
+    )
+    .args(["--jobs", "2", "a", "b"])
+    .run();
+}
+
+#[test]
+fn dependencies_run_concurrently() {
+  Test::new()
+    .justfile(
+      "
+        foo: a b
+
+        a:
+          @for i in `seq 100`; do test -f b && exit 0; sleep 0.1; done; exit 1
+
+        b:
+          @touch b
+      ",
+    )
+    .args(["--jobs", "2", "foo"])
+    .run();
+}
+
+#[test]
+fn dependencies_run_before_dependents() {
+  Test::new()
+    .justfile(
+      "
+        a: b c
+          @echo a
+
+        b: c
+          @echo b
+
+        c:
+          @echo c
+      ",
+    )
+    .args(["--jobs", "4", "a"])
+    .stdout(
+      "
+        c
+        b
+        a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn shared_dependencies_run_once() {
+  Test::new()
+    .justfile(
+      "
+        a: c
+        b: c
+        c:
+          @echo c
+      ",
+    )
+    .args(["--jobs", "4", "a", "b"])
+    .stdout("c\n")
+    .run();
+}
+
+#[test]
+fn subsequents_run_after_body() {
+  Test::new()
+    .justfile(
+      "
+        a: && b c
+          @echo a
+
+        b:
+          @echo b
+
+        c: b
+          @echo c
+      ",
+    )
+    .args(["--jobs", "4", "a"])
+    .stdout(
+      "
+        a
+        b
+        c
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn failure_stops_run() {
+  Test::new()
+    .justfile(
+      "
+        a: b
+          @echo a
+
+        b:
+          @exit 1
+      ",
+    )
+    .args(["--jobs", "4", "a"])
+    .stderr("error: Recipe `b` failed on line 5 with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn single_job_runs_invocations_in_order() {
+  Test::new()
+    .justfile(
+      "
+        a:
+          @echo a
+
+        b:
+          @echo b
+
+        c:
+          @echo c
+      ",
+    )
+    .args(["--jobs", "1", "c", "a", "b"])
+    .stdout(
+      "
+        c
+        a
+        b
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn environment_variable() {
+  Test::new()
+    .justfile(
+      "
+        a:
+          @for i in `seq 100`; do test -f b && exit 0; sleep 0.1; done; exit 1
+
+        b:
+          @touch b
+      ",
+    )
+    .env("JUST_JOBS", "2")
+    .args(["a", "b"])
+    .run();
+}
+
+#[test]
+fn zero_jobs_is_an_error() {
+  Test::new()
+    .args(["--jobs", "0"])
+    .stderr_regex("error: invalid value '0' for '--jobs <JOBS>'.*")
+    .status(2)
+    .run();
+}
@@ -14,6 +14,7 @@ struct Assignment<'a> {
   export: bool,
   name: &'a str,
   private: bool,
+  secret: bool,
   value: &'a str,
 }
 
@@ -44,7 +45,7 @@ struct Module<'a> {
   settings: Settings<'a>,
   source: PathBuf,
   unexports: Vec<&'a str>,
-  warnings: Vec<&'a str>,
+  warnings: Vec<Warning<'a>>,
 }
 
 #[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -54,6 +55,8 @@ struct Parameter<'a> {
   export: bool,
   kind: &'a str,
   name: &'a str,
+  #[serde(rename = "type")]
+  ty: Option<Value>,
 }
 
 #[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -78,6 +81,7 @@ struct Settings<'a> {
   allow_duplicate_recipes: bool,
   allow_duplicate_variables: bool,
   dotenv_filename: Option<&'a str>,
+  dotenv_files: Option<Vec<&'a str>>,
   dotenv_load: bool,
   dotenv_override: bool,
   dotenv_path: Option<&'a str>,
@@ -91,11 +95,19 @@ struct Settings<'a> {
   shell: Option<Interpreter<'a>>,
   tempdir: Option<&'a str>,
   unstable: bool,
+  variadic_lists: bool,
   windows_powershell: bool,
   windows_shell: Option<&'a str>,
   working_directory: Option<&'a str>,
 }
 
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+struct Warning<'a> {
+  lint: &'a str,
+  message: &'a str,
+}
+
 #[track_caller]
 fn case(justfile: &str, expected: Module) {
   case_with_submodule(justfile, None, expected);
@@ -185,6 +197,10 @@ fn assignment() {
         },
       )]
       .into(),
+      warnings: vec![Warning {
+        lint: "unused-variable",
+        message: "Variable `foo` is never used",
+      }],
       ..default()
     },
   );
@@ -220,6 +236,16 @@ fn private_assignment() {
         ),
       ]
       .into(),
+      warnings: vec![
+        Warning {
+          lint: "unused-variable",
+          message: "Variable `_foo` is never used",
+        },
+        Warning {
+          lint: "unused-variable",
+          message: "Variable `bar` is never used",
+        },
+      ],
       ..default()
     },
   );
@@ -363,6 +389,16 @@ fn dependency_argument() {
         ),
       ]
       .into(),
+      warnings: vec![
+        Warning {
+          lint: "unused-parameter",
+          message: "Recipe `foo` parameter `args` is never used",
+        },
+        Warning {
+          lint: "deprecated-function",
+          message: "Function `env_var` is deprecated, use `env` instead",
+        },
+      ],
       ..default()
     },
   );
@@ -408,6 +444,10 @@ fn duplicate_recipes() {
         allow_duplicate_recipes: true,
         ..default()
       },
+      warnings: vec![Warning {
+        lint: "unused-parameter",
+        message: "Recipe `foo` parameter `bar` is never used",
+      }],
       ..default()
     },
   );
@@ -435,6 +475,16 @@ fn duplicate_variables() {
         allow_duplicate_variables: true,
         ..default()
       },
+      warnings: vec![
+        Warning {
+          lint: "unused-variable",
+          message: "Variable `x` is never used",
+        },
+        Warning {
+          lint: "unused-variable",
+          message: "Variable `x` is never used",
+        },
+      ],
       ..default()
     },
   );
@@ -562,6 +612,81 @@ fn parameters() {
         ),
       ]
       .into(),
+      warnings: vec![
+        Warning {
+          lint: "unused-parameter",
+          message: "Recipe `b` parameter `x` is never used",
+        },
+        Warning {
+          lint: "unused-parameter",
+          message: "Recipe `c` parameter `x` is never used",
+        },
+        Warning {
+          lint: "unused-parameter",
+          message: "Recipe `d` parameter `x` is never used",
+        },
+        Warning {
+          lint: "unused-parameter",
+          message: "Recipe `e` parameter `x` is never used",
+        },
+      ],
+      ..default()
+    },
+  );
+}
+
+#[test]
+fn parameter_types() {
+  case(
+    "
+      a x: bool y: int z: ('foo' | 'bar'):
+    ",
+    Module {
+      first: Some("a"),
+      recipes: [(
+        "a",
+        Recipe {
+          name: "a",
+          namepath: "a",
+          parameters: [
+            Parameter {
+              kind: "singular",
+              name: "x",
+              ty: Some(json!("bool")),
+              ..default()
+            },
+            Parameter {
+              kind: "singular",
+              name: "y",
+              ty: Some(json!("int")),
+              ..default()
+            },
+            Parameter {
+              kind: "singular",
+              name: "z",
+              ty: Some(json!({"choice": ["foo", "bar"]})),
+              ..default()
+            },
+          ]
+          .into(),
+          ..default()
+        },
+      )]
+      .into(),
+      warnings: vec![
+        Warning {
+          lint: "unused-parameter",
+          message: "Recipe `a` parameter `x` is never used",
+        },
+        Warning {
+          lint: "unused-parameter",
+          message: "Recipe `a` parameter `y` is never used",
+        },
+        Warning {
+          lint: "unused-parameter",
+          message: "Recipe `a` parameter `z` is never used",
+        },
+      ],
       ..default()
     },
   );
@@ -981,3 +1106,28 @@ fn doc_attribute_overrides_comment() {
     },
   );
 }
+
+#[test]
+fn retry_and_timeout_attributes() {
+  case(
+    "
+      [retry('3', '1s')]
+      [timeout('5m')]
+      foo:
+    ",
+    Module {
+      first: Some("foo"),
+      recipes: [(
+        "foo",
+        Recipe {
+          attributes: [json!({"retry": ["3", "1s"]}), json!({"timeout": "5m"})].into(),
+          name: "foo",
+          namepath: "foo",
+          ..default()
+        },
+      )]
+      .into(),
+      ..default()
+    },
+  );
+}
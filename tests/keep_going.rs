@@ -0,0 +1,202 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  lint:
+    @echo lint
+
+  build:
+    @echo build
+
+  test: build
+    @exit 3
+
+  docs: test
+    @echo docs
+";
+
+#[test]
+fn remaining_invocations_run_after_failure() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "test", "lint"])
+    .stdout("build\nlint\n")
+    .stderr(
+      "
+        error: Recipe `test` failed on line 8 with exit code 3
+        ===> 2 passed, 1 failed, 0 skipped
+        build  passed
+        test   failed
+        lint   passed
+        error: 1 recipe failed.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dependents_of_failed_recipes_are_skipped() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "docs", "lint"])
+    .stdout("build\nlint\n")
+    .stderr(
+      "
+        error: Recipe `test` failed on line 8 with exit code 3
+        ===> 2 passed, 1 failed, 1 skipped
+        build  passed
+        test   failed
+        docs   skipped
+        lint   passed
+        error: 1 recipe failed.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recipes_which_depend_on_already_failed_recipes_are_skipped() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "test", "docs"])
+    .stdout("build\n")
+    .stderr(
+      "
+        error: Recipe `test` failed on line 8 with exit code 3
+        ===> 1 passed, 1 failed, 1 skipped
+        build  passed
+        test   failed
+        docs   skipped
+        error: 1 recipe failed.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dependents_of_recipes_with_failed_subsequents_are_skipped() {
+  Test::new()
+    .justfile(
+      "
+        deploy: build
+
+        build: && notify
+          @echo build
+
+        notify:
+          @exit 1
+      ",
+    )
+    .args(["--keep-going", "deploy"])
+    .stdout("build\n")
+    .stderr(
+      "
+        error: Recipe `notify` failed on line 7 with exit code 1
+        ===> 1 passed, 1 failed, 1 skipped
+        notify  failed
+        build   passed
+        deploy  skipped
+        error: 1 recipe failed.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn summary_is_printed_on_success() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "lint", "build"])
+    .stdout("lint\nbuild\n")
+    .stderr(
+      "
+        ===> 2 passed, 0 failed, 0 skipped
+        lint   passed
+        build  passed
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn parallel() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "--jobs", "4", "docs", "lint"])
+    .stdout_regex("^(build\nlint\n|lint\nbuild\n)$")
+    .stderr_regex(
+      "(?s)error: Recipe `test` failed on line 8 with exit code 3\n.*===> 2 passed, 1 failed, 1 skipped\n.*docs   skipped\n.*error: 1 recipe failed.\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn multiple_failures() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @exit 1
+
+        bar:
+          @exit 2
+      ",
+    )
+    .args(["--keep-going", "foo", "bar"])
+    .stderr(
+      "
+        error: Recipe `foo` failed on line 2 with exit code 1
+        error: Recipe `bar` failed on line 5 with exit code 2
+        ===> 0 passed, 2 failed, 0 skipped
+        foo  failed
+        bar  failed
+        error: 2 recipes failed.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn arguments_are_included_in_summary() {
+  Test::new()
+    .justfile(
+      "
+        foo x:
+          @echo {{ x }}
+      ",
+    )
+    .args(["--keep-going", "foo", "bar"])
+    .stdout("bar\n")
+    .stderr(
+      "
+        ===> 1 passed, 0 failed, 0 skipped
+        foo bar  passed
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn quiet_suppresses_errors_and_summary() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "--quiet", "test", "lint"])
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn without_keep_going_first_failure_stops_run() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["test", "lint"])
+    .stdout("build\n")
+    .stderr("error: Recipe `test` failed on line 8 with exit code 3\n")
+    .status(3)
+    .run();
+}
@@ -16,6 +16,7 @@ use {
     collections::BTreeMap,
     env::{self, consts::EXE_SUFFIX},
     error::Error,
+    ffi::OsStr,
     fmt::Debug,
     fs,
     io::Write,
@@ -52,12 +53,14 @@ mod assignment;
 mod attributes;
 mod backticks;
 mod byte_order_mark;
+mod cache;
 mod ceiling;
 mod changelog;
 mod choose;
 mod command;
 mod completions;
 mod conditional;
+mod config_file;
 mod confirm;
 mod constants;
 mod datetime;
@@ -67,9 +70,11 @@ mod dependencies;
 mod directories;
 mod dotenv;
 mod edit;
+mod env_attribute;
 mod equals;
 mod error_messages;
 mod evaluate;
+mod events;
 mod examples;
 mod explain;
 mod export;
@@ -78,15 +83,22 @@ mod format;
 mod functions;
 #[cfg(unix)]
 mod global;
+mod graph;
 mod groups;
+mod hooks;
 mod ignore_comments;
 mod imports;
 mod init;
 mod invocation_directory;
+mod jobs;
 mod json;
+mod keep_going;
 mod line_prefixes;
+mod lint;
 mod list;
+mod lists;
 mod logical_operators;
+mod lsp;
 mod man;
 mod misc;
 mod modules;
@@ -97,22 +109,31 @@ mod no_cd;
 mod no_dependencies;
 mod no_exit_message;
 mod os_attributes;
+mod outputs;
 mod parallel;
+mod parameter_types;
 mod parameters;
 mod parser;
+mod plan;
 mod positional_arguments;
 mod private;
+mod prompt;
 mod quiet;
 mod quote;
 mod readme;
+mod recipe_help;
+mod recipe_options;
 mod recursion_limit;
 mod regexes;
+mod report;
 mod request;
+mod retry;
 mod run;
 mod scope;
 mod script;
 mod search;
 mod search_arguments;
+mod secrets;
 mod shadowing_parameters;
 mod shebang;
 mod shell;
@@ -125,10 +146,14 @@ mod string;
 mod subsequents;
 mod summary;
 mod tempdir;
+mod timeout;
 mod timestamps;
 mod undefined_variables;
 mod unexport;
 mod unstable;
+mod user_functions;
+#[cfg(unix)]
+mod watch;
 mod which_function;
 #[cfg(windows)]
 mod windows;
@@ -0,0 +1,456 @@
+use super::*;
+
+#[test]
+fn no_warnings() {
+  Test::new()
+    .justfile(
+      "
+        x := 'a'
+
+        foo y:
+          echo {{ x }} {{ y }}
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn unused_variable() {
+  Test::new()
+    .justfile(
+      "
+        x := 'a'
+
+        foo:
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning[unused-variable]: Variable `x` is never used
+         ——▶ justfile:1:1
+          │
+        1 │ x := 'a'
+          │ ^
+
+        error: Found 1 lint warning.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn variables_used_by_assignments_and_dependencies_are_used() {
+  Test::new()
+    .justfile(
+      "
+        a := 'a'
+        b := a
+        c := 'c'
+
+        fn f(x) := x + 'd'
+
+        foo: (bar f(b) c)
+
+        bar x y:
+          echo {{ x }} {{ y }}
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn exported_variables_are_used() {
+  Test::new()
+    .justfile(
+      "
+        export x := 'a'
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn export_setting_disables_unused_lints() {
+  Test::new()
+    .justfile(
+      "
+        set export
+
+        x := 'a'
+
+        foo y:
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn unused_parameter() {
+  Test::new()
+    .justfile(
+      "
+        foo bar:
+          echo foo
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning[unused-parameter]: Recipe `foo` parameter `bar` is never used
+         ——▶ justfile:1:5
+          │
+        1 │ foo bar:
+          │     ^^^
+
+        error: Found 1 lint warning.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn positional_and_exported_parameters_are_used() {
+  Test::new()
+    .justfile(
+      "
+        [positional-arguments]
+        foo bar:
+          echo $1
+
+        baz $qux:
+          echo $qux
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn parameter_shadows_variable() {
+  Test::new()
+    .justfile(
+      "
+        x := 'a'
+
+        foo x:
+          echo {{ x }}
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning[unused-variable]: Variable `x` is never used
+         ——▶ justfile:1:1
+          │
+        1 │ x := 'a'
+          │ ^
+
+        warning[parameter-shadows-variable]: Recipe `foo` parameter `x` shadows variable `x`
+         ——▶ justfile:3:5
+          │
+        3 │ foo x:
+          │     ^
+
+        error: Found 2 lint warnings.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn deprecated_function() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          echo {{ env_var_or_default('FOO', 'bar') }}
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning[deprecated-function]: Function `env_var_or_default` is deprecated, use `env` instead
+         ——▶ justfile:2:11
+          │
+        2 │   echo {{ env_var_or_default('FOO', 'bar') }}
+          │           ^^^^^^^^^^^^^^^^^^
+
+        error: Found 1 lint warning.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recipe_shadows_alias() {
+  Test::new()
+    .justfile(
+      "
+        alias build := compile
+
+        compile:
+
+        [openbsd]
+        build:
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning[recipe-shadows-alias]: Recipe `build` conflicts with alias `build` on line 1 on platforms where it is enabled
+         ——▶ justfile:6:1
+          │
+        6 │ build:
+          │ ^^^^^
+
+        error: Found 1 lint warning.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recipe_shadows_alias_allowed_on_alias() {
+  Test::new()
+    .justfile(
+      "
+        [allow('recipe-shadows-alias')]
+        alias build := compile
+
+        compile:
+
+        [openbsd]
+        build:
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn setting_overridden_by_import() {
+  Test::new()
+    .write("import.just", "set quiet\n\nfoo:\n")
+    .justfile(
+      "
+        import 'import.just'
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning[setting-overridden-by-import]: Setting `quiet` is set in an imported file, overriding it for the importing justfile
+         ——▶ import.just:1:5
+          │
+        1 │ set quiet
+          │     ^^^^^
+
+        error: Found 1 lint warning.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn setting_overridden_by_import_allowed() {
+  Test::new()
+    .write(
+      "import.just",
+      "[allow('setting-overridden-by-import')]\nset quiet\n",
+    )
+    .justfile(
+      "
+        import 'import.just'
+
+        set export
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn settings_in_importing_justfile_are_not_linted() {
+  Test::new()
+    .justfile(
+      "
+        set quiet
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn settings_only_accept_allow_attribute() {
+  Test::new()
+    .justfile(
+      "
+        [private]
+        set quiet
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        error: Setting `quiet` has invalid attribute `private`
+         ——▶ justfile:2:5
+          │
+        2 │ set quiet
+          │     ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn unreachable_recipe() {
+  Test::new()
+    .justfile(
+      "
+        set allow-duplicate-recipes
+
+        [linux]
+        [macos]
+        build:
+
+        build:
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning[unreachable-recipe]: Recipe `build` is unreachable, since recipe `build` on line 7 is enabled on all of its platforms
+         ——▶ justfile:5:1
+          │
+        5 │ build:
+          │ ^^^^^
+
+        error: Found 1 lint warning.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn partially_shadowed_recipes_are_reachable() {
+  Test::new()
+    .justfile(
+      "
+        set allow-duplicate-recipes
+
+        [unix]
+        build:
+
+        [linux]
+        build:
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn allow_attribute() {
+  Test::new()
+    .justfile(
+      "
+        [allow('unused-variable')]
+        x := 'a'
+
+        [allow('deprecated-function')]
+        fn f(y) := env_var(y)
+
+        [allow('unused-parameter', 'parameter-shadows-variable')]
+        foo x:
+
+        [allow('unused-parameter')]
+        [allow('deprecated-function')]
+        bar z:
+          echo {{ f('HOME') }} {{ env_var('HOME') }}
+      ",
+    )
+    .arg("--lint")
+    .run();
+}
+
+#[test]
+fn allow_unknown_lint() {
+  Test::new()
+    .justfile(
+      "
+        [allow('unused-foo')]
+        x := 'a'
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        error: Unknown lint `unused-foo`
+         ——▶ justfile:1:2
+          │
+        1 │ [allow('unused-foo')]
+          │  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn warnings_in_submodules() {
+  Test::new()
+    .write("foo.just", "x := 'a'\n")
+    .justfile("mod foo")
+    .arg("--lint")
+    .stderr(
+      "
+        warning[unused-variable]: Variable `x` is never used
+         ——▶ foo.just:1:1
+          │
+        1 │ x := 'a'
+          │ ^
+
+        error: Found 1 lint warning.
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn warnings_are_not_printed_when_running_recipes() {
+  Test::new()
+    .justfile(
+      "
+        x := 'a'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn quiet_suppresses_warnings() {
+  Test::new()
+    .justfile("x := 'a'")
+    .args(["--lint", "--quiet"])
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,340 @@
+use super::*;
+
+#[test]
+fn list_literals_are_joined_with_spaces_when_interpolated() {
+  Test::new()
+    .justfile(
+      "
+        x := ['a', 'b', 'c']
+
+        foo:
+          @echo {{ x }}
+      ",
+    )
+    .stdout("a b c\n")
+    .run();
+}
+
+#[test]
+fn nested_lists_are_flattened() {
+  Test::new()
+    .justfile(
+      "
+        x := ['a', ['b', 'c'], []]
+
+        foo:
+          @echo {{ len(x) }}
+      ",
+    )
+    .stdout("3\n")
+    .run();
+}
+
+#[test]
+fn lists_may_span_multiple_lines() {
+  Test::new()
+    .justfile(
+      "
+        x := [
+          'a',
+          'b',
+        ]
+
+        foo:
+          @echo {{ x }}
+      ",
+    )
+    .stdout("a b\n")
+    .run();
+}
+
+#[test]
+fn list_concatenation() {
+  Test::new()
+    .justfile(
+      "
+        x := ['a'] + ['b', 'c']
+
+        foo:
+          @echo {{ len(x) }} {{ x }}
+      ",
+    )
+    .stdout("3 a b c\n")
+    .run();
+}
+
+#[test]
+fn string_concatenation_with_list() {
+  Test::new()
+    .justfile(
+      "
+        x := 'a-' + ['b', 'c']
+
+        foo:
+          @echo {{ len(x) }} {{ x }}
+      ",
+    )
+    .stdout("5 a-b c\n")
+    .run();
+}
+
+#[test]
+fn len() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo {{ len([]) }} {{ len(['a', 'b']) }} {{ len('abc') }}
+      ",
+    )
+    .stdout("0 2 3\n")
+    .run();
+}
+
+#[test]
+fn split() {
+  Test::new()
+    .justfile(
+      "
+        x := split('a,b,,c', ',')
+
+        foo:
+          @echo '{{ len(x) }} {{ x }} {{ len(split('', ',')) }}'
+      ",
+    )
+    .stdout("4 a b  c 0\n")
+    .run();
+}
+
+#[test]
+fn split_with_empty_separator() {
+  Test::new()
+    .justfile(
+      "
+        x := split('abc', '')
+      ",
+    )
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+        error: Call to function `split` failed: separator may not be empty
+         ——▶ justfile:1:6
+          │
+        1 │ x := split('abc', '')
+          │      ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn index() {
+  Test::new()
+    .justfile(
+      "
+        x := ['a', 'b', 'c']
+
+        foo:
+          @echo {{ index(x, '0') }} {{ index(x, '2') }}
+      ",
+    )
+    .stdout("a c\n")
+    .run();
+}
+
+#[test]
+fn index_out_of_range() {
+  Test::new()
+    .justfile(
+      "
+        x := index(['a', 'b'], '2')
+      ",
+    )
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+        error: Call to function `index` failed: index 2 out of range for list of length 2
+         ——▶ justfile:1:6
+          │
+        1 │ x := index(['a', 'b'], '2')
+          │      ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn map_with_builtin_function() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo {{ map(['a', 'b'], uppercase) }}
+      ",
+    )
+    .stdout("A B\n")
+    .run();
+}
+
+#[test]
+fn map_with_user_defined_function() {
+  Test::new()
+    .justfile(
+      "
+        fn object(s) := s + '.o'
+
+        foo:
+          @echo {{ map(['a', 'b'], object) }}
+      ",
+    )
+    .stdout("a.o b.o\n")
+    .run();
+}
+
+#[test]
+fn filter_keeps_non_empty_results() {
+  Test::new()
+    .justfile(
+      "
+        fn is_c(s) := if extension(s) == 'c' { 'true' } else { '' }
+
+        foo:
+          @echo {{ filter(['a.c', 'b.h', 'c.c'], is_c) }}
+      ",
+    )
+    .stdout("a.c c.c\n")
+    .run();
+}
+
+#[test]
+fn map_second_argument_must_be_function_name() {
+  Test::new()
+    .justfile("x := map(['a'], 'uppercase')")
+    .stderr(
+      "
+        error: Second argument to `map` must be the name of a function
+         ——▶ justfile:1:6
+          │
+        1 │ x := map(['a'], 'uppercase')
+          │      ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn variadic_parameters_are_lists_with_setting() {
+  Test::new()
+    .justfile(
+      "
+        set variadic-lists
+
+        foo *args:
+          @echo {{ len(args) }} {{ index(args, '1') }} {{ join('x', args) }}
+      ",
+    )
+    .args(["foo", "a b", "c"])
+    .stdout("2 c x/a b/c\n")
+    .run();
+}
+
+#[test]
+fn variadic_parameters_are_strings_without_setting() {
+  Test::new()
+    .justfile(
+      "
+        foo *args:
+          @echo {{ join('x', args) }}
+      ",
+    )
+    .args(["foo", "a", "b"])
+    .stdout("x/a b\n")
+    .run();
+}
+
+#[test]
+fn empty_variadic_parameters_are_empty_strings_without_setting() {
+  Test::new()
+    .justfile(
+      "
+        foo *args:
+          @echo '[{{ args }}]' {{ join('x', args) }}
+      ",
+    )
+    .args(["foo"])
+    .stdout("[] x/\n")
+    .run();
+}
+
+#[test]
+fn lists_expand_to_separate_variadic_function_arguments() {
+  Test::new()
+    .justfile(
+      "
+        parts := ['b', 'c']
+
+        foo:
+          @echo {{ join('a', parts) }} {{ join(parts, 'd') }}
+      ",
+    )
+    .stdout("a/b/c b/c/d\n")
+    .run();
+}
+
+#[test]
+fn lists_expand_to_separate_shell_arguments() {
+  Test::new()
+    .justfile(
+      "
+        args := ['a b', 'c']
+
+        foo:
+          @echo {{ shell('echo $#', args) }}
+      ",
+    )
+    .stdout("2\n")
+    .run();
+}
+
+#[test]
+fn list_defaults_expand_to_separate_positional_arguments() {
+  Test::new()
+    .justfile(
+      "
+        [positional-arguments]
+        foo *args=['a b', 'c']:
+          @printf '<%s>' \"$@\"
+      ",
+    )
+    .stdout("<a b><c>")
+    .run();
+}
+
+#[test]
+fn list_defaults_expand_to_separate_script_arguments() {
+  Test::new()
+    .justfile(
+      "
+        set unstable
+
+        [positional-arguments]
+        [script('sh')]
+        foo *args=['a b', 'c']:
+          printf '<%s>' \"$@\"
+      ",
+    )
+    .stdout("<a b><c>")
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile("x := map(['a', 'b'], uppercase)")
+    .arg("--dump")
+    .stdout("x := map(['a', 'b'], uppercase)\n")
+    .run();
+}
@@ -0,0 +1,395 @@
+use super::*;
+
+fn uri(test: &Test, path: &str) -> String {
+  format!("file://{}", test.tempdir.path().join(path).display())
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+  json!({
+    "jsonrpc": "2.0",
+    "id": id,
+    "method": method,
+    "params": params,
+  })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+  json!({
+    "jsonrpc": "2.0",
+    "method": method,
+    "params": params,
+  })
+}
+
+fn open(uri: &str, text: &str) -> Value {
+  notification(
+    "textDocument/didOpen",
+    json!({
+      "textDocument": {
+        "uri": uri,
+        "languageId": "just",
+        "version": 1,
+        "text": text,
+      },
+    }),
+  )
+}
+
+fn position(id: u64, method: &str, uri: &str, line: u64, character: u64) -> Value {
+  request(
+    id,
+    method,
+    json!({
+      "textDocument": { "uri": uri },
+      "position": { "line": line, "character": character },
+    }),
+  )
+}
+
+/// Send `messages` to `just --lsp`, followed by `shutdown` and `exit`, and
+/// return the messages it sends back, excluding the `shutdown` response
+fn session(test: Test, messages: &[Value]) -> Vec<Value> {
+  let mut stdin = String::new();
+
+  for message in messages
+    .iter()
+    .cloned()
+    .chain([request(u64::MAX, "shutdown", Value::Null)])
+    .chain([notification("exit", Value::Null)])
+  {
+    let content = message.to_string();
+    stdin.push_str("Content-Length: ");
+    stdin.push_str(&content.len().to_string());
+    stdin.push_str("\r\n\r\n");
+    stdin.push_str(&content);
+  }
+
+  let output = test.arg("--lsp").stdin(stdin).stdout_regex("(?s).*").run();
+
+  let mut responses = Vec::new();
+  let mut rest = output.stdout.as_str();
+
+  while !rest.is_empty() {
+    let (header, content) = rest.split_once("\r\n\r\n").unwrap();
+    let length = header
+      .strip_prefix("Content-Length: ")
+      .unwrap()
+      .parse::<usize>()
+      .unwrap();
+    responses.push(serde_json::from_str::<Value>(&content[..length]).unwrap());
+    rest = &content[length..];
+  }
+
+  assert_eq!(
+    responses.pop().unwrap(),
+    json!({ "jsonrpc": "2.0", "id": u64::MAX, "result": null }),
+  );
+
+  responses
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+  json!({
+    "start": { "line": start.0, "character": start.1 },
+    "end": { "line": end.0, "character": end.1 },
+  })
+}
+
+#[test]
+fn initialize() {
+  let responses = session(Test::new(), &[request(1, "initialize", json!({}))]);
+
+  assert_eq!(
+    responses[0]["result"]["capabilities"],
+    json!({
+      "completionProvider": {},
+      "definitionProvider": true,
+      "hoverProvider": true,
+      "textDocumentSync": 1,
+    }),
+  );
+
+  assert_eq!(responses[0]["result"]["serverInfo"]["name"], "just");
+}
+
+#[test]
+fn unknown_methods_are_errors() {
+  let responses = session(
+    Test::new(),
+    &[
+      request(1, "workspace/symbol", json!({})),
+      notification("$/unknown", json!({})),
+    ],
+  );
+
+  assert_eq!(
+    responses,
+    [json!({
+      "jsonrpc": "2.0",
+      "id": 1,
+      "error": {
+        "code": -32601,
+        "message": "Method not found: workspace/symbol",
+      },
+    })],
+  );
+}
+
+#[test]
+fn diagnostics() {
+  let test = Test::new();
+  let uri = uri(&test, "justfile");
+
+  let responses = session(test, &[open(&uri, "foo:\n  echo {{ bar }}\n")]);
+
+  assert_eq!(
+    responses,
+    [json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": {
+        "uri": uri,
+        "diagnostics": [{
+          "range": range((1, 10), (1, 13)),
+          "severity": 1,
+          "source": "just",
+          "message": "Variable `bar` not defined",
+        }],
+      },
+    })],
+  );
+}
+
+#[test]
+fn diagnostics_use_utf16_columns() {
+  let test = Test::new();
+  let uri = uri(&test, "justfile");
+
+  let responses = session(test, &[open(&uri, "foo:\n  echo 🦀 {{ bar }}\n")]);
+
+  assert_eq!(
+    responses[0]["params"]["diagnostics"][0]["range"],
+    range((1, 13), (1, 16)),
+  );
+}
+
+#[test]
+fn diagnostics_are_cleared_by_changes() {
+  let test = Test::new();
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[
+      open(&uri, "foo: bar\n"),
+      notification(
+        "textDocument/didChange",
+        json!({
+          "textDocument": { "uri": uri, "version": 2 },
+          "contentChanges": [{ "text": "foo: bar\nbar:\n" }],
+        }),
+      ),
+    ],
+  );
+
+  assert_eq!(
+    responses[0]["params"]["diagnostics"][0]["message"],
+    "Recipe `foo` has unknown dependency `bar`",
+  );
+
+  assert_eq!(responses[1]["params"]["diagnostics"], json!([]));
+}
+
+#[test]
+fn diagnostics_in_imported_files() {
+  let test = Test::new().write("bar.just", "baz:\n  echo {{ qux }}\n");
+  let uri = uri(&test, "justfile");
+
+  let responses = session(test, &[open(&uri, "import 'bar.just'\n")]);
+
+  assert_eq!(
+    responses[0]["params"]["diagnostics"],
+    json!([{
+      "range": range((0, 0), (0, 0)),
+      "severity": 1,
+      "source": "just",
+      "message": "bar.just:2:11: Variable `qux` not defined",
+    }]),
+  );
+}
+
+#[test]
+fn diagnostics_include_warnings() {
+  let test = Test::new();
+  let uri = uri(&test, "justfile");
+
+  let responses = session(test, &[open(&uri, "x := 'a'\n\nfoo:\n")]);
+
+  assert_eq!(
+    responses[0]["params"]["diagnostics"],
+    json!([{
+      "range": range((0, 0), (0, 1)),
+      "severity": 2,
+      "source": "just",
+      "code": "unused-variable",
+      "message": "Variable `x` is never used",
+    }]),
+  );
+}
+
+#[test]
+fn definition() {
+  let test = Test::new();
+  let uri = uri(&test, "justfile");
+
+  let text = "
+x := 'a'
+
+alias b := build
+
+build target: test
+  echo {{ x }} {{ target }}
+
+test:
+"
+  .trim_start();
+
+  let responses = session(
+    test,
+    &[
+      open(&uri, text),
+      position(1, "textDocument/definition", &uri, 5, 10),
+      position(2, "textDocument/definition", &uri, 4, 16),
+      position(3, "textDocument/definition", &uri, 5, 19),
+      position(4, "textDocument/definition", &uri, 2, 12),
+      position(5, "textDocument/definition", &uri, 5, 3),
+    ],
+  );
+
+  let location =
+    |line, start, end| json!({ "uri": uri, "range": range((line, start), (line, end)) });
+
+  assert_eq!(responses[1]["result"], location(0, 0, 1));
+  assert_eq!(responses[2]["result"], location(7, 0, 4));
+  assert_eq!(responses[3]["result"], location(4, 6, 12));
+  assert_eq!(responses[4]["result"], location(4, 0, 5));
+  assert_eq!(responses[5]["result"], Value::Null);
+}
+
+#[test]
+fn definition_of_imports_and_modules() {
+  let test = Test::new()
+    .write("bar.just", "bar:\n")
+    .write("foo.just", "baz:\n");
+  let uri = uri(&test, "justfile");
+  let bar = self::uri(&test, "bar.just");
+  let foo = self::uri(&test, "foo.just");
+
+  let responses = session(
+    test,
+    &[
+      open(&uri, "import 'bar.just'\nmod foo\n\nqux: bar foo::baz\n"),
+      position(1, "textDocument/definition", &uri, 0, 10),
+      position(2, "textDocument/definition", &uri, 1, 5),
+      position(3, "textDocument/definition", &uri, 3, 6),
+      position(4, "textDocument/definition", &uri, 3, 9),
+      position(5, "textDocument/definition", &uri, 3, 15),
+    ],
+  );
+
+  assert_eq!(
+    responses[1]["result"],
+    json!({ "uri": bar, "range": range((0, 0), (0, 0)) }),
+  );
+  assert_eq!(
+    responses[2]["result"],
+    json!({ "uri": foo, "range": range((0, 0), (0, 0)) }),
+  );
+  assert_eq!(
+    responses[3]["result"],
+    json!({ "uri": bar, "range": range((0, 0), (0, 3)) }),
+  );
+  assert_eq!(
+    responses[4]["result"],
+    json!({ "uri": foo, "range": range((0, 0), (0, 0)) }),
+  );
+  assert_eq!(
+    responses[5]["result"],
+    json!({ "uri": foo, "range": range((0, 0), (0, 3)) }),
+  );
+}
+
+#[test]
+fn hover() {
+  let test = Test::new();
+  let uri = uri(&test, "justfile");
+
+  let text = "
+x := 'a'
+
+# build a target
+build target='debug':
+
+run: build
+"
+  .trim_start();
+
+  let responses = session(
+    test,
+    &[
+      open(&uri, text),
+      position(1, "textDocument/hover", &uri, 5, 7),
+      position(2, "textDocument/hover", &uri, 0, 0),
+      position(3, "textDocument/hover", &uri, 3, 8),
+    ],
+  );
+
+  assert_eq!(
+    responses[1]["result"]["contents"],
+    json!({
+      "kind": "plaintext",
+      "value": "build a target\n\nusage:\n    just build target='debug'\n\nparameters:\n    target  optional, default: 'debug'",
+    }),
+  );
+
+  assert_eq!(responses[2]["result"]["contents"]["value"], "x := 'a'");
+
+  assert_eq!(
+    responses[3]["result"]["contents"]["value"],
+    "target='debug'"
+  );
+}
+
+#[test]
+fn completion() {
+  let test = Test::new();
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[
+      open(&uri, "fn slug(s) := lowercase(s)\nx := 'a'\n"),
+      position(1, "textDocument/completion", &uri, 1, 5),
+    ],
+  );
+
+  let items = responses[1]["result"].as_array().unwrap();
+
+  assert!(items.contains(&json!({
+    "label": "uppercase",
+    "kind": 3,
+    "detail": "built-in function",
+  })));
+
+  assert!(items.contains(&json!({
+    "label": "slug",
+    "kind": 3,
+    "detail": "fn slug(s) := lowercase(s)",
+  })));
+
+  assert!(items.contains(&json!({
+    "label": "x",
+    "kind": 6,
+    "detail": "x := 'a'",
+  })));
+}
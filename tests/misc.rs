@@ -831,7 +831,17 @@ foo A B:
   echo A:{{A}} B:{{B}}
     ",
     )
-    .stderr("error: Recipe `foo` got 1 argument but takes 2\nusage:\n    just foo A B\n")
+    .stderr(
+      "
+      error: Recipe `foo` got 1 argument but takes 2
+      usage:
+          just foo A B
+
+      parameters:
+          A  required
+          B  required
+    ",
+    )
     .status(EXIT_FAILURE)
     .run();
 }
@@ -870,6 +880,11 @@ foo A B C='C':
     error: Recipe `foo` got 1 argument but takes at least 2
     usage:
         just foo A B C='C'
+
+    parameters:
+        A  required
+        B  required
+        C  optional, default: 'C'
   ",
     )
     .status(EXIT_FAILURE)
@@ -1695,7 +1710,7 @@ fn unexpected_token_after_name() {
     .arg("foo")
     .justfile("foo 'bar'")
     .stderr(
-      "error: Expected '*', ':', '$', identifier, or '+', but found string
+      "error: Expected '*', ':', '$', identifier, '--', or '+', but found string
  ——▶ justfile:1:5
   │
 1 │ foo 'bar'
@@ -1868,7 +1883,18 @@ a x y +z:
   echo {{x}} {{y}} {{z}}
 ",
     )
-    .stderr("error: Recipe `a` got 2 arguments but takes at least 3\nusage:\n    just a x y +z\n")
+    .stderr(
+      "
+      error: Recipe `a` got 2 arguments but takes at least 3
+      usage:
+          just a x y +z
+
+      parameters:
+          x   required
+          y   required
+          +z  one or more
+    ",
+    )
     .status(EXIT_FAILURE)
     .run();
 }
@@ -2485,7 +2511,7 @@ fn old_equals_assignment_syntax_produces_error() {
     )
     .stderr(
       "
-    error: Expected '*', ':', '$', identifier, or '+', but found '='
+    error: Expected '*', ':', '$', identifier, '--', or '+', but found '='
      ——▶ justfile:1:5
       │
     1 │ foo = 'bar'
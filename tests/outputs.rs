@@ -0,0 +1,256 @@
+use {super::*, std::time::SystemTime};
+
+const JUSTFILE: &str = "
+  [inputs('src/*.c')]
+  [outputs('out')]
+  build:
+    @echo building
+";
+
+fn write(tempdir: &TempDir, path: &str, age: u64) {
+  let path = tempdir.path().join(path);
+  fs::create_dir_all(path.parent().unwrap()).unwrap();
+  fs::write(&path, "").unwrap();
+  fs::File::options()
+    .write(true)
+    .open(&path)
+    .unwrap()
+    .set_modified(SystemTime::now() - Duration::from_secs(age))
+    .unwrap();
+}
+
+#[test]
+fn missing_output_runs_recipe() {
+  let tempdir = tempdir();
+  write(&tempdir, "src/a.c", 10);
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .stdout("building\n")
+    .run();
+}
+
+#[test]
+fn up_to_date_recipe_is_skipped() {
+  let tempdir = tempdir();
+  write(&tempdir, "src/a.c", 20);
+  write(&tempdir, "src/b.c", 10);
+  write(&tempdir, "out", 5);
+
+  Test::with_tempdir(tempdir).justfile(JUSTFILE).run();
+}
+
+#[test]
+fn newer_input_runs_recipe() {
+  let tempdir = tempdir();
+  write(&tempdir, "src/a.c", 20);
+  write(&tempdir, "src/b.c", 5);
+  write(&tempdir, "out", 10);
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .stdout("building\n")
+    .run();
+}
+
+#[test]
+fn oldest_output_is_compared() {
+  let tempdir = tempdir();
+  write(&tempdir, "src/a.c", 10);
+  write(&tempdir, "a", 20);
+  write(&tempdir, "b", 5);
+
+  Test::with_tempdir(tempdir)
+    .justfile(
+      "
+        [inputs('src/*.c')]
+        [outputs('a', 'b')]
+        build:
+          @echo building
+      ",
+    )
+    .stdout("building\n")
+    .run();
+}
+
+#[test]
+fn output_without_inputs_is_up_to_date() {
+  let tempdir = tempdir();
+  write(&tempdir, "out", 5);
+
+  Test::with_tempdir(tempdir)
+    .justfile(
+      "
+        [outputs('out')]
+        build:
+          @echo building
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn force_runs_up_to_date_recipe() {
+  let tempdir = tempdir();
+  write(&tempdir, "src/a.c", 10);
+  write(&tempdir, "out", 5);
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .arg("--force")
+    .stdout("building\n")
+    .run();
+}
+
+#[test]
+fn force_environment_variable() {
+  let tempdir = tempdir();
+  write(&tempdir, "src/a.c", 10);
+  write(&tempdir, "out", 5);
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .env("JUST_FORCE", "true")
+    .stdout("building\n")
+    .run();
+}
+
+#[test]
+fn explain_up_to_date() {
+  let tempdir = tempdir();
+  write(&tempdir, "src/a.c", 10);
+  write(&tempdir, "out", 5);
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .arg("--explain")
+    .stderr("#### Recipe `build` is up to date: output `out` is newer than input `src/a.c`\n")
+    .run();
+}
+
+#[test]
+fn explain_newer_input() {
+  let tempdir = tempdir();
+  write(&tempdir, "src/a.c", 5);
+  write(&tempdir, "out", 10);
+
+  Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .arg("--explain")
+    .stdout("building\n")
+    .stderr("#### Recipe `build` is out of date: input `src/a.c` is newer than output `out`\n")
+    .run();
+}
+
+#[test]
+fn dry_run_missing_output() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .arg("--dry-run")
+    .stderr(
+      "
+        #### Recipe `build` is out of date: output `out` does not exist
+        echo building
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn dry_run_forced() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--dry-run", "--force"])
+    .stderr(
+      "
+        #### Recipe `build` is out of date: `--force` was passed
+        echo building
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn paths_are_relative_to_working_directory() {
+  let tempdir = tempdir();
+  write(&tempdir, "foo/src/a.c", 10);
+  write(&tempdir, "foo/out", 5);
+
+  Test::with_tempdir(tempdir)
+    .justfile(
+      "
+        [working-directory('foo')]
+        [inputs('src/*.c')]
+        [outputs('out')]
+        build:
+          @echo building
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn invalid_glob() {
+  Test::new()
+    .justfile(
+      "
+        [inputs('[')]
+        [outputs('out')]
+        build:
+      ",
+    )
+    .stderr(
+      "
+        error: Invalid glob pattern `[`: invalid range pattern
+         ——▶ justfile:1:2
+          │
+        1 │ [inputs('[')]
+          │  ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn attributes_are_repeatable() {
+  let tempdir = tempdir();
+  write(&tempdir, "a.c", 10);
+  write(&tempdir, "b.c", 5);
+  write(&tempdir, "out", 7);
+
+  Test::with_tempdir(tempdir)
+    .justfile(
+      "
+        [inputs('a.c')]
+        [inputs('b.c')]
+        [outputs('out')]
+        build:
+          @echo building
+      ",
+    )
+    .stdout("building\n")
+    .run();
+}
+
+#[test]
+fn inputs_without_outputs() {
+  Test::new()
+    .justfile(
+      "
+        [inputs('src/*.c')]
+        build:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `build` has `[inputs]` attribute without `[outputs]` or `[cache]` attribute
+         ——▶ justfile:2:1
+          │
+        2 │ build:
+          │ ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,302 @@
+use super::*;
+
+#[test]
+fn choice_argument_is_accepted() {
+  Test::new()
+    .justfile(
+      "
+        @deploy env: ('dev' | 'staging' | 'prod'):
+          echo {{ env }}
+      ",
+    )
+    .args(["deploy", "staging"])
+    .stdout("staging\n")
+    .run();
+}
+
+#[test]
+fn choice_argument_is_rejected() {
+  Test::new()
+    .justfile(
+      "
+        deploy env: ('dev' | 'staging' | 'prod'):
+          echo {{ env }}
+      ",
+    )
+    .args(["deploy", "qa"])
+    .stderr(
+      "error: Recipe `deploy` parameter `env` got `qa` but must be one of `dev`, `staging`, or \
+       `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn single_choice_error_message() {
+  Test::new()
+    .justfile(
+      "
+        foo bar: ('baz'):
+      ",
+    )
+    .args(["foo", "qux"])
+    .stderr("error: Recipe `foo` parameter `bar` got `qux` but must be `baz`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn int_argument() {
+  Test::new()
+    .justfile(
+      "
+        @foo count: int:
+          echo {{ count }}
+      ",
+    )
+    .args(["foo", "-12"])
+    .stdout("-12\n")
+    .run();
+
+  Test::new()
+    .justfile(
+      "
+        foo count: int:
+      ",
+    )
+    .args(["foo", "1.5"])
+    .stderr("error: Recipe `foo` parameter `count` got `1.5` but must be an integer\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn bool_argument() {
+  Test::new()
+    .justfile(
+      "
+        @foo verbose: bool:
+          echo {{ verbose }}
+      ",
+    )
+    .args(["foo", "false"])
+    .stdout("false\n")
+    .run();
+
+  Test::new()
+    .justfile(
+      "
+        foo verbose: bool:
+      ",
+    )
+    .args(["foo", "yes"])
+    .stderr("error: Recipe `foo` parameter `verbose` got `yes` but must be `true` or `false`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn default_is_checked() {
+  Test::new()
+    .justfile(
+      "
+        foo count: int='many':
+      ",
+    )
+    .args(["foo"])
+    .stderr("error: Recipe `foo` parameter `count` got `many` but must be an integer\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn every_variadic_argument_is_checked() {
+  Test::new()
+    .justfile(
+      "
+        foo +counts: int:
+          echo {{ counts }}
+      ",
+    )
+    .args(["foo", "1", "2", "three"])
+    .stderr("error: Recipe `foo` parameter `counts` got `three` but must be an integer\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn arguments_are_checked_before_dependencies_run() {
+  Test::new()
+    .justfile(
+      "
+        foo count: int: bar
+
+        bar:
+          echo bar
+      ",
+    )
+    .args(["foo", "x"])
+    .stderr("error: Recipe `foo` parameter `count` got `x` but must be an integer\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn arguments_are_checked_before_earlier_recipes_run() {
+  Test::new()
+    .justfile(
+      "
+        a:
+          echo a
+
+        b env: ('dev' | 'prod'):
+          echo {{ env }}
+      ",
+    )
+    .args(["a", "b", "staging"])
+    .stderr("error: Recipe `b` parameter `env` got `staging` but must be one of `dev` or `prod`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn options_are_checked_before_earlier_recipes_run() {
+  Test::new()
+    .justfile(
+      "
+        a:
+          echo a
+
+        b --count: int='1':
+          echo {{ count }}
+      ",
+    )
+    .args(["a", "b", "--count=x"])
+    .stderr("error: Recipe `b` parameter `count` got `x` but must be an integer\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn colon_without_second_colon_begins_dependencies() {
+  Test::new()
+    .justfile(
+      "
+        foo bar: int
+
+        int:
+          @echo int
+      ",
+    )
+    .args(["foo", "x"])
+    .stdout("int\n")
+    .run();
+}
+
+#[test]
+fn unknown_type() {
+  Test::new()
+    .justfile(
+      "
+        foo bar: float:
+      ",
+    )
+    .stderr(
+      "
+        error: Unknown parameter type `float`, expected `bool`, `int`, or a parenthesized list of choices
+         ——▶ justfile:1:10
+          │
+        1 │ foo bar: float:
+          │          ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn types_are_listed() {
+  Test::new()
+    .justfile(
+      "
+        foo env: ('dev' | 'prod') count: int='1' *flags: bool:
+      ",
+    )
+    .args(["--list"])
+    .stdout(
+      "
+        Available recipes:
+            foo env: ('dev' | 'prod') count: int='1' *flags: bool
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn types_are_shown() {
+  Test::new()
+    .justfile(
+      "
+        foo env: ('dev' | 'prod') count: int='1':
+          echo {{ env }}
+      ",
+    )
+    .args(["--show", "foo"])
+    .stdout(
+      "
+        foo env: ('dev' | 'prod') count: int='1':
+            echo {{ env }}
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn types_are_preserved_by_format() {
+  Test::new()
+    .justfile(
+      "
+        foo   env:('dev'|'prod')   count:int='1':
+      ",
+    )
+    .args(["--dump"])
+    .stdout("foo env: ('dev' | 'prod') count: int='1':\n")
+    .run();
+}
+
+#[test]
+fn choices_are_completed() {
+  Test::new()
+    .justfile(
+      "
+        foo env: ('dev' | 'prod') verbose: bool count: int:
+        bar:
+      ",
+    )
+    .args(["--complete", "foo"])
+    .stdout("dev prod\n")
+    .run();
+
+  Test::new()
+    .justfile(
+      "
+        foo env: ('dev' | 'prod') verbose: bool count: int:
+        bar:
+      ",
+    )
+    .args(["--complete", "foo", "dev"])
+    .stdout("true false\n")
+    .run();
+
+  Test::new()
+    .justfile(
+      "
+        foo env: ('dev' | 'prod') verbose: bool count: int:
+        bar:
+      ",
+    )
+    .args(["--complete", "foo", "dev", "true", "1"])
+    .stdout("bar foo\n")
+    .run();
+}
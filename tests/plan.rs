@@ -0,0 +1,186 @@
+use super::*;
+
+fn plan(justfile: &str, args: &[&str], plan: &str) {
+  let tempdir = tempdir();
+
+  let path = tempdir.path().to_str().unwrap().to_owned();
+
+  Test::with_tempdir(tempdir)
+    .justfile(justfile)
+    .args(args)
+    .shell(false)
+    .stdout(unindent(plan).replace("DIR", &path))
+    .run();
+}
+
+#[test]
+fn priors_and_subsequents() {
+  plan(
+    "
+      foo: bar && baz
+        echo foo
+
+      bar:
+        echo bar
+
+      baz:
+        echo baz
+    ",
+    &["--plan", "foo"],
+    "
+      2. foo       sh -cu  DIR
+        1. bar     sh -cu  DIR
+        3. && baz  sh -cu  DIR
+    ",
+  );
+}
+
+#[test]
+fn arguments_are_evaluated() {
+  plan(
+    "
+      x := 'a'
+
+      foo y=x: (bar y + 'b')
+
+      bar z:
+    ",
+    &["--plan"],
+    "
+      2. foo y='a'     sh -cu  DIR
+        1. bar z='ab'  sh -cu  DIR
+    ",
+  );
+}
+
+#[test]
+fn recipes_which_already_ran_are_not_numbered() {
+  plan(
+    "
+      foo: (bar 'x') (bar 'x')
+
+      bar y:
+    ",
+    &["--plan"],
+    "
+      2. foo          sh -cu  DIR
+        1. bar y='x'  sh -cu  DIR
+        -  bar 'x' (already run)
+    ",
+  );
+}
+
+#[test]
+fn hooks() {
+  plan(
+    "
+      [finally('stop')]
+      [on-failure('notify')]
+      foo:
+
+      stop:
+
+      notify:
+    ",
+    &["--plan"],
+    "
+      1. foo             sh -cu  DIR
+        -  on-failure notify (if failed)
+        2. finally stop  sh -cu  DIR
+    ",
+  );
+}
+
+#[test]
+fn backticks_are_not_run() {
+  plan(
+    "
+      x := `echo foo > bar.txt; echo foo`
+
+      foo y=x:
+    ",
+    &["--plan"],
+    "
+      1. foo y='`echo foo > bar.txt; echo foo`'  sh -cu  DIR
+    ",
+  );
+}
+
+#[test]
+fn backticks_are_run_with_plan_eval() {
+  plan(
+    "
+      x := `echo foo`
+
+      foo y=x:
+    ",
+    &["--plan", "--plan-eval"],
+    "
+      1. foo y='foo'  sh -cu  DIR
+    ",
+  );
+}
+
+#[test]
+fn recipe_bodies_are_not_run() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          touch bar.txt
+      ",
+    )
+    .args(["--plan"])
+    .stdout_regex("1. foo  bash -cu  .*\n")
+    .run()
+    .tempdir
+    .path()
+    .join("bar.txt")
+    .try_exists()
+    .map(|exists| assert!(!exists))
+    .unwrap();
+}
+
+#[test]
+fn no_dependencies() {
+  plan(
+    "
+      foo: bar
+
+      bar:
+    ",
+    &["--plan", "--no-deps"],
+    "
+      1. foo  sh -cu  DIR
+    ",
+  );
+}
+
+#[test]
+fn working_directory_and_shell() {
+  plan(
+    "
+      set shell := ['bash', '-c']
+
+      [working-directory('bar')]
+      foo:
+
+      baz:
+        #!/usr/bin/env python3
+    ",
+    &["--plan", "foo", "baz"],
+    "
+      1. foo  bash -c                 DIR/bar
+      2. baz  #!/usr/bin/env python3  DIR
+    ",
+  );
+}
+
+#[test]
+fn plan_eval_requires_plan() {
+  Test::new()
+    .arg("--plan-eval")
+    .stderr_regex("error: the following required arguments were not provided:\n  --plan\n(.|\n)*")
+    .status(2)
+    .run();
+}
@@ -0,0 +1,244 @@
+use super::*;
+
+/// Run `just` with `args` and stdin attached to a pseudoterminal, to which
+/// `input` is written, returning stdout, stderr, and the exit code
+#[cfg(unix)]
+fn terminal(justfile: &str, args: &[&str], input: &str) -> (String, String, i32) {
+  let tempdir = tempdir();
+
+  fs::write(tempdir.path().join("justfile"), unindent(justfile)).unwrap();
+
+  let pty = nix::pty::openpty(None, None).unwrap();
+
+  let child = Command::new(executable_path("just"))
+    .current_dir(tempdir.path())
+    .args(args)
+    .stdin(Stdio::from(pty.slave))
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+
+  let mut master = fs::File::from(pty.master);
+
+  master.write_all(input.as_bytes()).unwrap();
+
+  let output = child.wait_with_output().unwrap();
+
+  (
+    String::from_utf8(output.stdout).unwrap(),
+    String::from_utf8(output.stderr).unwrap(),
+    output.status.code().unwrap(),
+  )
+}
+
+#[test]
+#[cfg(unix)]
+fn prompt_attribute() {
+  let (stdout, stderr, code) = terminal(
+    "
+      [prompt]
+      foo a b='B':
+        @echo {{a}} {{b}}
+    ",
+    &["foo"],
+    "A\n\n",
+  );
+
+  assert_eq!(stdout, "A B\n");
+  assert_eq!(stderr, "foo a: foo b [B]: ");
+  assert_eq!(code, 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn interactive_flag() {
+  let (stdout, stderr, code) = terminal(
+    "
+      foo a:
+        @echo {{a}}
+    ",
+    &["--interactive", "foo"],
+    "A\n",
+  );
+
+  assert_eq!(stdout, "A\n");
+  assert_eq!(stderr, "foo a: ");
+  assert_eq!(code, 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn given_arguments_are_not_prompted_for() {
+  let (stdout, stderr, code) = terminal(
+    "
+      [prompt]
+      foo a b='B' c='C':
+        @echo {{a}} {{b}} {{c}}
+    ",
+    &["foo", "A", "X"],
+    "\n",
+  );
+
+  assert_eq!(stdout, "A X C\n");
+  assert_eq!(stderr, "foo c [C]: ");
+  assert_eq!(code, 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn choices_are_shown_and_checked() {
+  let (stdout, stderr, code) = terminal(
+    "
+      [prompt]
+      foo a: ('x' | 'y'):
+        @echo {{a}}
+    ",
+    &["foo"],
+    "z\ny\n",
+  );
+
+  assert_eq!(stdout, "y\n");
+  assert_eq!(
+    stderr,
+    "foo a (x | y): \
+     error: Recipe `foo` parameter `a` got `z` but must be one of `x` or `y`\n\
+     foo a (x | y): ",
+  );
+  assert_eq!(code, 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn variadic_arguments_are_split_on_whitespace() {
+  let (stdout, stderr, code) = terminal(
+    "
+      [prompt]
+      foo +a:
+        @echo {{a}}
+    ",
+    &["foo"],
+    "\nx  y\n",
+  );
+
+  assert_eq!(stdout, "x y\n");
+  assert_eq!(stderr, "foo +a: foo +a: ");
+  assert_eq!(code, 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn default_recipe() {
+  let (stdout, stderr, code) = terminal(
+    "
+      [prompt]
+      foo a:
+        @echo {{a}}
+    ",
+    &[],
+    "A\n",
+  );
+
+  assert_eq!(stdout, "A\n");
+  assert_eq!(stderr, "foo a: ");
+  assert_eq!(code, 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn dependency_arguments_are_not_prompted_for() {
+  let (stdout, stderr, code) = terminal(
+    "
+      foo: (bar 'A')
+
+      [prompt]
+      bar a b='B':
+        @echo {{a}} {{b}}
+    ",
+    &["foo"],
+    "",
+  );
+
+  assert_eq!(stdout, "A B\n");
+  assert_eq!(stderr, "");
+  assert_eq!(code, 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn secret_defaults_are_redacted() {
+  let (stdout, stderr, code) = terminal(
+    "
+      [prompt]
+      [secret('token')]
+      foo token='hunter2':
+        @echo ok
+    ",
+    &["foo"],
+    "\n",
+  );
+
+  assert_eq!(stdout, "ok\n");
+  assert_eq!(stderr, "foo token [***]: ");
+  assert_eq!(code, 0);
+}
+
+#[test]
+fn missing_arguments_are_an_error_if_stdin_is_not_a_terminal() {
+  Test::new()
+    .justfile(
+      "
+        [prompt]
+        foo a:
+      ",
+    )
+    .args(["--interactive", "foo"])
+    .stderr(
+      "
+        error: Recipe `foo` got 0 arguments but takes 1
+        usage:
+            just foo a
+
+        parameters:
+            a  required
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn arguments_with_defaults_are_not_prompted_for_if_stdin_is_not_a_terminal() {
+  Test::new()
+    .justfile(
+      "
+        [prompt]
+        foo a='A':
+          @echo {{a}}
+      ",
+    )
+    .stdout("A\n")
+    .run();
+}
+
+#[test]
+fn prompt_attribute_takes_no_arguments() {
+  Test::new()
+    .justfile(
+      "
+        [prompt('x')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Attribute `prompt` got 1 argument but takes 0 arguments
+         ——▶ justfile:1:2
+          │
+        1 │ [prompt('x')]
+          │  ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
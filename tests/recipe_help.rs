@@ -0,0 +1,358 @@
+use super::*;
+
+#[test]
+fn recipe_without_parameters() {
+  Test::new()
+    .justfile(
+      "
+        build:
+          echo build
+      ",
+    )
+    .args(["build", "--help"])
+    .stdout(
+      "
+        usage:
+            just build
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn doc_comment() {
+  Test::new()
+    .justfile(
+      "
+        # build the project
+        build:
+      ",
+    )
+    .args(["build", "--help"])
+    .stdout(
+      "
+        build the project
+
+        usage:
+            just build
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn parameters() {
+  Test::new()
+    .justfile(
+      "
+        test $filter target='debug':
+      ",
+    )
+    .args(["test"])
+    .stderr(
+      "
+        error: Recipe `test` got 0 arguments but takes at least 1
+        usage:
+            just test $filter target='debug'
+
+        parameters:
+            $filter  required, exported
+            target   optional, default: 'debug'
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn typed_parameters_and_options() {
+  Test::new()
+    .justfile(
+      "
+        deploy --dry --region='us' target: ('web' | 'db'):
+      ",
+    )
+    .args(["deploy", "--help"])
+    .stdout(
+      "
+        usage:
+            just deploy --dry --region='us' target: ('web' | 'db')
+
+        parameters:
+            --dry     flag
+            --region  option, default: 'us'
+            target    required, type: ('web' | 'db')
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn groups_and_aliases() {
+  Test::new()
+    .justfile(
+      "
+        alias b := build
+        alias c := build
+
+        [group('dev')]
+        [group('ci')]
+        build:
+      ",
+    )
+    .args(["build", "--help"])
+    .stdout(
+      "
+        usage:
+            just build
+
+        groups: ci, dev
+
+        aliases: b, c
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn private_aliases_are_not_shown() {
+  Test::new()
+    .justfile(
+      "
+        alias b := build
+
+        [private]
+        alias _c := build
+
+        [group('dev')]
+        build:
+      ",
+    )
+    .args(["build", "--help"])
+    .stdout(
+      "
+        usage:
+            just build
+
+        group: dev
+
+        alias: b
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn help_does_not_run_recipes() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          echo foo
+
+        bar:
+          echo bar
+      ",
+    )
+    .args(["foo", "bar", "--help"])
+    .stdout(
+      "
+        usage:
+            just bar
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn submodule_recipe() {
+  Test::new()
+    .write("foo.just", "# bar it\nbar --x:")
+    .justfile("mod foo")
+    .args(["foo", "bar", "--help"])
+    .stdout(
+      "
+        bar it
+
+        usage:
+            just foo bar --x
+
+        parameters:
+            --x  flag
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn recipe_with_positional_parameters() {
+  Test::new()
+    .justfile(
+      "
+        foo bar baz='qux':
+          echo {{ bar }}
+      ",
+    )
+    .args(["foo", "--help"])
+    .stdout(
+      "
+        usage:
+            just foo bar baz='qux'
+
+        parameters:
+            bar  required
+            baz  optional, default: 'qux'
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn variadic_recipes_receive_help_after_separator() {
+  Test::new()
+    .justfile(
+      "
+        cargo *args:
+          echo {{ args }}
+      ",
+    )
+    .args(["cargo", "--", "--help"])
+    .stdout("--help\n")
+    .stderr("echo --help\n")
+    .run();
+}
+
+#[test]
+fn recipes_with_parameters_receive_help_after_separator() {
+  Test::new()
+    .justfile(
+      "
+        foo bar:
+          echo {{ bar }}
+      ",
+    )
+    .args(["foo", "--", "--help"])
+    .stdout("--help\n")
+    .stderr("echo --help\n")
+    .run();
+}
+
+#[test]
+fn recipes_with_options_receive_help_after_separator() {
+  Test::new()
+    .justfile(
+      "
+        foo --dry bar:
+          echo {{ bar }}
+      ",
+    )
+    .args(["foo", "--", "--help"])
+    .stdout("--help\n")
+    .stderr("echo --help\n")
+    .run();
+}
+
+#[test]
+fn help_is_only_intercepted_as_first_argument() {
+  Test::new()
+    .justfile(
+      "
+        foo *args:
+          echo {{ args }}
+      ",
+    )
+    .args(["foo", "bar", "--help"])
+    .stdout("bar --help\n")
+    .stderr("echo bar --help\n")
+    .run();
+}
+
+#[test]
+fn help_option_is_not_intercepted() {
+  Test::new()
+    .justfile(
+      "
+        foo --help:
+          echo {{ help }}
+      ",
+    )
+    .args(["foo", "--help"])
+    .stdout("true\n")
+    .stderr("echo true\n")
+    .run();
+}
+
+#[test]
+fn aliases_to_recipes_in_other_modules_are_not_shown() {
+  Test::new()
+    .write("foo.just", "build:")
+    .justfile(
+      "
+        mod foo
+
+        alias b := foo::build
+
+        build:
+      ",
+    )
+    .args(["build", "--help"])
+    .stdout(
+      "
+        usage:
+            just build
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn variadic_recipes_with_options_print_help() {
+  Test::new()
+    .justfile(
+      "
+        cargo --release *args:
+      ",
+    )
+    .args(["cargo", "--help"])
+    .stdout(
+      "
+        usage:
+            just cargo --release *args
+
+        parameters:
+            --release  flag
+            *args      zero or more
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn argument_count_mismatch_prints_usage() {
+  Test::new()
+    .justfile(
+      "
+        # deploy a target
+        [group('ops')]
+        deploy target region='us':
+      ",
+    )
+    .args(["deploy"])
+    .stderr(
+      "
+        error: Recipe `deploy` got 0 arguments but takes at least 1
+        deploy a target
+
+        usage:
+            just deploy target region='us'
+
+        parameters:
+            target  required
+            region  optional, default: 'us'
+
+        group: ops
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,255 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  @deploy --dry --region='us' target:
+    echo dry={{ dry }} region={{ region }} target={{ target }}
+
+  @build:
+    echo build
+";
+
+#[test]
+fn flags_default_to_false() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "web"])
+    .stdout("dry=false region=us target=web\n")
+    .run();
+}
+
+#[test]
+fn flags_are_true_when_passed() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--dry", "web"])
+    .stdout("dry=true region=us target=web\n")
+    .run();
+}
+
+#[test]
+fn option_with_equals() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--region=eu", "web"])
+    .stdout("dry=false region=eu target=web\n")
+    .run();
+}
+
+#[test]
+fn option_with_separate_value() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--region", "eu", "web"])
+    .stdout("dry=false region=eu target=web\n")
+    .run();
+}
+
+#[test]
+fn options_may_follow_positional_arguments() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "web", "--dry", "--region=eu", "build"])
+    .stdout("dry=true region=eu target=web\nbuild\n")
+    .run();
+}
+
+#[test]
+fn unknown_option() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--force", "web"])
+    .stderr("error: Recipe `deploy` does not have option `--force`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn flag_with_value() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--dry=false", "web"])
+    .stderr("error: Recipe `deploy` flag `--dry` does not take a value\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn option_without_value() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "web", "--region"])
+    .stderr("error: Recipe `deploy` option `--region` requires a value\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn options_are_not_counted_as_arguments() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--dry"])
+    .stderr(
+      "
+        error: Recipe `deploy` got 0 arguments but takes 1
+        usage:
+            just deploy --dry --region='us' target
+
+        parameters:
+            --dry     flag
+            --region  option, default: 'us'
+            target    required
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dashes_are_passed_to_recipes_without_options() {
+  Test::new()
+    .justfile(
+      "
+        @test *flags:
+          echo {{ flags }}
+      ",
+    )
+    .args(["test", "--release"])
+    .stdout("--release\n")
+    .run();
+}
+
+#[test]
+fn unknown_option_with_value() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--bogus=1", "web"])
+    .stderr("error: Recipe `deploy` does not have option `--bogus`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn separator_ends_options() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--region=eu", "--", "--dry"])
+    .stdout("dry=false region=eu target=--dry\n")
+    .run();
+}
+
+#[test]
+fn dependency_arguments_are_not_options() {
+  Test::new()
+    .justfile(
+      "
+        @deploy --region='us' target:
+          echo region={{ region }} target={{ target }}
+
+        all: (deploy '--region=eu')
+      ",
+    )
+    .args(["all"])
+    .stdout("region=us target=--region=eu\n")
+    .run();
+}
+
+#[test]
+fn dependency_arguments_are_counted_as_positional_arguments() {
+  Test::new()
+    .justfile(
+      "
+        build --release='no':
+
+        all: (build '--release=yes')
+      ",
+    )
+    .args(["all"])
+    .stderr(
+      "
+        error: Dependency `build` got 1 argument but takes 0 arguments
+         ——▶ justfile:3:7
+          │
+        3 │ all: (build '--release=yes')
+          │       ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn option_types_are_checked() {
+  Test::new()
+    .justfile(
+      "
+        foo --count: int='1':
+      ",
+    )
+    .args(["foo", "--count=many"])
+    .stderr("error: Recipe `foo` parameter `count` got `many` but must be an integer\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn options_may_be_exported() {
+  Test::new()
+    .justfile(
+      "
+        @foo --$region='us':
+          echo $region
+      ",
+    )
+    .args(["foo", "--region=eu"])
+    .stdout("eu\n")
+    .run();
+}
+
+#[test]
+fn options_are_not_positional_arguments() {
+  Test::new()
+    .justfile(
+      "
+        set positional-arguments
+
+        @foo --dry a:
+          echo $# $1
+      ",
+    )
+    .args(["foo", "--dry", "bar"])
+    .stdout("1 bar\n")
+    .run();
+}
+
+#[test]
+fn options_are_listed() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--list"])
+    .stdout(
+      "
+        Available recipes:
+            build
+            deploy --dry --region='us' target
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile("foo   --dry   --region = 'us':")
+    .args(["--dump"])
+    .stdout("foo --dry --region='us':\n")
+    .run();
+}
+
+#[test]
+fn options_are_completed() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--complete", "deploy", "--dry"])
+    .stdout("--dry --region build deploy\n")
+    .run();
+}
@@ -0,0 +1,173 @@
+use super::*;
+
+/// Read `report.xml`, replacing durations with `0.000`
+fn report(output: &Output) -> String {
+  Regex::new(r#"time="[0-9.]+""#)
+    .unwrap()
+    .replace_all(
+      &fs::read_to_string(output.tempdir.path().join("report.xml")).unwrap(),
+      r#"time="0.000""#,
+    )
+    .into_owned()
+}
+
+#[test]
+fn junit() {
+  let output = Test::new()
+    .justfile(
+      "
+        test: (compile 'release')
+          @echo test
+
+        compile profile:
+          @echo {{ profile }}
+      ",
+    )
+    .args(["--report", "junit=report.xml", "test"])
+    .stdout("release\ntest\n")
+    .run();
+
+  assert_eq!(
+    report(&output),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="0" time="0.000">
+  <testsuite name="just" tests="2" failures="0" time="0.000">
+    <testcase classname="justfile" name="compile release" time="0.000"/>
+    <testcase classname="justfile" name="test" time="0.000"/>
+  </testsuite>
+</testsuites>
+"#,
+  );
+}
+
+#[test]
+fn failures_are_reported() {
+  let output = Test::new()
+    .justfile(
+      "
+        test:
+          @exit 3
+      ",
+    )
+    .args(["--report", "junit=report.xml"])
+    .stderr("error: Recipe `test` failed on line 2 with exit code 3\n")
+    .status(3)
+    .run();
+
+  assert_eq!(
+    report(&output),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="1" failures="1" time="0.000">
+  <testsuite name="just" tests="1" failures="1" time="0.000">
+    <testcase classname="justfile" name="test" time="0.000">
+      <failure message="Recipe `test` failed on line 2 with exit code 3" type="exit-code">exit code 3</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#,
+  );
+}
+
+#[test]
+fn failures_before_running_are_reported() {
+  let output = Test::new()
+    .justfile(
+      "
+        [confirm]
+        test:
+      ",
+    )
+    .args(["--report", "junit=report.xml"])
+    .stdin("n")
+    .stderr("Run recipe `test`? error: Recipe `test` was not confirmed\n")
+    .status(EXIT_FAILURE)
+    .run();
+
+  assert_eq!(
+    report(&output),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="1" failures="1" time="0.000">
+  <testsuite name="just" tests="1" failures="1" time="0.000">
+    <testcase classname="justfile" name="test" time="0.000">
+      <failure message="Recipe `test` was not confirmed" type="error"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#,
+  );
+}
+
+#[test]
+#[cfg(unix)]
+fn signals_are_reported() {
+  let output = Test::new()
+    .justfile(
+      "
+        test:
+          @kill -TERM $$
+      ",
+    )
+    .args(["--report", "junit=report.xml"])
+    .stderr("error: Recipe `test` was terminated on line 2 by signal 15\n")
+    .status(143)
+    .run();
+
+  assert!(report(&output).contains(
+    r#"<failure message="Recipe `test` was terminated on line 2 by signal 15" type="signal">signal 15</failure>"#
+  ));
+}
+
+#[test]
+fn module_path_is_classname() {
+  let output = Test::new()
+    .write("foo/mod.just", "bar:\n")
+    .justfile("mod foo")
+    .args(["--report", "junit=report.xml", "foo::bar"])
+    .run();
+
+  assert!(report(&output).contains(r#"<testcase classname="foo" name="bar" time="0.000"/>"#));
+}
+
+#[test]
+fn names_are_escaped() {
+  let output = Test::new()
+    .justfile("test x:")
+    .args(["--report", "junit=report.xml", "test", "<&\">"])
+    .run();
+
+  assert!(report(&output).contains(r#"name="test &lt;&amp;&quot;&gt;""#));
+}
+
+#[test]
+fn invalid_report() {
+  Test::new()
+    .justfile("test:")
+    .args(["--report", "junit"])
+    .stderr_regex(
+      "error: invalid value 'junit' for '--report <FORMAT=PATH>': expected `FORMAT=PATH`\n.*",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn unknown_report_format() {
+  Test::new()
+    .justfile("test:")
+    .args(["--report", "xml=report.xml"])
+    .stderr_regex(
+      "error: invalid value 'xml=report.xml' for '--report <FORMAT=PATH>': unknown report format `xml`\n.*",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn report_cannot_be_written() {
+  Test::new()
+    .justfile("test:")
+    .args(["--report", "junit=missing/report.xml"])
+    .stderr_regex("error: Failed to write report `missing/report.xml`: .*\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,185 @@
+use super::*;
+
+#[test]
+fn failed_recipes_are_retried() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2')]
+        foo:
+          @echo attempt
+          @test -f done || (touch done && exit 1)
+      ",
+    )
+    .stdout("attempt\nattempt\n")
+    .stderr("===> Recipe `foo` failed on line 4 with exit code 1. Retrying (attempt 2 of 3)...\n")
+    .run();
+}
+
+#[test]
+fn final_error_reports_attempts() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2')]
+        foo:
+          @exit 3
+      ",
+    )
+    .stderr(
+      "
+        ===> Recipe `foo` failed on line 3 with exit code 3. Retrying (attempt 2 of 3)...
+        ===> Recipe `foo` failed on line 3 with exit code 3. Retrying (attempt 3 of 3)...
+        error: Recipe `foo` failed on line 3 with exit code 3 (gave up after 3 attempts)
+      ",
+    )
+    .status(3)
+    .run();
+}
+
+#[test]
+fn dependencies_are_not_rerun() {
+  Test::new()
+    .justfile(
+      "
+        [retry('1')]
+        foo: bar
+          @exit 1
+
+        bar:
+          @echo bar
+      ",
+    )
+    .stdout("bar\n")
+    .stderr(
+      "
+        ===> Recipe `foo` failed on line 3 with exit code 1. Retrying (attempt 2 of 2)...
+        error: Recipe `foo` failed on line 3 with exit code 1 (gave up after 2 attempts)
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn delay() {
+  let start = Instant::now();
+
+  Test::new()
+    .justfile(
+      "
+        [retry('2', '200ms')]
+        foo:
+          @exit 1
+      ",
+    )
+    .stderr(
+      "
+        ===> Recipe `foo` failed on line 3 with exit code 1. Retrying in 200ms (attempt 2 of 3)...
+        ===> Recipe `foo` failed on line 3 with exit code 1. Retrying in 200ms (attempt 3 of 3)...
+        error: Recipe `foo` failed on line 3 with exit code 1 (gave up after 3 attempts)
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+
+  assert!(start.elapsed() >= Duration::from_millis(400));
+}
+
+#[test]
+fn exponential_backoff() {
+  Test::new()
+    .justfile(
+      "
+        [retry('3', '10ms', 'exponential')]
+        foo:
+          @exit 1
+      ",
+    )
+    .stderr(
+      "
+        ===> Recipe `foo` failed on line 3 with exit code 1. Retrying in 10ms (attempt 2 of 4)...
+        ===> Recipe `foo` failed on line 3 with exit code 1. Retrying in 20ms (attempt 3 of 4)...
+        ===> Recipe `foo` failed on line 3 with exit code 1. Retrying in 40ms (attempt 4 of 4)...
+        error: Recipe `foo` failed on line 3 with exit code 1 (gave up after 4 attempts)
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn script_recipes_are_retried() {
+  Test::new()
+    .justfile(
+      "
+        [retry('1')]
+        foo:
+          #!/usr/bin/env bash
+          echo attempt
+          test -f done || (touch done && exit 1)
+      ",
+    )
+    .stdout("attempt\nattempt\n")
+    .stderr("===> Recipe `foo` failed with exit code 1. Retrying (attempt 2 of 2)...\n")
+    .run();
+}
+
+#[test]
+fn quiet_suppresses_retry_messages() {
+  Test::new()
+    .justfile(
+      "
+        [retry('1')]
+        foo:
+          @exit 1
+      ",
+    )
+    .arg("--quiet")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_count() {
+  Test::new()
+    .justfile(
+      "
+        [retry('0')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Invalid `[retry]` argument `0`: expected a positive number of retries
+         ——▶ justfile:1:2
+          │
+        1 │ [retry('0')]
+          │  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_backoff() {
+  Test::new()
+    .justfile(
+      "
+        [retry('1', '1s', 'linear')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Invalid `[retry]` argument `linear`: expected `fixed` or `exponential` backoff
+         ——▶ justfile:1:2
+          │
+        1 │ [retry('1', '1s', 'linear')]
+          │  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
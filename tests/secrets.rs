@@ -0,0 +1,399 @@
+use super::*;
+
+#[test]
+fn echoed_lines_are_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret token := 'hunter2'
+
+        foo:
+          echo {{ token }}
+      ",
+    )
+    .stdout("hunter2\n")
+    .stderr("echo ***\n")
+    .run();
+}
+
+#[test]
+fn exported_secrets_are_passed_to_recipes() {
+  Test::new()
+    .justfile(
+      "
+        export secret TOKEN := 'hunter2'
+
+        foo:
+          @echo $TOKEN
+      ",
+    )
+    .stdout("hunter2\n")
+    .run();
+}
+
+#[test]
+fn dry_run_is_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret token := env('TOKEN')
+
+        foo:
+          curl -H 'Authorization: {{ token }}'
+      ",
+    )
+    .env("TOKEN", "hunter2")
+    .arg("--dry-run")
+    .stderr("curl -H 'Authorization: ***'\n")
+    .run();
+}
+
+#[test]
+fn script_dry_run_is_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret token := 'hunter2'
+
+        foo:
+          #!/usr/bin/env bash
+          echo {{ token }}
+      ",
+    )
+    .arg("--dry-run")
+    .stderr("#!/usr/bin/env bash\necho ***\n")
+    .run();
+}
+
+#[test]
+fn secret_parameters() {
+  Test::new()
+    .justfile(
+      "
+        [secret('password')]
+        login user password:
+          echo {{ user }} {{ password }}
+      ",
+    )
+    .args(["login", "bob", "hunter2"])
+    .stdout("bob hunter2\n")
+    .stderr("echo bob ***\n")
+    .run();
+}
+
+#[test]
+fn variadic_secret_parameters() {
+  Test::new()
+    .justfile(
+      "
+        [secret('tokens')]
+        foo +tokens:
+          echo {{ tokens }}
+      ",
+    )
+    .args(["foo", "hunter2", "swordfish"])
+    .stdout("hunter2 swordfish\n")
+    .stderr("echo ***\n")
+    .run();
+}
+
+#[test]
+fn unknown_secret_parameter() {
+  Test::new()
+    .justfile(
+      "
+        [secret('password')]
+        login user:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `login` has unknown secret parameter `password`
+         ——▶ justfile:2:1
+          │
+        2 │ login user:
+          │ ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn evaluate() {
+  Test::new()
+    .justfile(
+      "
+        secret token := 'hunter2'
+        user := 'bob'
+      ",
+    )
+    .arg("--evaluate")
+    .stdout(
+      "
+        token := \"***\"
+        user  := \"bob\"
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn evaluate_variable() {
+  Test::new()
+    .justfile("secret token := 'hunter2'")
+    .args(["--evaluate", "token"])
+    .stdout("***")
+    .run();
+}
+
+#[test]
+fn overrides_are_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret token := ''
+
+        foo:
+          echo {{ token }}
+      ",
+    )
+    .args(["token=swordfish", "foo"])
+    .stdout("swordfish\n")
+    .stderr("echo ***\n")
+    .run();
+}
+
+#[test]
+fn errors_are_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret token := 'hunter2'
+
+        foo:
+          @echo {{ error('bad token: ' + token) }}
+      ",
+    )
+    .stderr(
+      "
+        error: Call to function `error` failed: ***
+         ——▶ justfile:4:12
+          │
+        4 │   @echo {{ error('bad token: ' + token) }}
+          │            ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        secret token := 'hunter2'
+        export secret key := env('KEY')
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        secret token := '***'
+        export secret key := env('KEY')
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn dump_json() {
+  Test::new()
+    .justfile("secret token := 'hunter2'")
+    .args(["--dump", "--dump-format", "json"])
+    .stdout_regex(r#".*"token":\{"export":false,"name":"token","private":false,"secret":true,"value":"\*\*\*"\}.*\n"#)
+    .run();
+}
+
+#[test]
+fn format_is_not_redacted() {
+  Test::new()
+    .justfile("secret token   := 'hunter2'")
+    .args(["--fmt", "--unstable", "--check"])
+    .stdout(
+      "
+        -secret token   := 'hunter2'
+        +secret token := 'hunter2'
+      ",
+    )
+    .stderr_regex(".*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn plan() {
+  Test::new()
+    .justfile(
+      "
+        [secret('password')]
+        login password:
+      ",
+    )
+    .args(["--plan", "login", "hunter2"])
+    .stdout_regex(r"1\. login password='\*\*\*'  bash -cu  .*\n")
+    .run();
+}
+
+#[test]
+fn other_output_is_not_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret flag := 'e'
+
+        deploy:
+          echo deploy
+      ",
+    )
+    .stdout("deploy\n")
+    .stderr("echo deploy\n")
+    .run();
+}
+
+#[test]
+fn dump_does_not_redact_other_output() {
+  Test::new()
+    .justfile(
+      "
+        secret flag := 'e'
+
+        deploy:
+          echo deploy
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        secret flag := '***'
+
+        deploy:
+            echo deploy
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn derived_assignments_are_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret token := 'hunter2'
+        header := 'Authorization: ' + token
+
+        foo:
+          echo {{ header }}
+      ",
+    )
+    .stdout("Authorization: hunter2\n")
+    .stderr("echo ***\n")
+    .run();
+}
+
+#[test]
+fn event_arguments_are_redacted() {
+  Test::new()
+    .justfile(
+      "
+        [secret('password')]
+        login user password:
+      ",
+    )
+    .args(["--events", "json", "login", "bob", "hunter2"])
+    .stderr_regex(r#"(?s).*"recipe_started".*"arguments":\["bob","\*\*\*"\].*"#)
+    .run();
+}
+
+const DEPENDENCY_ARGUMENT: &str = "
+  secret token := 'hunter2'
+
+  bar: (baz token)
+
+  baz x:
+    echo {{ x }}
+";
+
+#[test]
+fn dependency_arguments_are_redacted() {
+  Test::new()
+    .justfile(DEPENDENCY_ARGUMENT)
+    .arg("bar")
+    .stdout("hunter2\n")
+    .stderr("echo ***\n")
+    .run();
+}
+
+#[test]
+fn dependency_arguments_are_redacted_in_dry_run() {
+  Test::new()
+    .justfile(DEPENDENCY_ARGUMENT)
+    .args(["--dry-run", "bar"])
+    .stderr("echo ***\n")
+    .run();
+}
+
+#[test]
+fn dependency_arguments_are_redacted_in_events() {
+  Test::new()
+    .justfile(DEPENDENCY_ARGUMENT)
+    .args(["--events", "json", "bar"])
+    .stdout("hunter2\n")
+    .stderr_regex(r#"(?s).*"recipe_queued".*"arguments":\["\*\*\*"\].*"#)
+    .run();
+}
+
+#[test]
+fn dependency_arguments_are_redacted_in_plan() {
+  Test::new()
+    .justfile(DEPENDENCY_ARGUMENT)
+    .args(["--plan", "bar"])
+    .stdout_regex(r"2\. bar  .*\n  1\. baz x='\*\*\*'  bash -cu  .*\n")
+    .run();
+}
+
+#[test]
+fn derived_dependency_arguments_are_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret token := 'hunter2'
+
+        bar: (baz 'Bearer ' + token)
+
+        baz x:
+          echo '{{ x }}'
+      ",
+    )
+    .arg("bar")
+    .stdout("Bearer hunter2\n")
+    .stderr("echo '***'\n")
+    .run();
+}
+
+#[test]
+fn secret_parameter_defaults_are_redacted() {
+  Test::new()
+    .justfile(
+      "
+        secret token := 'hunter2'
+
+        baz x=token:
+          echo {{ x }}
+      ",
+    )
+    .args(["--dry-run", "baz"])
+    .stderr("echo ***\n")
+    .run();
+}
@@ -25,7 +25,7 @@ fn shell_expanded_strings_must_not_have_whitespace() {
     .status(1)
     .stderr(
       "
-        error: Expected '&&', '||', comment, end of file, end of line, '(', '+', or '/', but found string
+        error: Expected '&&', '||', '::', comment, end of file, end of line, '(', '+', or '/', but found string
          ——▶ justfile:1:8
           │
         1 │ x := x '$JUST_TEST_VARIABLE'
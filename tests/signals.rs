@@ -102,8 +102,15 @@ fn forwarding() {
   )
   .unwrap();
 
-  for signal in [Signal::SIGINT, Signal::SIGQUIT, Signal::SIGHUP] {
-    let mut child = Command::new(&just)
+  // child processes are in their own process groups, so signals are
+  // forwarded to them, rather than being received from the terminal
+  for signal in [
+    Signal::SIGINT,
+    Signal::SIGQUIT,
+    Signal::SIGHUP,
+    Signal::SIGTERM,
+  ] {
+    let child = Command::new(&just)
       .current_dir(&tempdir)
       .stdout(Stdio::piped())
       .stderr(Stdio::piped())
@@ -113,18 +120,8 @@ fn forwarding() {
     // wait for child to start
     thread::sleep(Duration::from_millis(500));
 
-    // send non-forwarded signal
     kill(&child, signal);
 
-    // wait for child to receive signal
-    thread::sleep(Duration::from_millis(500));
-
-    // assert that child does not exit, because signal is not forwarded
-    assert!(child.try_wait().unwrap().is_none());
-
-    // send forwarded signal
-    kill(&child, Signal::SIGTERM);
-
     // child exits
     let output = child.wait_with_output().unwrap();
 
@@ -139,14 +136,20 @@ fn forwarding() {
       eprintln!("unexpected status: {status}");
     }
 
-    // just reports that it was interrupted by first, non-forwarded signal
-    if stderr != format!("error: Interrupted by {signal}\n") {
+    // signals are sent to the child's process group, so the shell which runs
+    // the recipe line is terminated by the signal as well
+    if stderr
+      != format!(
+        "error: Recipe `foo` was terminated on line 2 by signal {}\n",
+        signal as i32,
+      )
+    {
       failures += 1;
       eprintln!("unexpected stderr: {stderr}");
     }
 
     // child reports that it was terminated by forwarded signal
-    if stdout != r#"{"signal":"SIGTERM"}"# {
+    if stdout != format!(r#"{{"signal":"{}"}}"#, signal.as_str()) {
       failures += 1;
       eprintln!("unexpected stdout: {stdout}");
     }
@@ -47,7 +47,7 @@ fn no_rhs_once() {
     .justfile("x := 'a' /")
     .stderr(
       "
-      error: Expected backtick, identifier, '(', '/', or string, but found end of file
+      error: Expected backtick, '[', identifier, '(', '/', or string, but found end of file
        ——▶ justfile:1:11
         │
       1 │ x := 'a' /
@@ -69,7 +69,7 @@ fn default_un_parenthesized() {
     )
     .stderr(
       "
-      error: Expected '*', ':', '$', identifier, or '+', but found '/'
+      error: Expected '*', ':', '$', identifier, '--', or '+', but found '/'
        ——▶ justfile:1:11
         │
       1 │ foo x='a' / 'b':
@@ -91,7 +91,7 @@ fn no_lhs_un_parenthesized() {
     )
     .stderr(
       "
-      error: Expected backtick, identifier, '(', or string, but found '/'
+      error: Expected backtick, '[', identifier, '(', or string, but found '/'
        ——▶ justfile:1:7
         │
       1 │ foo x=/ 'a' / 'b':
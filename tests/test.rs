@@ -12,6 +12,7 @@ pub(crate) struct Output {
 #[must_use]
 pub(crate) struct Test {
   pub(crate) args: Vec<String>,
+  pub(crate) config_home: TempDir,
   pub(crate) current_dir: PathBuf,
   pub(crate) env: BTreeMap<String, String>,
   pub(crate) expected_files: BTreeMap<PathBuf, Vec<u8>>,
@@ -37,6 +38,7 @@ impl Test {
   pub(crate) fn with_tempdir(tempdir: TempDir) -> Self {
     Self {
       args: Vec::new(),
+      config_home: self::tempdir(),
       current_dir: PathBuf::new(),
       env: BTreeMap::new(),
       expected_files: BTreeMap::new(),
@@ -243,6 +245,7 @@ impl Test {
 
     let mut child = command
       .args(&self.args)
+      .envs(self.isolation())
       .envs(&self.env)
       .current_dir(self.tempdir.path().join(&self.current_dir))
       .stdin(Stdio::piped())
@@ -319,12 +322,22 @@ impl Test {
     }
   }
 
+  /// Environment variables which isolate `just` from the user's config files,
+  /// and from project config files in ancestors of the temporary directory
+  fn isolation(&self) -> [(&str, &OsStr); 2] {
+    [
+      ("JUST_NO_CONFIG", "true".as_ref()),
+      ("XDG_CONFIG_HOME", self.config_home.path().as_os_str()),
+    ]
+  }
+
   fn round_trip(&self) {
     println!("Reparsing...");
 
     let output = Command::new(executable_path("just"))
       .current_dir(self.tempdir.path())
       .arg("--dump")
+      .envs(self.isolation())
       .envs(&self.env)
       .output()
       .expect("just invocation failed");
@@ -347,6 +360,7 @@ impl Test {
       .arg("--justfile")
       .arg(&reparsed_path)
       .arg("--dump")
+      .envs(self.isolation())
       .envs(&self.env)
       .output()
       .expect("just invocation failed");
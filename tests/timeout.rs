@@ -0,0 +1,197 @@
+use super::*;
+
+const TIMED_OUT: &str = "error: Recipe `foo` timed out after ([0-9]+s)?([0-9]+ms)?\n";
+
+#[test]
+fn recipes_which_finish_in_time_succeed() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('10s')]
+        foo:
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn linewise_recipes_time_out() {
+  let start = Instant::now();
+
+  Test::new()
+    .justfile(
+      "
+        [timeout('500ms')]
+        foo:
+          @echo foo
+          @sleep 10
+          @echo bar
+      ",
+    )
+    .stdout("foo\n")
+    .stderr_regex(TIMED_OUT)
+    .status(EXIT_FAILURE)
+    .run();
+
+  assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+#[cfg(unix)]
+fn lines_are_not_started_after_timeout() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('500ms')]
+        foo:
+          echo {{ `sleep 1` }}foo
+      ",
+    )
+    .stderr_regex(TIMED_OUT)
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn timeout_applies_to_whole_recipe_body() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('500ms')]
+        foo:
+          @sleep 0.3
+          @sleep 0.3
+          @echo bar
+      ",
+    )
+    .stderr_regex(TIMED_OUT)
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn script_recipes_time_out() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('500ms')]
+        foo:
+          #!/bin/sh
+          echo foo
+          sleep 10
+          echo bar
+      ",
+    )
+    .stdout("foo\n")
+    .stderr_regex(TIMED_OUT)
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn recipes_which_ignore_sigterm_are_killed() {
+  let start = Instant::now();
+
+  Test::new()
+    .justfile(
+      "
+        [timeout('500ms')]
+        foo:
+          #!/bin/sh
+          trap '' TERM
+          sleep 30
+      ",
+    )
+    .stderr_regex("error: Recipe `foo` timed out after [5-9]s([0-9]+ms)?\n")
+    .status(EXIT_FAILURE)
+    .run();
+
+  assert!(start.elapsed() < Duration::from_secs(15));
+}
+
+#[test]
+#[cfg(unix)]
+fn timeout_flag() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @sleep 10
+      ",
+    )
+    .args(["--timeout", "500ms"])
+    .stderr_regex(TIMED_OUT)
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn attribute_overrides_timeout_flag() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('10s')]
+        foo:
+          @sleep 1
+          @echo foo
+      ",
+    )
+    .args(["--timeout", "500ms"])
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn invalid_timeout_attribute() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('30')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Invalid timeout `30`: expected a duration like `30s`, `5m`, or `1h30m`
+         ——▶ justfile:1:2
+          │
+        1 │ [timeout('30')]
+          │  ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_timeout_flag() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--timeout", "soon"])
+    .stderr_regex(
+      "error: invalid value 'soon' for '--timeout <DURATION>': expected a duration like `30s`, `5m`, or `1h30m`\n.*",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn timeout_attribute_is_dumped() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('30s')]
+        foo:
+      ",
+    )
+    .arg("--dump")
+    .stdout("[timeout('30s')]\nfoo:\n")
+    .run();
+}
@@ -0,0 +1,431 @@
+use super::*;
+
+#[test]
+fn assignment() {
+  Test::new()
+    .justfile(
+      "
+        fn slug(s) := lowercase(replace_regex(s, '[^A-Za-z0-9]+', '-'))
+
+        x := slug('Hello, World')
+
+        foo:
+          @echo {{ x }}
+      ",
+    )
+    .stdout("hello-world\n")
+    .run();
+}
+
+#[test]
+fn interpolation() {
+  Test::new()
+    .justfile(
+      "
+        fn greet(name) := 'hello ' + name
+
+        foo:
+          @echo {{ greet('bob') }}
+      ",
+    )
+    .stdout("hello bob\n")
+    .run();
+}
+
+#[test]
+fn recipe_parameters_and_dependency_arguments() {
+  Test::new()
+    .justfile(
+      "
+        fn double(s) := s + s
+
+        foo x=double('a'): (bar double(x))
+
+        bar y:
+          @echo {{ y }}
+      ",
+    )
+    .stdout("aaaa\n")
+    .run();
+}
+
+#[test]
+fn functions_may_call_other_functions() {
+  Test::new()
+    .justfile(
+      "
+        fn wrap(s, c) := c + s + c
+        fn dquote(s) := wrap(s, '\"')
+
+        foo:
+          @echo '{{ dquote('bar') }}'
+      ",
+    )
+    .stdout("\"bar\"\n")
+    .run();
+}
+
+#[test]
+fn parameters_shadow_variables() {
+  Test::new()
+    .justfile(
+      "
+        x := 'variable'
+
+        fn f(x) := x
+
+        foo:
+          @echo {{ f('parameter') }} {{ x }}
+      ",
+    )
+    .stdout("parameter variable\n")
+    .run();
+}
+
+#[test]
+fn constants_are_available() {
+  Test::new()
+    .justfile(
+      "
+        fn hex(s) := HEXUPPER + s
+
+        foo:
+          @echo {{ hex('') }}
+      ",
+    )
+    .stdout("0123456789ABCDEF\n")
+    .run();
+}
+
+#[test]
+fn imported_functions_are_available() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        fn slug(s) := lowercase(s)
+      ",
+    })
+    .justfile(
+      "
+        import './import.justfile'
+
+        foo:
+          @echo {{ slug('FOO') }}
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn functions_are_available_in_submodules() {
+  Test::new()
+    .tree(tree! {
+      "foo.just": "
+        mod bar
+
+        baz:
+          @echo {{ slug('BAZ') }}
+      ",
+      "bar.just": "
+        qux:
+          @echo {{ slug('QUX') }}
+      ",
+    })
+    .justfile(
+      "
+        mod foo
+
+        fn slug(s) := lowercase(s)
+      ",
+    )
+    .args(["foo::baz", "foo::bar::qux"])
+    .stdout("baz\nqux\n")
+    .run();
+}
+
+#[test]
+fn submodule_functions_shadow_parent_functions() {
+  Test::new()
+    .write(
+      "foo.just",
+      "fn f(s) := uppercase(s)\nbar:\n @echo {{ f('bar') }}",
+    )
+    .justfile(
+      "
+        mod foo
+
+        fn f(s) := lowercase(s)
+      ",
+    )
+    .args(["foo::bar"])
+    .stdout("BAR\n")
+    .run();
+}
+
+#[test]
+fn submodule_functions_are_not_available_in_parent() {
+  Test::new()
+    .write("foo.just", "fn f(s) := s")
+    .justfile(
+      "
+        mod foo
+
+        x := f('a')
+      ",
+    )
+    .stderr(
+      "
+        error: Call to unknown function `f`
+         ——▶ justfile:3:6
+          │
+        3 │ x := f('a')
+          │      ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn functions_call_functions_in_the_module_in_which_they_are_defined() {
+  Test::new()
+    .write(
+      "foo.just",
+      "fn g(s) := uppercase(s)\nbar:\n @echo {{ f('bar') }}",
+    )
+    .justfile(
+      "
+        mod foo
+
+        fn f(s) := g(s)
+        fn g(s) := s + s
+      ",
+    )
+    .args(["foo::bar"])
+    .stdout("barbar\n")
+    .run();
+}
+
+#[test]
+fn submodule_functions_may_be_called_with_module_path() {
+  Test::new()
+    .write("foo.just", "fn f(s) := g(s)\nfn g(s) := uppercase(s)")
+    .justfile(
+      "
+        mod foo
+
+        fn g(s) := s + s
+
+        bar:
+          @echo {{ foo::f('bar') }}
+      ",
+    )
+    .stdout("BAR\n")
+    .run();
+}
+
+#[test]
+fn nested_submodule_functions_may_be_called_with_module_path() {
+  Test::new()
+    .tree(tree! {
+      "foo.just": "mod bar",
+      "bar.just": "fn f(s) := uppercase(s)",
+    })
+    .justfile(
+      "
+        mod foo
+
+        x := foo::bar::f('baz')
+
+        baz:
+          @echo {{ x }}
+      ",
+    )
+    .stdout("BAZ\n")
+    .run();
+}
+
+#[test]
+fn unknown_submodule_function() {
+  Test::new()
+    .write("foo.just", "fn f(s) := s")
+    .justfile(
+      "
+        mod foo
+
+        x := foo::g('a')
+      ",
+    )
+    .stderr(
+      "
+        error: Call to unknown function `g`
+         ——▶ justfile:3:11
+          │
+        3 │ x := foo::g('a')
+          │           ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recursion_through_submodule_is_an_error() {
+  Test::new()
+    .write("foo.just", "fn g(a) := f(a)")
+    .justfile(
+      "
+        mod foo
+
+        fn f(a) := foo::g(a)
+      ",
+    )
+    .stderr(
+      "
+        error: Function `f` has circular call `f -> g -> f`
+         ——▶ foo.just:1:12
+          │
+        1 │ fn g(a) := f(a)
+          │            ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn variables_from_outer_scope_are_undefined() {
+  Test::new()
+    .justfile(
+      "
+        x := 'a'
+
+        fn f(s) := s + x
+      ",
+    )
+    .stderr(
+      "
+        error: Variable `x` not defined
+         ——▶ justfile:3:16
+          │
+        3 │ fn f(s) := s + x
+          │                ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn unknown_function() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo {{ bar('a') }}
+      ",
+    )
+    .stderr(
+      "
+        error: Call to unknown function `bar`
+         ——▶ justfile:2:12
+          │
+        2 │   @echo {{ bar('a') }}
+          │            ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn argument_count_mismatch() {
+  Test::new()
+    .justfile(
+      "
+        fn f(a, b) := a + b
+
+        x := f('a')
+      ",
+    )
+    .stderr(
+      "
+        error: Function `f` called with 1 argument but takes 2
+         ——▶ justfile:3:6
+          │
+        3 │ x := f('a')
+          │      ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recursion_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+        fn f(a) := g(a)
+        fn g(a) := f(a)
+      ",
+    )
+    .stderr(
+      "
+        error: Function `f` has circular call `f -> g -> f`
+         ——▶ justfile:2:12
+          │
+        2 │ fn g(a) := f(a)
+          │            ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn builtin_functions_may_not_be_redefined() {
+  Test::new()
+    .justfile("fn uppercase(s) := s")
+    .stderr(
+      "
+        error: Function `uppercase` redefines a built-in function
+         ——▶ justfile:1:4
+          │
+        1 │ fn uppercase(s) := s
+          │    ^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        fn join3(a, b, c) := a + b + c
+      ",
+    )
+    .arg("--dump")
+    .stdout("fn join3(a, b, c) := a + b + c\n")
+    .run();
+}
+
+#[test]
+fn dump_module_path() {
+  Test::new()
+    .write("foo.just", "fn f(s) := s")
+    .justfile(
+      "
+        mod foo
+
+        x := foo::f('a')
+      ",
+    )
+    .arg("--dump")
+    .stdout("mod foo\n\nx := foo::f('a')\n")
+    .run();
+}
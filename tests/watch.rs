@@ -0,0 +1,293 @@
+use {
+  super::*,
+  nix::{sys::signal::Signal, unistd::Pid},
+  std::process::Child,
+};
+
+struct Watch {
+  child: Child,
+  tempdir: TempDir,
+}
+
+impl Watch {
+  fn new(files: &[(&str, &str)], arguments: &[&str]) -> Self {
+    let tempdir = tempdir();
+
+    for (path, content) in files {
+      fs::write(tempdir.path().join(path), unindent(content)).unwrap();
+    }
+
+    let child = Command::new(executable_path("just"))
+      .current_dir(&tempdir)
+      .arg("--watch")
+      .args(arguments)
+      .stdin(Stdio::null())
+      .stdout(Stdio::null())
+      .stderr(Stdio::null())
+      .spawn()
+      .unwrap();
+
+    Self { child, tempdir }
+  }
+
+  fn expect(&self, expected: &str) {
+    let start = Instant::now();
+
+    loop {
+      let log = fs::read_to_string(self.tempdir.path().join("log")).unwrap_or_default();
+
+      if log == expected {
+        return;
+      }
+
+      assert!(
+        start.elapsed() < Duration::from_secs(10),
+        "expected log {expected:?} but found {log:?}",
+      );
+
+      thread::sleep(Duration::from_millis(10));
+    }
+  }
+
+  fn write(&self, path: &str, content: &str) {
+    let path = self.tempdir.path().join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, unindent(content)).unwrap();
+  }
+}
+
+impl Drop for Watch {
+  fn drop(&mut self) {
+    nix::sys::signal::kill(
+      Pid::from_raw(self.child.id().try_into().unwrap()),
+      Signal::SIGTERM,
+    )
+    .unwrap();
+    self.child.wait().unwrap();
+  }
+}
+
+#[test]
+fn justfile_change_reruns_recipe() {
+  let watch = Watch::new(
+    &[(
+      "justfile",
+      "
+        foo:
+          @echo foo >> log
+      ",
+    )],
+    &[],
+  );
+
+  watch.expect("foo\n");
+
+  watch.write(
+    "justfile",
+    "
+      foo:
+        @echo bar >> log
+    ",
+  );
+
+  watch.expect("foo\nbar\n");
+}
+
+#[test]
+fn import_change_reruns_recipe() {
+  let watch = Watch::new(
+    &[
+      ("justfile", "import 'foo.just'"),
+      (
+        "foo.just",
+        "
+          foo:
+            @echo foo >> log
+        ",
+      ),
+    ],
+    &["foo"],
+  );
+
+  watch.expect("foo\n");
+
+  watch.write(
+    "foo.just",
+    "
+      foo:
+        @echo bar >> log
+    ",
+  );
+
+  watch.expect("foo\nbar\n");
+}
+
+#[test]
+fn module_change_reruns_recipe() {
+  let watch = Watch::new(
+    &[
+      ("justfile", "mod foo"),
+      (
+        "foo.just",
+        "
+          bar:
+            @echo foo >> log
+        ",
+      ),
+    ],
+    &["foo::bar"],
+  );
+
+  watch.expect("foo\n");
+
+  watch.write(
+    "foo.just",
+    "
+      bar:
+        @echo bar >> log
+    ",
+  );
+
+  watch.expect("foo\nbar\n");
+}
+
+#[test]
+fn watch_attribute() {
+  let watch = Watch::new(
+    &[(
+      "justfile",
+      "
+        [watch('src/*.txt')]
+        foo:
+          @echo foo >> log
+      ",
+    )],
+    &[],
+  );
+
+  watch.expect("foo\n");
+
+  watch.write("src/a.txt", "a");
+
+  watch.expect("foo\nfoo\n");
+
+  watch.write("other.txt", "a");
+
+  watch.write("src/a.txt", "b");
+
+  watch.expect("foo\nfoo\nfoo\n");
+}
+
+#[test]
+fn watch_attribute_on_dependency() {
+  let watch = Watch::new(
+    &[(
+      "justfile",
+      "
+        foo: bar
+          @echo foo >> log
+  
+        [watch('*.txt')]
+        bar:
+      ",
+    )],
+    &["foo"],
+  );
+
+  watch.expect("foo\n");
+
+  watch.write("a.txt", "a");
+
+  watch.expect("foo\nfoo\n");
+}
+
+#[test]
+fn running_recipe_is_killed_on_change() {
+  let watch = Watch::new(
+    &[(
+      "justfile",
+      "
+        [watch('*.txt')]
+        foo:
+          @echo start >> log
+          @sleep 30
+          @echo end >> log
+      ",
+    )],
+    &[],
+  );
+
+  watch.expect("start\n");
+
+  watch.write("a.txt", "a");
+
+  watch.expect("start\nstart\n");
+}
+
+#[test]
+fn recipe_ignoring_sigterm_is_killed_on_change() {
+  let watch = Watch::new(
+    &[(
+      "justfile",
+      "
+        [watch('*.txt')]
+        foo:
+          @if [ ! -e log ]; then trap '' TERM; fi; echo start >> log; sleep 30
+      ",
+    )],
+    &[],
+  );
+
+  watch.expect("start\n");
+
+  watch.write("a.txt", "a");
+
+  watch.expect("start\nstart\n");
+}
+
+#[test]
+fn compile_error_is_fixed() {
+  let watch = Watch::new(
+    &[(
+      "justfile",
+      "
+        foo
+      ",
+    )],
+    &[],
+  );
+
+  thread::sleep(Duration::from_millis(100));
+
+  watch.write(
+    "justfile",
+    "
+      foo:
+        @echo foo >> log
+    ",
+  );
+
+  watch.expect("foo\n");
+}
+
+#[test]
+fn invalid_watch_pattern() {
+  Test::new()
+    .justfile(
+      "
+        [watch('[')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Invalid glob pattern `[`: invalid range pattern
+         ——▶ justfile:1:2
+          │
+        1 │ [watch('[')]
+          │  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}